@@ -0,0 +1,53 @@
+/*!
+ * Generates the JSON dictionaries from the raw Whitaker's Words files into OUT_DIR, see src/build_data.
+ * DICTLINE.GEN is not part of the repository, when a raw file is missing the generated JSON kept in src/dictionary
+ * is used instead. Without either the build fails and says what to fetch.
+ */
+#[allow(dead_code)]
+#[path = "src/build_data/mod.rs"]
+mod build_data;
+
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+
+fn main() {
+    let manifest_dir = PathBuf::from(env::var("CARGO_MANIFEST_DIR").unwrap());
+    let out_dir = PathBuf::from(env::var("OUT_DIR").unwrap());
+    let data_dir = manifest_dir.join("Whitakers-Words-Data");
+    let fallback_dir = manifest_dir.join("src").join("dictionary");
+
+    println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rerun-if-changed=src/build_data/mod.rs");
+    println!("cargo:rerun-if-changed=src/build_data/missing_uniques.json");
+    println!("cargo:rerun-if-changed=Whitakers-Words-Data");
+    println!("cargo:rerun-if-changed=src/dictionary");
+
+    let report = build_data::build_data(&data_dir, &out_dir)
+        .unwrap_or_else(|error| panic!("Failed to build the dictionaries: {}", error));
+
+    for (source, outputs) in report.missing {
+        let missing_fallbacks: Vec<&str> = outputs
+            .iter()
+            .copied()
+            .filter(|file| !fallback_dir.join(file).is_file())
+            .collect();
+        if !missing_fallbacks.is_empty() {
+            panic!(
+                "{} was not found and {} are missing from {}. Restore them from git, or copy the raw file from \
+                 the Whitaker's Words data (https://github.com/dsanson/Words) into {} (see Dictionary Data in README.md)",
+                source.display(),
+                missing_fallbacks.join(", "),
+                fallback_dir.display(),
+                data_dir.display()
+            );
+        }
+
+        for file in outputs {
+            let fallback = fallback_dir.join(file);
+            if let Err(error) = fs::copy(&fallback, out_dir.join(file)) {
+                panic!("{} could not be copied: {}", fallback.display(), error);
+            }
+        }
+    }
+}
@@ -0,0 +1,723 @@
+/*!
+ * Parses the raw Whitaker's Words files (DICTLINE.GEN, STEMLIST.GEN, INFLECTS.LAT, ADDONS.LAT and UNIQUES.LAT)
+ * into the JSON dictionaries the translator embeds.
+ *
+ * The output only depends on the input files, so running it twice gives the same bytes.
+ * UNIQUES.LAT is missing some unique words the translator has always had (the forms of sum and unumquodque),
+ * they are kept in missing_uniques.json and added after the parsed records, so the raw files stay as they are upstream.
+ * Every UNIQUES.LAT record is kept, a word can have more than one (quidquid, nonus), lookups use the first.
+ * This module only uses std and serde_json, the build script includes it directly to generate the dictionaries.
+ */
+use serde_json::{json, Value};
+use std::collections::HashSet;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+pub const DICTLINE: &str = "DICTLINE.GEN";
+pub const STEMLIST: &str = "STEMLIST.GEN";
+pub const INFLECTS: &str = "INFLECTS.LAT";
+pub const ADDONS: &str = "ADDONS.LAT";
+pub const UNIQUES: &str = "UNIQUES.LAT";
+
+// unique words that are not in UNIQUES.LAT, in the generated format, see the module docs
+const MISSING_UNIQUES: &str = include_str!("missing_uniques.json");
+
+/**
+ * Every raw file and the JSON files generated from it.
+ * STEMLIST.GEN is built together with DICTLINE.GEN because the stem ids are DICTLINE line numbers.
+ */
+pub const SOURCES: [(&str, &[&str]); 4] = [
+    (
+        DICTLINE,
+        &[
+            "latin_dictionary.json",
+            "english_words.json",
+            "latin_stems.json",
+        ],
+    ),
+    (INFLECTS, &["latin_inflections.json"]),
+    (
+        ADDONS,
+        &[
+            "latin_prefixes.json",
+            "latin_suffixes.json",
+            "latin_packons.json",
+            "latin_not_packons.json",
+            "latin_tackons.json",
+            "latin_tickons.json",
+        ],
+    ),
+    (UNIQUES, &["unique_latin_words.json"]),
+];
+
+// number of form values after the n values for each part of speech in INFLECTS.LAT
+const FORM_LENGTHS: [(&str, usize); 7] = [
+    ("N", 3),      // case, number, gender
+    ("PRON", 3),   // case, number, gender
+    ("SUPINE", 3), // case, number, gender
+    ("ADJ", 4),    // case, number, gender, comparison
+    ("NUM", 4),    // case, number, gender, numeral type
+    ("V", 5),      // tense, voice, mood, person, number
+    ("VPAR", 6),   // case, number, gender, tense, voice, PPL
+];
+
+// the notes are not in INFLECTS.LAT, each belongs to a part of speech and n values, n1 = 0 matches every n value
+const INFLECTION_NOTES: [(&str, i8, i8, &str); 51] = [
+    ("N", 1, 6, "greek"),
+    ("N", 1, 7, "greek"),
+    ("N", 1, 8, "greek"),
+    ("N", 2, 3, "er"),
+    ("N", 2, 4, "ius/ium"),
+    ("N", 2, 5, "ius/ium"),
+    ("N", 2, 6, "greek"),
+    ("N", 2, 7, "greek"),
+    ("N", 2, 8, "greek"),
+    ("N", 2, 9, "greek"),
+    ("N", 3, 2, "stem_ends_in_cons"),
+    ("N", 3, 3, "i-stems_m-f"),
+    ("N", 3, 4, "i-stems_n"),
+    ("N", 3, 6, "greek"),
+    ("N", 3, 7, "greek"),
+    ("N", 3, 8, "greek"),
+    ("N", 3, 9, "greek"),
+    ("N", 4, 2, "u"),
+    ("N", 4, 3, "jesus_jesu"),
+    ("N", 9, 8, "abbreviation"),
+    ("N", 9, 9, "not_declined"),
+    ("ADJ", 1, 2, "first_and_second"),
+    ("ADJ", 1, 3, "nullus_alius"),
+    ("ADJ", 1, 4, "nullus_alius"),
+    ("ADJ", 1, 5, "nullus_alius"),
+    ("ADJ", 2, 0, "greek"),
+    ("ADJ", 2, 1, "greek"),
+    ("ADJ", 2, 2, "greek"),
+    ("ADJ", 2, 3, "greek"),
+    ("ADJ", 2, 6, "greek"),
+    ("ADJ", 2, 7, "greek"),
+    ("ADJ", 2, 8, "greek"),
+    ("ADJ", 3, 2, "two_endings"),
+    ("ADJ", 3, 3, "three_endings"),
+    ("ADJ", 3, 6, "greek"),
+    ("ADJ", 9, 8, "greek"),
+    ("ADJ", 9, 9, "greek"),
+    ("V", 3, 2, "irregular"),
+    ("V", 3, 3, "irregular"),
+    ("V", 5, 0, "like_to_be"),
+    ("V", 5, 1, "like_to_be"),
+    ("V", 5, 2, "like_to_be"),
+    ("V", 6, 1, "eo_ire"),
+    ("V", 6, 2, "volere"),
+    ("V", 7, 1, "defective"),
+    ("V", 7, 2, "defective"),
+    ("V", 7, 3, "defective"),
+    ("VPAR", 0, 0, "participles"),
+    ("SUPINE", 0, 0, "supine"),
+    ("PRON", 0, 0, "pronoun"),
+    ("NUM", 0, 0, "numeral"),
+];
+
+// words that are not worth looking up on their own when indexing the english senses
+const ENGLISH_STOP_WORDS: [&str; 18] = [
+    "a", "an", "the", "to", "of", "and", "or", "be", "in", "on", "at", "by", "for", "with", "from",
+    "as", "is", "w",
+];
+
+/**
+ * The files written by `build_data` and the raw files that were not found.
+ */
+#[derive(Debug, Default)]
+pub struct BuildReport {
+    pub written: Vec<PathBuf>,
+    /// the raw file that was not found and the JSON files that were not generated because of it
+    pub missing: Vec<(PathBuf, Vec<&'static str>)>,
+}
+
+/**
+ * Generates every JSON dictionary it has the raw file for from `input` into `output`.
+ * A missing raw file is not an error, it is listed in the report so the caller can decide what to do.
+ */
+pub fn build_data(input: &Path, output: &Path) -> io::Result<BuildReport> {
+    let mut report = BuildReport::default();
+    fs::create_dir_all(output)?;
+
+    let read = |file: &str| -> io::Result<Option<String>> {
+        match fs::read(input.join(file)) {
+            // the Whitaker's files are latin-1
+            Ok(bytes) => Ok(Some(bytes.iter().map(|byte| *byte as char).collect())),
+            Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(error) => Err(error),
+        }
+    };
+
+    let mut write = |file: &'static str, entries: Vec<Value>| -> io::Result<()> {
+        let path = output.join(file);
+        fs::write(&path, serde_json::to_string(&entries)?)?;
+        report.written.push(path);
+        Ok(())
+    };
+
+    let mut missing = Vec::new();
+    for (source, outputs) in SOURCES {
+        let text = match read(source)? {
+            Some(text) => text,
+            None => {
+                missing.push((input.join(source), outputs.to_vec()));
+                continue;
+            }
+        };
+
+        match source {
+            DICTLINE => {
+                let stemlist = match read(STEMLIST)? {
+                    Some(text) => text,
+                    None => {
+                        missing.push((input.join(STEMLIST), outputs.to_vec()));
+                        continue;
+                    }
+                };
+                let dictionary = parse_dictline(&text)?;
+                write("english_words.json", english_words(&dictionary))?;
+                write("latin_dictionary.json", dictionary)?;
+                write("latin_stems.json", parse_stemlist(&stemlist)?)?;
+            }
+            INFLECTS => write("latin_inflections.json", parse_inflects(&text)?)?,
+            ADDONS => {
+                let addons = parse_addons(&text)?;
+                write("latin_prefixes.json", addons.prefixes)?;
+                write("latin_suffixes.json", addons.suffixes)?;
+                write("latin_packons.json", addons.packons)?;
+                write("latin_not_packons.json", addons.not_packons)?;
+                write("latin_tackons.json", addons.tackons)?;
+                write("latin_tickons.json", addons.tickons)?;
+            }
+            UNIQUES => {
+                let mut uniques = parse_uniques(&text)?;
+                uniques.extend(missing_uniques()?);
+                write("unique_latin_words.json", uniques)?;
+            }
+            _ => {}
+        }
+    }
+
+    report.missing = missing;
+    Ok(report)
+}
+
+fn invalid_line(file: &str, line: usize, message: &str) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidData,
+        format!("{} line {}: {}", file, line, message),
+    )
+}
+
+/**
+ * The characters from `start` to `end` of a fixed column line, shorter lines give an empty or partial column
+ */
+fn column(line: &str, start: usize, end: usize) -> &str {
+    let byte = |column: usize| {
+        line.char_indices()
+            .nth(column)
+            .map_or(line.len(), |(byte, _)| byte)
+    };
+    &line[byte(start)..byte(end)]
+}
+
+fn to_number_or_string(value: &str) -> Value {
+    match value.parse::<i64>() {
+        Ok(number) => json!(number),
+        Err(_) => json!(value),
+    }
+}
+
+/**
+ * The n values are the leading numbers of a form, ex: "3 1 M P" -> [3, 1].
+ * Forms without numbers keep their first value, ex: "ACC" -> ["ACC"], and empty forms are ["X"]
+ */
+fn n_values(form: &str) -> Vec<Value> {
+    let values: Vec<&str> = form.split_whitespace().take(2).collect();
+    match values.first() {
+        None => vec![json!("X")],
+        Some(first) if first.parse::<i64>().is_err() => vec![json!(first)],
+        Some(_) => values
+            .iter()
+            .take_while(|value| value.parse::<i64>().is_ok())
+            .map(|value| to_number_or_string(value))
+            .collect(),
+    }
+}
+
+fn info(values: &[&str], file: &str, line: usize) -> io::Result<Value> {
+    match values {
+        [age, area, geo, freq, source, ..] => Ok(json!({
+            "age": age,
+            "area": area,
+            "geo": geo,
+            "freq": freq,
+            "source": source,
+        })),
+        _ => Err(invalid_line(
+            file,
+            line,
+            "expected age, area, geo, freq and source",
+        )),
+    }
+}
+
+fn split_senses(senses: &str) -> Vec<Value> {
+    let mut senses: Vec<&str> = senses.split(';').map(|sense| sense.trim()).collect();
+    if senses.last() == Some(&"") {
+        senses.pop();
+    }
+    senses.iter().map(|sense| json!(sense)).collect()
+}
+
+/**
+ * DICTLINE.GEN has one entry per line in fixed columns: the stems, part of speech, form, info and senses.
+ * The id of an entry is its line number.
+ */
+pub fn parse_dictline(text: &str) -> io::Result<Vec<Value>> {
+    text.lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(index, line)| {
+            let id = index + 1;
+            let parts: Vec<&str> = column(line, 0, 76).split_whitespace().collect();
+            let orth = match parts.first() {
+                Some(orth) => orth,
+                None => return Err(invalid_line(DICTLINE, id, "the entry has no stems")),
+            };
+
+            let n_column = column(line, 83, 87).trim();
+            let n: Vec<Value> = if n_column.is_empty() {
+                vec![json!("X")]
+            } else {
+                n_column.split(' ').map(to_number_or_string).collect()
+            };
+
+            let form = match column(line, 83, 100).trim() {
+                "" => "X",
+                form => form,
+            };
+
+            let info_values: Vec<&str> = column(line, 100, 110).split_whitespace().collect();
+
+            Ok(json!({
+                "pos": column(line, 76, 83).trim(),
+                "n": n,
+                "parts": parts,
+                "senses": split_senses(column(line, 110, usize::MAX)),
+                "form": form,
+                "info": info(&info_values, DICTLINE, id)?,
+                "orth": orth,
+                "id": id,
+            }))
+        })
+        .collect()
+}
+
+fn frequency_value(freq: &str) -> i64 {
+    match freq {
+        "A" => 70,
+        "B" => 60,
+        "C" => 50,
+        "D" => 40,
+        "E" => 30,
+        "F" => 20,
+        _ => 0,
+    }
+}
+
+/**
+ * Removes the text in (), [] and {}, it explains a meaning instead of being one
+ */
+fn remove_brackets(sense: &str) -> String {
+    let mut depth = 0;
+    let mut result = String::new();
+    for c in sense.chars() {
+        match c {
+            '(' | '[' | '{' => depth += 1,
+            ')' | ']' | '}' => depth = 0.max(depth - 1),
+            _ if depth == 0 => result.push(c),
+            _ => {}
+        }
+    }
+    result
+}
+
+/**
+ * Builds the English to Latin index from the senses of the dictionary entries, like Whitaker's EWDSLIST.
+ * Every comma separated meaning of a sense is split into words, a meaning that is a single word is a better match,
+ * so it gets a compound value of 10. semi is the position of the sense, starting at 1.
+ */
+pub fn english_words(dictionary: &[Value]) -> Vec<Value> {
+    let mut english_words: Vec<Value> = Vec::new();
+
+    for entry in dictionary {
+        let wid = &entry["id"];
+        let pos = &entry["pos"];
+        let freq = entry["info"]["freq"].as_str().unwrap_or("X");
+        let senses = entry["senses"].as_array().cloned().unwrap_or_default();
+        let mut seen: HashSet<String> = HashSet::new();
+
+        for (semi, sense) in senses.iter().enumerate() {
+            let sense = remove_brackets(sense.as_str().unwrap_or_default());
+            for meaning in sense.split(',') {
+                let words: Vec<String> = meaning
+                    .split(|c: char| c.is_whitespace() || c == '/')
+                    .map(|word| {
+                        word.trim_matches(|c: char| !c.is_ascii_alphabetic())
+                            .to_lowercase()
+                    })
+                    .filter(|word| {
+                        !word.is_empty()
+                            && word
+                                .chars()
+                                .all(|c| c.is_ascii_alphabetic() || c == '-' || c == '\'')
+                    })
+                    .collect();
+                let compound = if words.len() == 1 { 10 } else { 0 };
+
+                for word in words {
+                    if ENGLISH_STOP_WORDS.contains(&word.as_str()) || !seen.insert(word.clone()) {
+                        continue;
+                    }
+
+                    english_words.push(json!({
+                        "orth": word,
+                        "wid": wid,
+                        "pos": pos,
+                        "frequency_type": freq,
+                        "frequency": frequency_value(freq),
+                        "compound": compound,
+                        "semi": semi + 1,
+                    }));
+                }
+            }
+        }
+    }
+
+    // stable, so the entries of a word stay in dictionary order
+    english_words.sort_by(|a, b| a["orth"].as_str().cmp(&b["orth"].as_str()));
+    english_words
+}
+
+/**
+ * STEMLIST.GEN has one stem per line: the stem in the first 19 columns,
+ * then the part of speech, form, stem key and the id of the DICTLINE entry.
+ */
+pub fn parse_stemlist(text: &str) -> io::Result<Vec<Value>> {
+    text.lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(index, line)| {
+            let orth = column(line, 0, 19).trim();
+            let values: Vec<&str> = column(line, 19, usize::MAX).split_whitespace().collect();
+            if values.len() < 3 {
+                return Err(invalid_line(
+                    STEMLIST,
+                    index + 1,
+                    "expected a part of speech, stem key and id",
+                ));
+            }
+
+            let pos = values[0];
+            let form = values[1..values.len() - 2].join(" ");
+            let wid: i64 = values[values.len() - 1]
+                .parse()
+                .map_err(|_| invalid_line(STEMLIST, index + 1, "the id is not a number"))?;
+
+            Ok(json!({
+                "pos": pos,
+                "form": if form.is_empty() { "X" } else { &form },
+                "orth": orth,
+                "n": n_values(&form),
+                "wid": wid,
+            }))
+        })
+        .collect()
+}
+
+fn inflection_note(pos: &str, n: &[Value]) -> &'static str {
+    let n = |index: usize| n.get(index).and_then(Value::as_i64).unwrap_or(-1);
+    INFLECTION_NOTES
+        .iter()
+        .find(|(note_pos, n1, n2, _)| {
+            *note_pos == pos && (*n1 == 0 || (n(0) == *n1 as i64 && n(1) == *n2 as i64))
+        })
+        .map(|(_, _, _, note)| *note)
+        .unwrap_or("")
+}
+
+/**
+ * INFLECTS.LAT lines are `POS N1 N2 FORM... STEM_KEY ENDING_LENGTH ENDING AGE FREQ`,
+ * words without endings (ADV, PREP, CONJ, INTERJ) are `POS [FORM] STEM_KEY 0 AGE FREQ`.
+ * Everything after "--" is a comment.
+ */
+pub fn parse_inflects(text: &str) -> io::Result<Vec<Value>> {
+    let mut inflections = Vec::new();
+
+    for (index, line) in text.lines().enumerate() {
+        let invalid = |message| invalid_line(INFLECTS, index + 1, message);
+        let values: Vec<&str> = line
+            .split("--")
+            .next()
+            .unwrap_or_default()
+            .split_whitespace()
+            .collect();
+        if values.len() < 3 {
+            if values.is_empty() {
+                continue;
+            }
+            return Err(invalid("expected a part of speech, age and frequency"));
+        }
+
+        let pos = values[0];
+        // the last two values are always the age and frequency
+        let (age, freq) = (values[values.len() - 2], values[values.len() - 1]);
+        let values = &values[1..values.len() - 2];
+        let parse_key = |value: &str| {
+            value
+                .parse::<i64>()
+                .map_err(|_| invalid("expected a number"))
+        };
+
+        let (n, form, stem_key, ending) =
+            match FORM_LENGTHS.iter().find(|(form_pos, _)| *form_pos == pos) {
+                Some((_, form_length)) => {
+                    if values.len() < 4 + form_length {
+                        return Err(invalid("the inflection is missing values"));
+                    }
+                    let rest = &values[2 + form_length..];
+                    let ending = if parse_key(rest[1])? > 0 {
+                        rest.get(2).copied().unwrap_or_default()
+                    } else {
+                        ""
+                    };
+                    (
+                        vec![
+                            to_number_or_string(values[0]),
+                            to_number_or_string(values[1]),
+                        ],
+                        values[2..2 + form_length].join(" "),
+                        parse_key(rest[0])?,
+                        ending,
+                    )
+                }
+                None => {
+                    if values.len() < 2 {
+                        return Err(invalid("the inflection is missing values"));
+                    }
+                    (
+                        values
+                            .iter()
+                            .map(|value| to_number_or_string(value))
+                            .collect(),
+                        values.join(" "),
+                        parse_key(values[values.len() - 2])?,
+                        "",
+                    )
+                }
+            };
+
+        inflections.push(json!({
+            "ending": ending,
+            "pos": pos,
+            "note": inflection_note(pos, &n),
+            "n": n,
+            "form": form,
+            "stem_key": stem_key,
+            "age": age,
+            "freq": freq,
+        }));
+    }
+
+    Ok(inflections)
+}
+
+/**
+ * The entries of ADDONS.LAT, split the way the translator uses them
+ */
+#[derive(Debug, Default)]
+pub struct Addons {
+    pub prefixes: Vec<Value>,
+    pub suffixes: Vec<Value>,
+    pub packons: Vec<Value>,
+    pub not_packons: Vec<Value>,
+    pub tackons: Vec<Value>,
+    pub tickons: Vec<Value>,
+}
+
+/**
+ * The meaning line of an addon, a trailing note after the last ';' is put in brackets, ex: "or; rare" -> "or; (rare)"
+ */
+fn addon_senses(line: &str) -> Vec<Value> {
+    let line = line.trim();
+    let senses = match line.rfind(';') {
+        Some(end) if !line[end + 1..].trim().is_empty() => {
+            format!("{} ({})", &line[..=end], line[end + 1..].trim())
+        }
+        _ => line.to_string(),
+    };
+    vec![json!(senses)]
+}
+
+/**
+ * ADDONS.LAT records are three lines: `KIND orth [connect]`, the parts of speech and form, and the meaning.
+ * The connecting character of a prefix is the letter the word must continue with (ac + c: accurro),
+ * for a suffix it is the letter the stem must end with.
+ * - PREFIX records with a "PACK PACK" form are tickons, prefixes for the qui/quis pronouns
+ * - TACKON records with a "PACK" form are packons, the endings of the qui/quis pronouns
+ * - TACKON records whose meaning starts with "-orth =" are the enclitics (que, ne, ve, est)
+ * - the other TACKON records are kept once per orth
+ */
+pub fn parse_addons(text: &str) -> io::Result<Addons> {
+    let lines: Vec<(usize, &str)> = text
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim_start().starts_with("--") && !line.trim().is_empty())
+        .collect();
+
+    let mut addons = Addons::default();
+    let mut not_packon_orths: HashSet<String> = HashSet::new();
+
+    for record in lines.chunks(3) {
+        let (line_number, header) = record[0];
+        let (form, senses) = match record {
+            [_, (_, form), (_, senses)] => (form.trim(), *senses),
+            _ => return Err(invalid_line(ADDONS, line_number + 1, "incomplete record")),
+        };
+
+        // `KIND orth [connect] [-- comment]`
+        let mut header = header
+            .split("--")
+            .next()
+            .unwrap_or_default()
+            .split_whitespace();
+        let kind = header.next().unwrap_or_default();
+        let orth = header.next().unwrap_or_default().to_string();
+        let connect = header.next();
+        let pos = form.split_whitespace().next().unwrap_or("X");
+
+        match kind {
+            "PREFIX" if form.split_whitespace().eq(["PACK", "PACK"]) => {
+                addons.tickons.push(json!({
+                    "orth": orth,
+                    "pos": "PACK PACK",
+                    "senses": addon_senses(senses),
+                }));
+            }
+            "PREFIX" | "SUFFIX" => {
+                let mut entry = json!({
+                    "pos": pos,
+                    "form": form,
+                    "senses": addon_senses(senses),
+                    "orth": orth,
+                });
+                if let Some(connect) = connect {
+                    entry["connect"] = json!(connect);
+                }
+                if kind == "PREFIX" {
+                    addons.prefixes.push(entry);
+                } else {
+                    addons.suffixes.push(entry);
+                }
+            }
+            "TACKON" => {
+                let entry = json!({
+                    "orth": orth,
+                    "pos": form,
+                    "senses": addon_senses(senses),
+                });
+                if pos == "PACK" {
+                    addons.packons.push(entry);
+                } else if senses.trim_start().starts_with(&format!("-{} =", orth)) {
+                    addons.tackons.push(entry);
+                } else if not_packon_orths.insert(orth) {
+                    addons.not_packons.push(entry);
+                }
+            }
+            _ => {
+                return Err(invalid_line(
+                    ADDONS,
+                    line_number + 1,
+                    "expected PREFIX, SUFFIX or TACKON",
+                ))
+            }
+        }
+    }
+
+    Ok(addons)
+}
+
+/**
+ * The unique words added to the ones from UNIQUES.LAT
+ */
+pub fn missing_uniques() -> io::Result<Vec<Value>> {
+    serde_json::from_str(MISSING_UNIQUES)
+        .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))
+}
+
+/**
+ * UNIQUES.LAT records are three lines: the word, the part of speech, form and info in fixed columns, and the senses
+ */
+pub fn parse_uniques(text: &str) -> io::Result<Vec<Value>> {
+    let lines: Vec<(usize, &str)> = text
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .collect();
+
+    lines
+        .chunks(3)
+        .map(|record| {
+            let (line_number, orth) = record[0];
+            let (line, senses) = match record {
+                [_, (_, line), (_, senses)] => (*line, *senses),
+                _ => return Err(invalid_line(UNIQUES, line_number + 1, "incomplete record")),
+            };
+
+            let form = column(line, 6, 52)
+                .split_whitespace()
+                .collect::<Vec<&str>>()
+                .join(" ");
+            let info_values: Vec<&str> = column(line, 52, usize::MAX).split_whitespace().collect();
+
+            Ok(json!({
+                "orth": orth.trim(),
+                "senses": split_senses(senses),
+                "pos": column(line, 0, 6).trim(),
+                "form": form,
+                "n": n_values(&form),
+                "info": info(&info_values, UNIQUES, line_number + 2)?,
+            }))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_every_unique_record() {
+        let text = "nonus\nN     3 3 NOM P M T                                 X  X  X  X  O\nnine asses (money); (contracted form of nonnussis);\nnonus\nN     3 3 ACC P M T                                 X  X  X  X  O\nnine asses (money); (contracted form of nonnussis);\n";
+        let uniques = parse_uniques(text).unwrap();
+
+        assert_eq!(uniques.len(), 2);
+        assert_eq!(uniques[0]["orth"], "nonus");
+        assert_eq!(uniques[0]["pos"], "N");
+        assert_eq!(uniques[1]["form"], "3 3 ACC P M T");
+        assert_eq!(uniques[1]["n"], json!([3, 3]));
+        assert_eq!(
+            uniques[1]["senses"],
+            json!(["nine asses (money)", "(contracted form of nonnussis)"])
+        );
+        assert_eq!(uniques[1]["info"]["source"], "O");
+    }
+
+    #[test]
+    fn rejects_incomplete_unique_records() {
+        assert!(parse_uniques("nonus\nN     3 3 NOM P M T   X  X  X  X  O\n").is_err());
+    }
+}
@@ -1,5 +1,7 @@
 use std::env;
 
+use vocab_vault::VocabError;
+
 #[derive(Debug, Clone)]
 pub struct Arg {
     pub name: &'static str,
@@ -29,6 +31,12 @@ pub struct Cli<'a> {
     pub default_command: Option<String>,
 }
 
+impl Default for Arg {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Arg {
     pub fn new() -> Arg {
         Arg {
@@ -101,7 +109,7 @@ impl<'a> Command<'a> {
     /**
      * Adds arguments to the command
      */
-    pub fn with_args(mut self, args: &Vec<Arg>) -> Command<'a> {
+    pub fn with_args(mut self, args: &[Arg]) -> Command<'a> {
         if self.args.is_none() {
             self.args = Some(vec![]);
         }
@@ -110,25 +118,35 @@ impl<'a> Command<'a> {
     }
 
     /**
-     * Checks if the required arguments are present
+     * Checks that every flag present has the flags it requires
      */
-    fn check_if_required_args_are_present(&self, env_args: &Vec<String>, arg: &Arg) {
-        for required in &arg.requires {
-            let required_arg = self.find_arg(required).unwrap();
-            if !env_args
-                .iter()
-                .any(|s| *s == format!("-{}", required_arg.short.unwrap()))
-                && !env_args
-                    .iter()
-                    .any(|s| *s == format!("--{}", required_arg.long.unwrap()))
-            {
-                println!(
-                    "The argument \"{}\" requires the argument \"{}\"",
-                    arg.name, required
-                );
-                std::process::exit(0);
+    pub fn check_required_args(&self) -> Result<(), VocabError> {
+        let env_args: Vec<String> = env::args().collect();
+        let args = match &self.args {
+            Some(args) => args,
+            None => return Ok(()),
+        };
+
+        for arg in args {
+            if !is_flag_present(&env_args, arg) {
+                continue;
+            }
+
+            for required in &arg.requires {
+                let required_present = self
+                    .find_arg(required)
+                    .is_some_and(|required_arg| is_flag_present(&env_args, required_arg));
+
+                if !required_present {
+                    return Err(VocabError::MissingRequiredArgument {
+                        name: arg.name.to_string(),
+                        requires: required.to_string(),
+                    });
+                }
             }
         }
+
+        Ok(())
     }
 
     fn find_arg(&self, arg_name: &'static str) -> Option<&Arg> {
@@ -151,11 +169,18 @@ impl<'a> Command<'a> {
                 break;
             }
             value.push_str(arg);
-            value.push_str(" ");
+            value.push(' ');
         }
         ArgValue::Present(value.trim().to_string())
     }
 
+    /**
+     * If --help follows the command, its help should be printed instead of running it
+     */
+    pub fn asks_for_help(&self) -> bool {
+        env::args().skip(2).any(|arg| arg == "--help")
+    }
+
     /**
      * Check if a flag is present
      */
@@ -165,16 +190,7 @@ impl<'a> Command<'a> {
             .and_then(|args| args.iter().find(|&arg| arg.name == arg_name))
             .map(|arg| {
                 let args: Vec<String> = env::args().collect();
-                let found = args.iter().any(|s| {
-                    *s == format!("-{}", arg.short.unwrap())
-                        || *s == format!("--{}", arg.long.unwrap())
-                });
-
-                if found {
-                    self.check_if_required_args_are_present(&args, arg);
-                }
-
-                found
+                is_flag_present(&args, arg)
             })
             .unwrap_or(false)
     }
@@ -194,15 +210,19 @@ impl<'a> Command<'a> {
                 });
 
                 let value = arg_index.and_then(|index| args.get(index + 1));
-                value
-                    .or_else(|| arg.default.as_ref())
-                    .map(|s| s.to_string())
+                value.or(arg.default.as_ref()).map(|s| s.to_string())
             })
-            .map(|value| ArgValue::Present(value))
+            .map(ArgValue::Present)
             .unwrap_or(ArgValue::Missing(arg_name.to_string()))
     }
 }
 
+impl<'a> Default for Cli<'a> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl<'a> Cli<'a> {
     pub fn new() -> Cli<'a> {
         Cli {
@@ -247,7 +267,7 @@ impl<'a> Cli<'a> {
         self
     }
 
-    pub fn with_commands(mut self, commands: Vec<Command<'a>>) -> Cli {
+    pub fn with_commands(mut self, commands: Vec<Command<'a>>) -> Cli<'a> {
         self.commands = commands;
         self
     }
@@ -264,22 +284,16 @@ impl<'a> Cli<'a> {
         self
     }
 
-    pub fn match_commands(&self) -> &Command<'a> {
+    pub fn match_commands(&self) -> Result<&Command<'a>, VocabError> {
         let args: Vec<String> = env::args().collect();
         if args.len() <= 1 {
-            if self.default_command.is_some() {
+            if let Some(default_command) = &self.default_command {
                 self.commands
                     .iter()
-                    .find(|&command| command.name == self.default_command.as_ref().unwrap())
-                    .unwrap_or_else(|| {
-                        println!(
-                            "Failed to find set default command: {}",
-                            self.default_command.as_ref().unwrap()
-                        );
-                        std::process::exit(0);
-                    })
+                    .find(|&command| command.name == default_command)
+                    .ok_or_else(|| VocabError::CommandNotFound(default_command.to_string()))
             } else {
-                self.help(None);
+                self.help(None)?;
                 std::process::exit(0);
             }
         } else {
@@ -287,10 +301,7 @@ impl<'a> Cli<'a> {
             self.commands
                 .iter()
                 .find(|&command| command.name == command_name)
-                .unwrap_or_else(|| {
-                    println!("Command not found: {}", command_name);
-                    std::process::exit(0);
-                })
+                .ok_or_else(|| VocabError::CommandNotFound(command_name.to_string()))
         }
     }
 
@@ -298,7 +309,7 @@ impl<'a> Cli<'a> {
         println!("{} {}", self.name, self.version);
     }
 
-    pub fn help(&self, command_name: Option<String>) {
+    pub fn help(&self, command_name: Option<String>) -> Result<(), VocabError> {
         println!("{} {}", self.name, self.version);
         println!("{}", self.description);
         println!("Author: {}", self.author);
@@ -308,23 +319,21 @@ impl<'a> Cli<'a> {
         println!("    {} [COMMAND] [OPTIONS]", self.bin);
         println!();
         println!("COMMANDS:");
-        if command_name.is_some() {
+        if let Some(command_name) = command_name {
             let command = self
                 .commands
                 .iter()
-                .find(|&command| command.name == command_name.as_ref().unwrap())
-                .unwrap_or_else(|| {
-                    println!("Command not found: {}", command_name.unwrap());
-                    std::process::exit(0);
-                });
+                .find(|&command| command.name == command_name)
+                .ok_or(VocabError::CommandNotFound(command_name))?;
 
             self.command_help(command)
         } else {
             for command in &self.commands {
-                self.command_help(&command)
+                self.command_help(command)
             }
         }
         println!();
+        Ok(())
     }
 
     fn command_help(&self, command: &Command<'a>) {
@@ -356,6 +365,16 @@ impl<'a> Cli<'a> {
     }
 }
 
+/**
+ * Checks if the short or long version of a flag is in the args
+ */
+fn is_flag_present(env_args: &[String], arg: &Arg) -> bool {
+    env_args.iter().any(|s| {
+        arg.short.is_some_and(|short| *s == format!("-{}", short))
+            || arg.long.is_some_and(|long| *s == format!("--{}", long))
+    })
+}
+
 #[derive(Debug)]
 pub enum ArgValue {
     Missing(String),
@@ -363,13 +382,10 @@ pub enum ArgValue {
 }
 
 impl ArgValue {
-    pub fn throw_if_none(&self) -> String {
+    pub fn throw_if_none(&self) -> Result<String, VocabError> {
         match self {
-            ArgValue::Missing(name) => {
-                println!("Missing required argument: {}", name);
-                std::process::exit(0);
-            }
-            ArgValue::Present(value) => value.to_string(),
+            ArgValue::Missing(name) => Err(VocabError::MissingArgument(name.to_string())),
+            ArgValue::Present(value) => Ok(value.to_string()),
         }
     }
 
@@ -28,7 +28,7 @@ impl Comparison {
     }
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone, Copy)]
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
 pub enum Declension {
     Nominative,
     Genitive,
@@ -213,7 +213,7 @@ impl Numeral {
     }
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone, Copy)]
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
 pub enum Number {
     Singular,
     Plural,
@@ -288,18 +288,19 @@ impl PartOfSpeech {
         }
     }
 
-    pub fn convert_to_generator(&self) -> crate::utils::principle_part_generator::Generator {
+    pub fn convert_to_generator(
+        &self,
+    ) -> Result<crate::utils::principle_part_generator::Generator, crate::error::VocabError> {
         match &self {
-            PartOfSpeech::Noun => crate::utils::principle_part_generator::Generator::Noun,
-            PartOfSpeech::Pronoun => crate::utils::principle_part_generator::Generator::Pronoun,
-            PartOfSpeech::Adjective => crate::utils::principle_part_generator::Generator::Adjective,
-            PartOfSpeech::Verb => crate::utils::principle_part_generator::Generator::Verb,
-            PartOfSpeech::Participle => crate::utils::principle_part_generator::Generator::Verb,
-            PartOfSpeech::Numeral => crate::utils::principle_part_generator::Generator::Numeral,
-            _ => {
-                println!("Cannot convert {:?} to a generator", self);
-                std::process::exit(0);
+            PartOfSpeech::Noun => Ok(crate::utils::principle_part_generator::Generator::Noun),
+            PartOfSpeech::Pronoun => Ok(crate::utils::principle_part_generator::Generator::Pronoun),
+            PartOfSpeech::Adjective => {
+                Ok(crate::utils::principle_part_generator::Generator::Adjective)
             }
+            PartOfSpeech::Verb => Ok(crate::utils::principle_part_generator::Generator::Verb),
+            PartOfSpeech::Participle => Ok(crate::utils::principle_part_generator::Generator::Verb),
+            PartOfSpeech::Numeral => Ok(crate::utils::principle_part_generator::Generator::Numeral),
+            _ => Err(crate::error::VocabError::NoGenerator(*self)),
         }
     }
 
@@ -533,6 +534,24 @@ impl Age {
             _ => Age::Unknown,
         }
     }
+
+    /**
+     * The letter the dictionary files use for the age, see `dict_key_to_age`
+     */
+    pub fn as_dict_key(&self) -> &'static str {
+        match &self {
+            Age::Archaic => "A",
+            Age::Early => "B",
+            Age::Classical => "C",
+            Age::Late => "D",
+            Age::Later => "E",
+            Age::Medieval => "F",
+            Age::Scholar => "G",
+            Age::Modern => "H",
+            Age::UsedThroughoutAges => "X",
+            Age::Unknown => "",
+        }
+    }
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, Copy)]
@@ -726,6 +745,25 @@ impl Frequency {
             _ => Frequency::Unknown,
         }
     }
+
+    /**
+     * The letter the dictionary files use for the frequency, see `dict_key_to_frequency`
+     */
+    pub fn as_dict_key(&self) -> &'static str {
+        match &self {
+            Frequency::VeryFrequent => "A",
+            Frequency::Frequent => "B",
+            Frequency::Common => "C",
+            Frequency::Lesser => "D",
+            Frequency::Uncommon => "E",
+            Frequency::VeryRare => "F",
+            Frequency::Inscription => "I",
+            Frequency::Graffiti => "M",
+            Frequency::Pliny => "N",
+            Frequency::AllOrNone => "X",
+            Frequency::Unknown => "",
+        }
+    }
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, Copy)]
@@ -1,14 +1,58 @@
+use serde::de::{self, DeserializeOwned};
 use serde::{Deserialize, Deserializer, Serialize};
 
 use crate::dictionary_structures::dictionary_keys::{
     Age, Area, Comparison, Declension, Frequency, Gender, Geography, Mood, Noun, Number, Numeral,
     PartOfSpeech, Pronoun, Source, Tense, Verb, Voice,
 };
+use crate::error::VocabError;
 use crate::translators::Structure;
 use crate::utils::number_with_ending;
 use crate::utils::principle_part_generator::{generate_principle_parts, Generator};
 use crate::utils::type_translator::translate_type;
-// We know that the dictionary data, and that all the values are the types they should be, so we can unwrap without worry.
+
+type JsonMap = serde_json::Map<String, serde_json::Value>;
+
+fn take_field<E: de::Error>(
+    map: &mut JsonMap,
+    field: &'static str,
+) -> Result<serde_json::Value, E> {
+    map.remove(field).ok_or_else(|| E::missing_field(field))
+}
+
+fn from_field<T: DeserializeOwned, E: de::Error>(
+    map: &mut JsonMap,
+    field: &'static str,
+) -> Result<T, E> {
+    serde_json::from_value(take_field(map, field)?).map_err(E::custom)
+}
+
+fn parse_number<T: std::str::FromStr, E: de::Error>(value: &serde_json::Value) -> Result<T, E> {
+    value
+        .to_string()
+        .parse()
+        .map_err(|_| E::custom(format!("expected a number, found {}", value)))
+}
+
+// n is a list of values, so it can be a string, an integer, or a list of integers
+fn parse_n_values<E: de::Error>(n_value: serde_json::Value) -> Result<Option<Vec<NValue>>, E> {
+    match n_value {
+        serde_json::Value::Array(n) => n
+            .iter()
+            .map(|n| {
+                if n.is_array() {
+                    Ok(NValue::IntInt(parse_number(&n[0])?, parse_number(&n[1])?))
+                } else if let Some(s) = n.as_str() {
+                    Ok(NValue::String(s.to_string()))
+                } else {
+                    Ok(NValue::Integer(parse_number(n)?))
+                }
+            })
+            .collect::<Result<Vec<NValue>, E>>()
+            .map(Some),
+        _ => Ok(None),
+    }
+}
 
 pub enum Part {
     First,
@@ -31,6 +75,12 @@ pub struct LatinWordInfo {
     pub extension_senses: Option<Vec<String>>,
 }
 
+impl Default for LatinWordInfo {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl LatinWordInfo {
     pub fn new() -> LatinWordInfo {
         LatinWordInfo {
@@ -53,20 +103,20 @@ impl LatinWordInfo {
         }
     }
 
-    pub fn generate_principle_parts(&mut self) {
+    pub fn generate_principle_parts(&mut self) -> Result<(), VocabError> {
         let parts = &self.parts;
 
         let n = match &self.n {
             Some(n) => n,
-            None => return,
+            None => return Ok(()),
         };
 
         if parts.is_empty() || n.len() < 2 {
-            return;
+            return Ok(());
         }
 
         let n_value_1 = match &self.n {
-            Some(n) => n.get(0).unwrap().get_n_value_1(),
+            Some(n) => n.first().unwrap().get_n_value_1(),
             None => 0,
         };
 
@@ -81,7 +131,7 @@ impl LatinWordInfo {
                     Form::LongForm(form) => form.gender.unwrap_or(Gender::Unknown),
                     Form::StrForm(form) => {
                         let form_array = form.split_whitespace().collect::<Vec<&str>>();
-                        if form_array.len() < 2 {
+                        if form_array.len() < 3 {
                             Gender::Unknown
                         } else {
                             Gender::dict_key_to_gender(form_array[2])
@@ -97,7 +147,7 @@ impl LatinWordInfo {
                     None,
                     None,
                     None,
-                );
+                )?;
                 self.orth = new_parts[0].to_string();
                 self.parts = new_parts;
             }
@@ -106,7 +156,7 @@ impl LatinWordInfo {
                     Form::LongForm(form) => form.verb.unwrap_or(Verb::Unknown),
                     Form::StrForm(form) => {
                         let form_array = form.split_whitespace().collect::<Vec<&str>>();
-                        if form_array.len() < 2 {
+                        if form_array.len() < 3 {
                             Verb::Unknown
                         } else {
                             Verb::dict_key_to_verb(form_array[2])
@@ -122,7 +172,7 @@ impl LatinWordInfo {
                     None,
                     Some(verb_type),
                     None,
-                );
+                )?;
                 self.orth = new_parts[0].to_string();
                 self.parts = new_parts;
             }
@@ -131,7 +181,7 @@ impl LatinWordInfo {
                     Form::LongForm(form) => form.comparison.unwrap_or(Comparison::Unknown),
                     Form::StrForm(form) => {
                         let form_array = form.split_whitespace().collect::<Vec<&str>>();
-                        if form_array.len() < 2 {
+                        if form_array.len() < 3 {
                             Comparison::Unknown
                         } else {
                             Comparison::dict_key_to_comparison(form_array[2])
@@ -147,7 +197,7 @@ impl LatinWordInfo {
                     Some(comparison),
                     None,
                     None,
-                );
+                )?;
                 self.orth = new_parts[0].to_string();
                 self.parts = new_parts;
             }
@@ -161,7 +211,7 @@ impl LatinWordInfo {
                     None,
                     None,
                     None,
-                );
+                )?;
                 self.orth = new_parts[0].to_string();
                 self.parts = new_parts;
             }
@@ -170,7 +220,7 @@ impl LatinWordInfo {
                     Form::LongForm(form) => form.numeral.unwrap_or(Numeral::Unknown),
                     Form::StrForm(form) => {
                         let form_array = form.split_whitespace().collect::<Vec<&str>>();
-                        if form_array.len() < 2 {
+                        if form_array.len() < 3 {
                             Numeral::Unknown
                         } else {
                             Numeral::dict_key_to_numeral(form_array[2])
@@ -186,12 +236,14 @@ impl LatinWordInfo {
                     None,
                     None,
                     Some(numeral_type),
-                );
+                )?;
                 self.orth = new_parts[0].to_string();
                 self.parts = new_parts;
             }
             _ => {}
         }
+
+        Ok(())
     }
 
     pub fn set_word(&mut self, latin_word_info: &LatinWordInfo) {
@@ -209,7 +261,7 @@ impl LatinWordInfo {
 
     pub fn get_part(&self, part: Part) -> Option<String> {
         match part {
-            Part::First => self.parts.get(0).map(|s| s.to_string()),
+            Part::First => self.parts.first().map(|s| s.to_string()),
             Part::Second => self.parts.get(1).map(|s| s.to_string()),
             Part::Third => self.parts.get(2).map(|s| s.to_string()),
             Part::Fourth => self.parts.get(3).map(|s| s.to_string()),
@@ -356,42 +408,15 @@ impl<'de> Deserialize<'de> for LatinWordInfo {
     where
         D: Deserializer<'de>,
     {
-        let mut map = serde_json::Map::deserialize(deserializer)?;
-        let orth = serde_json::from_value(map.remove("orth").unwrap())
-            .expect("Failed to deserialize orth");
-        let parts = serde_json::from_value(map.remove("parts").unwrap())
-            .expect("Failed to deserialize parts");
-        let senses: Vec<String> = serde_json::from_value(map.remove("senses").unwrap())
-            .expect("Failed to deserialize senses");
-        let pos = map.remove("pos").unwrap().to_string();
-        let form = serde_json::from_value(map.remove("form").unwrap())
-            .expect("Failed to deserialize form");
-        let info = map.remove("info").unwrap().to_string();
-        // n is a list of values, so it can be a string, an integer, or a list of integers
-
-        let n_value =
-            serde_json::from_value(map.remove("n").unwrap()).expect("Failed to deserialize n");
-        let n = match n_value {
-            serde_json::Value::Array(n) => Some(
-                n.iter()
-                    .map(|n| {
-                        if n.is_array() {
-                            NValue::IntInt(
-                                n[0].to_string().parse().unwrap(),
-                                n[1].to_string().parse().unwrap(),
-                            )
-                        } else if n.is_string() {
-                            NValue::String(serde_json::from_value(n.to_owned()).unwrap())
-                        } else {
-                            NValue::Integer(n.to_string().parse().unwrap())
-                        }
-                    })
-                    .collect(),
-            ),
-            _ => None,
-        };
-
-        let id = map.remove("id").unwrap().to_string().parse().unwrap();
+        let mut map = JsonMap::deserialize(deserializer)?;
+        let orth = from_field(&mut map, "orth")?;
+        let parts = from_field(&mut map, "parts")?;
+        let senses: Vec<String> = from_field(&mut map, "senses")?;
+        let pos = take_field::<D::Error>(&mut map, "pos")?.to_string();
+        let form = from_field(&mut map, "form")?;
+        let info = from_field(&mut map, "info")?;
+        let n = parse_n_values(take_field(&mut map, "n")?)?;
+        let id = parse_number(&take_field::<D::Error>(&mut map, "id")?)?;
 
         Ok(LatinWordInfo {
             orth,
@@ -399,7 +424,7 @@ impl<'de> Deserialize<'de> for LatinWordInfo {
             senses,
             pos: PartOfSpeech::dict_key_to_part_of_speech(&pos),
             form: Form::StrForm(form),
-            info: serde_json::from_str(&info).unwrap(),
+            info,
             n,
             modifiers: None,
             id,
@@ -423,38 +448,14 @@ impl<'de> Deserialize<'de> for UniqueLatinWordInfo {
     where
         D: Deserializer<'de>,
     {
-        let mut map = serde_json::Map::deserialize(deserializer)?;
-        let orth = serde_json::from_value(map.remove("orth").unwrap())
-            .expect("Failed to deserialize orth");
-        let senses: Vec<String> = serde_json::from_value(map.remove("senses").unwrap())
-            .expect("Failed to deserialize senses");
-        let pos = map.remove("pos").unwrap().to_string();
-        let form = serde_json::from_value(map.remove("form").unwrap())
-            .expect("Failed to deserialize form");
-        let info = map.remove("info").unwrap().to_string();
+        let mut map = JsonMap::deserialize(deserializer)?;
+        let orth = from_field(&mut map, "orth")?;
+        let senses: Vec<String> = from_field(&mut map, "senses")?;
+        let pos = take_field::<D::Error>(&mut map, "pos")?.to_string();
+        let form = from_field(&mut map, "form")?;
+        let info = from_field(&mut map, "info")?;
         let n = match map.remove("n") {
-            Some(n) => {
-                let n_value = serde_json::from_value(n).expect("Failed to deserialize n");
-                match n_value {
-                    serde_json::Value::Array(n) => Some(
-                        n.iter()
-                            .map(|n| {
-                                if n.is_array() {
-                                    NValue::IntInt(
-                                        n[0].to_string().parse().unwrap(),
-                                        n[1].to_string().parse().unwrap(),
-                                    )
-                                } else if n.is_string() {
-                                    NValue::String(serde_json::from_value(n.to_owned()).unwrap())
-                                } else {
-                                    NValue::Integer(n.to_string().parse().unwrap())
-                                }
-                            })
-                            .collect(),
-                    ),
-                    _ => None,
-                }
-            }
+            Some(n) => parse_n_values(n)?,
             None => None,
         };
 
@@ -464,7 +465,7 @@ impl<'de> Deserialize<'de> for UniqueLatinWordInfo {
             pos: PartOfSpeech::dict_key_to_part_of_speech(&pos),
             form: Form::StrForm(form),
             n,
-            info: serde_json::from_str(&info).unwrap(),
+            info,
         })
     }
 }
@@ -481,6 +482,12 @@ pub struct EnglishWordInfo {
     pub semi: i16,
 }
 
+impl Default for EnglishWordInfo {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl EnglishWordInfo {
     pub fn new() -> EnglishWordInfo {
         EnglishWordInfo {
@@ -586,24 +593,19 @@ impl<'de> Deserialize<'de> for EnglishWordInfo {
     where
         D: Deserializer<'de>,
     {
-        let mut map = serde_json::Map::deserialize(deserializer)?;
-        let orth = map.remove("orth").unwrap();
-        let wid = map.remove("wid").unwrap();
-        let pos = map.remove("pos").unwrap().to_string();
-        let frequency_type = map.remove("frequency_type").unwrap().to_string();
-        let frequency = map
-            .remove("frequency")
-            .unwrap()
-            .to_string()
-            .parse()
-            .unwrap();
-        let compound = map.remove("compound").unwrap().to_string().parse().unwrap();
-        let semi = map.remove("semi").unwrap().to_string().parse().unwrap();
+        let mut map = JsonMap::deserialize(deserializer)?;
+        let orth: String = from_field(&mut map, "orth")?;
+        let wid = parse_number(&take_field::<D::Error>(&mut map, "wid")?)?;
+        let pos = take_field::<D::Error>(&mut map, "pos")?.to_string();
+        let frequency_type = take_field::<D::Error>(&mut map, "frequency_type")?.to_string();
+        let frequency = parse_number(&take_field::<D::Error>(&mut map, "frequency")?)?;
+        let compound = parse_number(&take_field::<D::Error>(&mut map, "compound")?)?;
+        let semi = parse_number(&take_field::<D::Error>(&mut map, "semi")?)?;
         let true_frequency = Some(frequency + compound - semi);
 
         Ok(EnglishWordInfo {
-            orth: orth.to_string().trim_matches('"').to_string(),
-            wid: wid.to_string().parse().unwrap(),
+            orth: orth.trim_matches('"').to_string(),
+            wid,
             pos: PartOfSpeech::dict_key_to_part_of_speech(&pos),
             frequency_type: Frequency::dict_key_to_frequency(&frequency_type),
             true_frequency,
@@ -623,6 +625,12 @@ pub struct WordInfo {
     pub source: Source,
 }
 
+impl Default for WordInfo {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl WordInfo {
     pub fn new() -> WordInfo {
         WordInfo {
@@ -701,12 +709,12 @@ impl<'de> Deserialize<'de> for WordInfo {
     where
         D: Deserializer<'de>,
     {
-        let mut map = serde_json::Map::deserialize(deserializer)?;
-        let age = map.remove("age").unwrap().to_string();
-        let area = map.remove("area").unwrap().to_string();
-        let geo = map.remove("geo").unwrap().to_string();
-        let freq = map.remove("freq").unwrap().to_string();
-        let source = map.remove("source").unwrap().to_string();
+        let mut map = JsonMap::deserialize(deserializer)?;
+        let age = take_field::<D::Error>(&mut map, "age")?.to_string();
+        let area = take_field::<D::Error>(&mut map, "area")?.to_string();
+        let geo = take_field::<D::Error>(&mut map, "geo")?.to_string();
+        let freq = take_field::<D::Error>(&mut map, "freq")?.to_string();
+        let source = take_field::<D::Error>(&mut map, "source")?.to_string();
 
         Ok(WordInfo {
             age: Age::dict_key_to_age(&age),
@@ -725,6 +733,16 @@ pub struct Inflection {
     pub note: Option<String>,
     pub n: Option<Vec<NValue>>,
     pub form: Form,
+    // which stem of the word the ending is added to, starting at 1
+    pub stem_key: i8,
+    pub age: Age,
+    pub freq: Frequency,
+}
+
+impl Default for Inflection {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl Inflection {
@@ -735,6 +753,9 @@ impl Inflection {
             note: None,
             n: None,
             form: Form::StrForm(String::new()),
+            stem_key: 0,
+            age: Age::UsedThroughoutAges,
+            freq: Frequency::VeryFrequent,
         }
     }
 
@@ -757,6 +778,32 @@ impl Inflection {
     pub fn set_form(&mut self, form: Form) {
         self.form = form;
     }
+
+    pub fn set_stem_key(&mut self, stem_key: i8) {
+        self.stem_key = stem_key;
+    }
+
+    pub fn set_age(&mut self, age: Age) {
+        self.age = age;
+    }
+
+    pub fn set_freq(&mut self, freq: Frequency) {
+        self.freq = freq;
+    }
+
+    /**
+     * Inflections used in classical Latin, undated ones count as classical
+     */
+    pub fn is_classical(&self) -> bool {
+        matches!(self.age, Age::Classical | Age::UsedThroughoutAges)
+    }
+
+    /**
+     * Lower is more common: the frequency first, then archaic, late and medieval endings after classical ones
+     */
+    pub fn rank(&self) -> (u8, bool) {
+        (self.freq.as_number(), !self.is_classical())
+    }
 }
 
 impl<'de> Deserialize<'de> for Inflection {
@@ -764,47 +811,38 @@ impl<'de> Deserialize<'de> for Inflection {
     where
         D: Deserializer<'de>,
     {
-        let mut map = serde_json::Map::deserialize(deserializer)?;
-        let ending: String = serde_json::from_value(map.remove("ending").unwrap())
-            .expect("Failed to deserialize ending");
-        let pos: String =
-            serde_json::from_value(map.remove("pos").unwrap()).expect("Failed to deserialize pos");
-        let note = serde_json::from_value(map.remove("note").unwrap())
-            .expect("Failed to deserialize note");
+        let mut map = JsonMap::deserialize(deserializer)?;
+        let ending: String = from_field(&mut map, "ending")?;
+        let pos: String = from_field(&mut map, "pos")?;
+        let note = from_field(&mut map, "note")?;
         let n = match map.remove("n") {
-            Some(n) => {
-                let n_value = serde_json::from_value(n).expect("Failed to deserialize n");
-                match n_value {
-                    serde_json::Value::Array(n) => Some(
-                        n.iter()
-                            .map(|n| {
-                                if n.is_array() {
-                                    NValue::IntInt(
-                                        n[0].to_string().parse().unwrap(),
-                                        n[1].to_string().parse().unwrap(),
-                                    )
-                                } else if n.is_string() {
-                                    NValue::String(serde_json::from_value(n.to_owned()).unwrap())
-                                } else {
-                                    NValue::Integer(n.to_string().parse().unwrap())
-                                }
-                            })
-                            .collect(),
-                    ),
-                    _ => None,
-                }
-            }
+            Some(n) => parse_n_values(n)?,
             None => None,
         };
-        let form = serde_json::from_value(map.remove("form").unwrap())
-            .expect("Failed to deserialize form");
+        let form = from_field(&mut map, "form")?;
+        let stem_key = match map.remove("stem_key") {
+            Some(stem_key) => parse_number(&stem_key)?,
+            None => 0,
+        };
+        // INFLECTS.LAT defaults inflections to used throughout the ages and very frequent
+        let age = match map.remove("age") {
+            Some(age) => Age::dict_key_to_age(&age.to_string()),
+            None => Age::UsedThroughoutAges,
+        };
+        let freq = match map.remove("freq") {
+            Some(freq) => Frequency::dict_key_to_frequency(&freq.to_string()),
+            None => Frequency::VeryFrequent,
+        };
 
         Ok(Inflection {
-            ending: ending.to_string().trim_matches('"').to_string(),
+            ending: ending.trim_matches('"').to_string(),
             pos: PartOfSpeech::dict_key_to_part_of_speech(&pos),
             note,
             n,
             form: Form::StrForm(form),
+            stem_key,
+            age,
+            freq,
         })
     }
 }
@@ -852,6 +890,19 @@ impl Serialize for Inflection {
                 ),
             },
         );
+        map.insert(
+            "stem_key".to_string(),
+            serde_json::Value::from(self.stem_key),
+        );
+        // the letters the dictionary files use, so the inflection deserializes the same
+        map.insert(
+            "age".to_string(),
+            serde_json::Value::String(self.age.as_dict_key().to_string()),
+        );
+        map.insert(
+            "freq".to_string(),
+            serde_json::Value::String(self.freq.as_dict_key().to_string()),
+        );
         serde_json::Value::Object(map).serialize(serializer)
     }
 }
@@ -865,6 +916,12 @@ pub struct Stem {
     pub wid: i32,
 }
 
+impl Default for Stem {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Stem {
     pub fn new() -> Stem {
         Stem {
@@ -946,45 +1003,20 @@ impl<'de> Deserialize<'de> for Stem {
     where
         D: Deserializer<'de>,
     {
-        let mut map = serde_json::Map::deserialize(deserializer)?;
-        let pos: String =
-            serde_json::from_value(map.remove("pos").unwrap()).expect("Failed to deserialize pos");
-        let form = serde_json::from_value(map.remove("form").unwrap())
-            .expect("Failed to deserialize form");
-        let orth: String = serde_json::from_value(map.remove("orth").unwrap())
-            .expect("Failed to deserialize orth");
+        let mut map = JsonMap::deserialize(deserializer)?;
+        let pos: String = from_field(&mut map, "pos")?;
+        let form = from_field(&mut map, "form")?;
+        let orth: String = from_field(&mut map, "orth")?;
         let n = match map.remove("n") {
-            Some(n) => {
-                let n_value = serde_json::from_value(n).expect("Failed to deserialize n");
-                match n_value {
-                    serde_json::Value::Array(n) => Some(
-                        n.iter()
-                            .map(|n| {
-                                if n.is_array() {
-                                    NValue::IntInt(
-                                        n[0].to_string().parse().unwrap(),
-                                        n[1].to_string().parse().unwrap(),
-                                    )
-                                } else if n.is_string() {
-                                    NValue::String(serde_json::from_value(n.to_owned()).unwrap())
-                                } else {
-                                    NValue::Integer(n.to_string().parse().unwrap())
-                                }
-                            })
-                            .collect(),
-                    ),
-                    _ => None,
-                }
-            }
+            Some(n) => parse_n_values(n)?,
             None => None,
         };
-        let wid =
-            serde_json::from_value(map.remove("wid").unwrap()).expect("Failed to deserialize wid");
+        let wid = from_field(&mut map, "wid")?;
 
         Ok(Stem {
             pos: PartOfSpeech::dict_key_to_part_of_speech(&pos),
             form: Form::StrForm(form),
-            orth: orth.to_string().trim_matches('"').to_string(),
+            orth: orth.trim_matches('"').to_string(),
             n,
             wid,
         })
@@ -998,6 +1030,7 @@ pub enum ModifierType {
     Tackon,
     Packon,
     NotPackon,
+    Tickon,
     Unspecified,
 }
 
@@ -1009,6 +1042,7 @@ impl ModifierType {
             ModifierType::Tackon => "enclitic tackon".to_string(),
             ModifierType::Packon => "enclitic packon".to_string(),
             ModifierType::NotPackon => "enclitic not packon".to_string(),
+            ModifierType::Tickon => "tickon".to_string(),
             ModifierType::Unspecified => "unspecified".to_string(),
         }
     }
@@ -1021,6 +1055,14 @@ pub struct Modifier {
     pub senses: Vec<String>,
     pub orth: String,
     pub modifier: ModifierType,
+    // the letter that must follow a prefix or come before a suffix, ex: ac + c in accurro
+    pub connect: Option<char>,
+}
+
+impl Default for Modifier {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl Modifier {
@@ -1031,6 +1073,7 @@ impl Modifier {
             senses: Vec::new(),
             orth: String::new(),
             modifier: ModifierType::Unspecified,
+            connect: None,
         }
     }
 
@@ -1042,7 +1085,7 @@ impl Modifier {
         self.form = Some(form);
     }
 
-    pub fn set_senses(&mut self, senses: &Vec<String>) {
+    pub fn set_senses(&mut self, senses: &[String]) {
         self.senses = senses.to_vec();
     }
 
@@ -1053,6 +1096,96 @@ impl Modifier {
     pub fn set_modifier(&mut self, modifier: ModifierType) {
         self.modifier = modifier;
     }
+
+    pub fn set_connect(&mut self, connect: Option<char>) {
+        self.connect = connect;
+    }
+
+    /**
+     * The rest of the word after the prefix, None if the word does not start with the prefix and its connecting letter.
+     * ex: "ad" with "c" strips "accurro" to "curro", but not "acerbus"
+     */
+    pub fn strip_prefix<'a>(&self, latin_word: &'a str) -> Option<&'a str> {
+        let rest = latin_word.strip_prefix(self.orth.as_str())?;
+        match self.connect {
+            Some(connect) if !rest.starts_with(connect) => None,
+            _ if rest.is_empty() => None,
+            _ => Some(rest),
+        }
+    }
+
+    /**
+     * The word without the suffix, None if the word does not end with the suffix and its connecting letter.
+     */
+    pub fn strip_suffix<'a>(&self, latin_word: &'a str) -> Option<&'a str> {
+        let rest = latin_word.strip_suffix(self.orth.as_str())?;
+        match self.connect {
+            Some(connect) if !rest.ends_with(connect) => None,
+            _ if rest.is_empty() => None,
+            _ => Some(rest),
+        }
+    }
+
+    /**
+     * What a suffix is added to and what it makes, parsed from its form. None for prefixes and other short forms.
+     */
+    pub fn suffix_form(&self) -> Option<SuffixForm> {
+        let form = match &self.form {
+            Some(Form::StrForm(form)) if !matches!(self.modifier, ModifierType::Prefix) => form,
+            _ => return None,
+        };
+        let values: Vec<&str> = form.split_whitespace().collect();
+        if values.len() < 4 {
+            return None;
+        }
+
+        let target_form = &values[3..values.len() - 1];
+        Some(SuffixForm {
+            root_pos: PartOfSpeech::dict_key_to_part_of_speech(values[0]),
+            root_key: values[1].parse().ok()?,
+            target_pos: PartOfSpeech::dict_key_to_part_of_speech(values[2]),
+            target_n: target_form
+                .iter()
+                .take(2)
+                .map_while(|value| value.parse().ok())
+                .collect(),
+            target_key: values[values.len() - 1].parse().ok()?,
+        })
+    }
+
+    /**
+     * If a prefix can be added to a word with the part of speech.
+     * ADDONS.LAT lists the from and to parts of speech of a prefix, they are always the same,
+     * X X prefixes apply to nouns, adjectives, adverbs and verbs.
+     */
+    pub fn applies_to(&self, pos: PartOfSpeech) -> bool {
+        match self.pos {
+            PartOfSpeech::Unknown => matches!(
+                pos,
+                PartOfSpeech::Noun
+                    | PartOfSpeech::Adjective
+                    | PartOfSpeech::Adverb
+                    | PartOfSpeech::Verb
+                    | PartOfSpeech::Participle
+            ),
+            PartOfSpeech::Verb => matches!(pos, PartOfSpeech::Verb | PartOfSpeech::Participle),
+            prefix_pos => prefix_pos == pos,
+        }
+    }
+}
+
+/**
+ * The parts of speech and stems a suffix connects, from its ADDONS.LAT form `ROOT_POS ROOT_KEY TARGET_POS TARGET_FORM... TARGET_KEY`.
+ * ex: "V 4 N 3 1 F p 2" is added to the 4th stem of a verb and makes a 3rd declension noun, used as its 2nd stem.
+ * A key of 0 is any stem.
+ */
+#[derive(Debug, Clone)]
+pub struct SuffixForm {
+    pub root_pos: PartOfSpeech,
+    pub root_key: i8,
+    pub target_pos: PartOfSpeech,
+    pub target_n: Vec<i8>,
+    pub target_key: i8,
 }
 
 impl<'de> Deserialize<'de> for Modifier {
@@ -1060,24 +1193,26 @@ impl<'de> Deserialize<'de> for Modifier {
     where
         D: Deserializer<'de>,
     {
-        let mut map = serde_json::Map::deserialize(deserializer)?;
-        let pos: String =
-            serde_json::from_value(map.remove("pos").unwrap()).expect("Failed to deserialize pos");
+        let mut map = JsonMap::deserialize(deserializer)?;
+        let pos: String = from_field(&mut map, "pos")?;
         let form = match map.remove("form") {
-            Some(form) => Some(serde_json::from_value(form).expect("Failed to deserialize form")),
+            Some(form) => Some(serde_json::from_value(form).map_err(de::Error::custom)?),
             None => None,
         };
-        let senses: Vec<String> = serde_json::from_value(map.remove("senses").unwrap())
-            .expect("Failed to deserialize senses");
-        let orth: String = serde_json::from_value(map.remove("orth").unwrap())
-            .expect("Failed to deserialize orth");
+        let senses: Vec<String> = from_field(&mut map, "senses")?;
+        let orth: String = from_field(&mut map, "orth")?;
+        let connect = match map.remove("connect") {
+            Some(serde_json::Value::String(connect)) => connect.chars().next(),
+            _ => None,
+        };
 
         Ok(Modifier {
             pos: PartOfSpeech::dict_key_to_part_of_speech(&pos),
             form,
             senses,
-            orth: orth.to_string().trim_matches('"').to_string(),
+            orth: orth.trim_matches('"').to_string(),
             modifier: ModifierType::Unspecified,
+            connect,
         })
     }
 }
@@ -1125,6 +1260,12 @@ impl Serialize for Modifier {
             "modifier".to_string(),
             serde_json::Value::String(self.modifier.as_str()),
         );
+        if let Some(connect) = self.connect {
+            map.insert(
+                "connect".to_string(),
+                serde_json::Value::String(connect.to_string()),
+            );
+        }
         serde_json::Value::Object(map).serialize(serializer)
     }
 }
@@ -1231,13 +1372,29 @@ impl Form {
                     | PartOfSpeech::Numeral
                     | PartOfSpeech::Supine => {
                         // GEN P C -> Genitive Plural Common
-                        if form_array.len() == 3 {
+                        if form_array.len() >= 3 {
                             long_form.declension =
                                 Some(Declension::dict_key_to_declension(form_array[0]));
                             long_form.number = Some(Number::dict_key_to_number(form_array[1]));
                             long_form.gender = Some(Gender::dict_key_to_gender(form_array[2]));
                         }
 
+                        // NOM S M COMP -> Nominative Singular Masculine Comparative
+                        // NOM S M CARD -> Nominative Singular Masculine Cardinal
+                        if form_array.len() == 4 {
+                            match part_of_speech {
+                                PartOfSpeech::Adjective => {
+                                    long_form.comparison =
+                                        Some(Comparison::dict_key_to_comparison(form_array[3]));
+                                }
+                                PartOfSpeech::Numeral => {
+                                    long_form.numeral =
+                                        Some(Numeral::dict_key_to_numeral(form_array[3]));
+                                }
+                                _ => {}
+                            }
+                        }
+
                         *self = Form::LongForm(long_form);
                     }
                     PartOfSpeech::Verb => {
@@ -1263,7 +1420,7 @@ impl Form {
                     }
                     PartOfSpeech::Participle => {
                         // NOM S X PRES ACTIVE  PPL -> Nominative Singular Present Active Participle
-                        if form_array.len() == 5 {
+                        if form_array.len() >= 5 {
                             long_form.declension =
                                 Some(Declension::dict_key_to_declension(form_array[0]));
                             long_form.number = Some(Number::dict_key_to_number(form_array[1]));
@@ -1423,7 +1580,7 @@ impl Serialize for LongForm {
         );
         map.insert(
             "person".to_string(),
-            serde_json::Value::String(self.person.to_owned().unwrap_or(String::new())),
+            serde_json::Value::String(self.person.to_owned().unwrap_or_default()),
         );
         map.insert(
             "pos".to_string(),
@@ -1464,6 +1621,12 @@ impl Serialize for LongForm {
     }
 }
 
+impl Default for LongForm {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl LongForm {
     pub fn new() -> LongForm {
         LongForm {
@@ -1564,6 +1727,11 @@ impl LongForm {
         self
     }
 
+    pub fn set_person(mut self, person: &str) -> LongForm {
+        self.person = Some(person.to_string());
+        self
+    }
+
     pub fn set_tense(mut self, tense: Tense) -> LongForm {
         self.tense = Some(tense);
         self
@@ -1587,6 +1755,12 @@ pub struct Attachment {
     pub orth: String,
 }
 
+impl Default for Attachment {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Attachment {
     pub fn new() -> Attachment {
         Attachment {
@@ -1641,18 +1815,84 @@ impl<'de> Deserialize<'de> for Attachment {
     where
         D: Deserializer<'de>,
     {
-        let mut map = serde_json::Map::deserialize(deserializer)?;
-        let pos: String =
-            serde_json::from_value(map.remove("pos").unwrap()).expect("Failed to deserialize pos");
-        let senses: Vec<String> = serde_json::from_value(map.remove("senses").unwrap())
-            .expect("Failed to deserialize senses");
-        let orth = serde_json::from_value(map.remove("orth").unwrap())
-            .expect("Failed to deserialize orth");
+        let mut map = JsonMap::deserialize(deserializer)?;
+        let pos: String = from_field(&mut map, "pos")?;
+        let senses: Vec<String> = from_field(&mut map, "senses")?;
+        let orth = from_field(&mut map, "orth")?;
 
+        // the pos is followed by the form of the words it is attached to, ex: "PRON 3 1 ADJECT"
         Ok(Attachment {
-            pos: PartOfSpeech::dict_key_to_part_of_speech(&pos),
+            pos: PartOfSpeech::dict_key_to_part_of_speech(
+                pos.split_whitespace().next().unwrap_or_default(),
+            ),
             senses,
             orth,
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn inflections_round_trip() {
+        let json = r#"{"age":"D","ending":"ibus","form":"DAT P X","freq":"B","n":[3,1],"note":"","pos":"N","stem_key":2}"#;
+        let inflection: Inflection = serde_json::from_str(json).unwrap();
+        assert!(matches!(inflection.age, Age::Late));
+        assert!(matches!(inflection.freq, Frequency::Frequent));
+
+        let serialized = serde_json::to_value(&inflection).unwrap();
+        assert_eq!(serialized["age"], "D");
+        assert_eq!(serialized["freq"], "B");
+
+        let round_trip: Inflection = serde_json::from_value(serialized.clone()).unwrap();
+        assert_eq!(serde_json::to_value(&round_trip).unwrap(), serialized);
+        assert_eq!(round_trip.ending, "ibus");
+        assert_eq!(round_trip.stem_key, 2);
+        assert!(round_trip.pos == PartOfSpeech::Noun);
+    }
+
+    #[test]
+    fn every_age_and_frequency_round_trips() {
+        let ages = [
+            Age::Archaic,
+            Age::Early,
+            Age::Classical,
+            Age::Late,
+            Age::Later,
+            Age::Medieval,
+            Age::Scholar,
+            Age::Modern,
+            Age::UsedThroughoutAges,
+            Age::Unknown,
+        ];
+        for age in ages {
+            assert_eq!(
+                Age::dict_key_to_age(age.as_dict_key()).as_str(),
+                age.as_str()
+            );
+        }
+
+        let frequencies = [
+            Frequency::VeryFrequent,
+            Frequency::Frequent,
+            Frequency::Common,
+            Frequency::Lesser,
+            Frequency::Uncommon,
+            Frequency::VeryRare,
+            Frequency::Inscription,
+            Frequency::Graffiti,
+            Frequency::Pliny,
+            Frequency::AllOrNone,
+            Frequency::Unknown,
+        ];
+        for freq in frequencies {
+            let mut inflection = Inflection::new();
+            inflection.set_freq(freq);
+            let serialized = serde_json::to_string(&inflection).unwrap();
+            let round_trip: Inflection = serde_json::from_str(&serialized).unwrap();
+            assert_eq!(round_trip.freq.as_str(), freq.as_str());
+        }
+    }
+}
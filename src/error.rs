@@ -0,0 +1,129 @@
+use crate::dictionary_structures::dictionary_keys::PartOfSpeech;
+use std::fmt;
+
+/**
+ * Every error that can be returned by the library.
+ * The binary maps each variant to an exit code, see `exit_code` in main.rs
+ */
+#[derive(Debug)]
+pub enum VocabError {
+    /// A stem or inflection in the dictionary data has no n value
+    MissingNValue {
+        structure: &'static str,
+        orth: String,
+    },
+    /// A value needed to generate principle parts was not provided (gender, comparison, verb type, numeral type)
+    MissingPrinciplePartValue {
+        part_of_speech: PartOfSpeech,
+        value: &'static str,
+    },
+    /// Principle parts were requested without any endings or special case
+    NoPrinciplePartEndings(String),
+    /// There is no principle part generator for the part of speech
+    NoGenerator(PartOfSpeech),
+    /// A translation was post processed as the wrong language
+    LanguageMismatch {
+        expected: &'static str,
+        found: &'static str,
+    },
+    /// A dictionary file could not be parsed
+    DataLoad {
+        file: &'static str,
+        source: serde_json::Error,
+    },
+    MissingArgument(String),
+    InvalidArgument {
+        name: String,
+        value: String,
+    },
+    /// An argument was used without the argument it requires
+    MissingRequiredArgument {
+        name: String,
+        requires: String,
+    },
+    CommandNotFound(String),
+    InvalidWordType(String),
+    InvalidPartOfSpeech(String),
+    Io(std::io::Error),
+    Json(serde_json::Error),
+}
+
+impl fmt::Display for VocabError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            VocabError::MissingNValue { structure, orth } => {
+                write!(f, "{} \"{}\" has no n value", structure, orth)
+            }
+            VocabError::MissingPrinciplePartValue {
+                part_of_speech,
+                value,
+            } => write!(
+                f,
+                "A {} is required for generating principle parts for a {}, but none was provided",
+                value,
+                part_of_speech.as_str()
+            ),
+            VocabError::NoPrinciplePartEndings(orth) => {
+                write!(f, "No endings or special case provided for \"{}\"", orth)
+            }
+            VocabError::NoGenerator(part_of_speech) => write!(
+                f,
+                "Cannot generate principle parts for a {}",
+                part_of_speech.as_str()
+            ),
+            VocabError::LanguageMismatch { expected, found } => write!(
+                f,
+                "Expected {} translation type, found {}",
+                expected, found
+            ),
+            VocabError::DataLoad { file, source } => {
+                write!(f, "Failed to load {}: {}", file, source)
+            }
+            VocabError::MissingArgument(name) => write!(f, "Missing required argument: {}", name),
+            VocabError::InvalidArgument { name, value } => {
+                write!(f, "Invalid value for {}: {}", name, value)
+            }
+            VocabError::MissingRequiredArgument { name, requires } => write!(
+                f,
+                "The argument \"{}\" requires the argument \"{}\"",
+                name, requires
+            ),
+            VocabError::CommandNotFound(name) => write!(f, "Command not found: {}", name),
+            VocabError::InvalidWordType(word_type) => write!(
+                f,
+                "Invalid type of words: {}. Please use `help` to see the available types of words.",
+                word_type
+            ),
+            VocabError::InvalidPartOfSpeech(pos) => write!(
+                f,
+                "Invalid part of speech entered: {}\nPlease use the following: noun, verb, participle, adjective, preposition, pronoun, interjection, numeral, conjunction, adverb, number, supine, packon, tackon, prefix, suffix",
+                pos
+            ),
+            VocabError::Io(error) => write!(f, "{}", error),
+            VocabError::Json(error) => write!(f, "{}", error),
+        }
+    }
+}
+
+impl std::error::Error for VocabError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            VocabError::DataLoad { source, .. } => Some(source),
+            VocabError::Io(error) => Some(error),
+            VocabError::Json(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<std::io::Error> for VocabError {
+    fn from(error: std::io::Error) -> VocabError {
+        VocabError::Io(error)
+    }
+}
+
+impl From<serde_json::Error> for VocabError {
+    fn from(error: serde_json::Error) -> VocabError {
+        VocabError::Json(error)
+    }
+}
@@ -0,0 +1,521 @@
+/*!
+ * Vocab Vault translates between Latin and English using the Whitaker's Words dictionary.
+ *
+ * The [`Translator`] is the entry point: it loads every dictionary once and can then be
+ * used for any number of translations, list queries and principle part generation.
+ * Nothing in the library prints or exits the process, results and errors ([`VocabError`])
+ * are returned to the caller.
+ *
+ * ```no_run
+ * use vocab_vault::Translator;
+ *
+ * # fn main() -> Result<(), vocab_vault::VocabError> {
+ * let translator = Translator::new()?.with_tricks(true).with_max(3);
+ * for definition in translator.latin_to_english("amat")? {
+ *     println!("{}: {}", definition.word.orth, definition.word.senses.join(", "));
+ * }
+ * # Ok(())
+ * # }
+ * ```
+ */
+pub mod build_data;
+pub mod dictionary_structures;
+pub mod error;
+pub mod lookup;
+pub mod paradigm;
+pub mod quiz;
+pub mod search;
+pub mod study;
+pub mod translators;
+pub mod use_data;
+pub mod utils;
+
+pub use dictionary_structures::dictionary_keys::PartOfSpeech;
+pub use dictionary_structures::dictionary_values::{EnglishWordInfo, LatinWordInfo};
+pub use error::VocabError;
+pub use lookup::DictionaryEntry;
+pub use paradigm::Paradigm;
+pub use quiz::{Question, QuizKind};
+pub use search::SenseMatch;
+pub use study::{Card, Deck, ExportFormat, Rating};
+pub use translators::english_to_latin::EnglishTranslationInfo;
+pub use translators::latin_to_english::orthography::Orthography;
+pub use translators::latin_to_english::sentence::{tokenize, SentenceAnalysis, Token};
+pub use translators::latin_to_english::suggestions::Suggestion;
+pub use translators::latin_to_english::word_formation::Derivation;
+pub use translators::latin_to_english::LatinTranslationInfo;
+pub use translators::{Translation, TranslationType};
+pub use use_data::{OutputList, WordFilter, WordType};
+pub use utils::data::Dictionary;
+
+use lookup::{lookup_english, lookup_latin};
+use paradigm::form::{generate_form, keep_form, parse_form_description};
+use paradigm::inflect;
+use quiz::make_questions;
+use search::query::SenseQuery;
+use search::search_senses;
+use study::export::export_deck;
+use translators::english_to_latin::translate_english_to_latin;
+use translators::latin_to_english::sentence::analyze_sentence;
+use translators::latin_to_english::suggestions::suggest;
+use translators::latin_to_english::translate_latin_to_english;
+use translators::latin_to_english::word_formation::find_derivations;
+use translators::{
+    mark_vowel_quantity, post_process_english_definitions, post_process_latin_definitions,
+    rank_latin_definitions, sort_latin_definitions,
+};
+use use_data::{get_list, random_latin_entries};
+use utils::sanitize_word;
+
+/**
+ * Holds the loaded dictionaries and the translation settings.
+ *
+ * Settings are set with the `with_*` builder functions, the defaults match the CLI:
+ * at most 6 definitions per word, no tricks, no sorting and every inflection.
+ */
+pub struct Translator {
+    dictionary: Dictionary,
+    max: usize,
+    tricks: bool,
+    sort: bool,
+    classical_only: bool,
+    vowel_quantity: bool,
+    orthography: Orthography,
+    suggestions: usize,
+    filter: WordFilter,
+}
+
+impl Translator {
+    /**
+     * Loads all dictionaries, this is the slow part, so reuse the translator.
+     */
+    pub fn new() -> Result<Translator, VocabError> {
+        Ok(Translator::from_dictionary(Dictionary::load()?))
+    }
+
+    pub fn from_dictionary(dictionary: Dictionary) -> Translator {
+        Translator {
+            dictionary,
+            max: 6,
+            tricks: false,
+            sort: false,
+            classical_only: false,
+            vowel_quantity: false,
+            orthography: Orthography::new(),
+            suggestions: 3,
+            filter: WordFilter::new(),
+        }
+    }
+
+    /**
+     * The maximum number of definitions returned per word
+     */
+    pub fn with_max(mut self, max: usize) -> Translator {
+        self.max = max;
+        self
+    }
+
+    /**
+     * Try Whitaker's spelling tricks when translating Latin
+     */
+    pub fn with_tricks(mut self, tricks: bool) -> Translator {
+        self.tricks = tricks;
+        self
+    }
+
+    /**
+     * Sort definitions by word frequency
+     */
+    pub fn with_sort(mut self, sort: bool) -> Translator {
+        self.sort = sort;
+        self
+    }
+
+    /**
+     * Hide archaic, early, late and medieval inflections when translating Latin
+     */
+    pub fn with_classical_only(mut self, classical_only: bool) -> Translator {
+        self.classical_only = classical_only;
+        self
+    }
+
+    /**
+     * Mark the long vowels of principle parts and endings with macrons when translating Latin, ex: amo, amāre, amāvī, amātus
+     */
+    pub fn with_vowel_quantity(mut self, vowel_quantity: bool) -> Translator {
+        self.vowel_quantity = vowel_quantity;
+        self
+    }
+
+    /**
+     * Which spelling variants (j, u/v, æ/œ, k) are mapped to the dictionary's spelling when translating Latin, all by default
+     */
+    pub fn with_orthography(mut self, orthography: Orthography) -> Translator {
+        self.orthography = orthography;
+        self
+    }
+
+    /**
+     * How many spellings to suggest for Latin words that are not found, 0 turns suggestions off, 3 by default
+     */
+    pub fn with_suggestions(mut self, suggestions: usize) -> Translator {
+        self.suggestions = suggestions;
+        self
+    }
+
+    /**
+     * Only keep the translations of entries the filter allows, ex: classical entries from Lewis and Short.
+     * The length filters are ignored, every entry is kept by default
+     */
+    pub fn with_filter(mut self, filter: WordFilter) -> Translator {
+        self.filter = filter;
+        self
+    }
+
+    pub fn dictionary(&self) -> &Dictionary {
+        &self.dictionary
+    }
+
+    /**
+     * Translates a single Latin word, the word is sanitized first.
+     */
+    pub fn latin_to_english(
+        &self,
+        latin_word: &str,
+    ) -> Result<Vec<LatinTranslationInfo>, VocabError> {
+        let mut definitions = translate_latin_to_english(
+            &self.dictionary,
+            &sanitize_word(latin_word),
+            self.tricks,
+            &self.orthography,
+        )?;
+        self.finish_latin_definitions(&mut definitions)?;
+
+        Ok(definitions)
+    }
+
+    /**
+     * Translates a single Latin word, with spelling suggestions when it is not found.
+     */
+    pub fn latin_translation(&self, latin_word: &str) -> Result<Translation, VocabError> {
+        let definitions = self.latin_to_english(latin_word)?;
+        let suggestions = match definitions.is_empty() && self.suggestions > 0 {
+            true => Some(self.suggest(latin_word)?),
+            false => None,
+        };
+
+        let translation =
+            Translation::new(latin_word.to_string(), TranslationType::Latin(definitions));
+        Ok(match suggestions {
+            Some(suggestions) => translation.with_suggestions(suggestions),
+            None => translation,
+        })
+    }
+
+    /**
+     * Finds the dictionary forms closest to a misspelled Latin word, closest first, ex: puelam -> puellam.
+     */
+    pub fn suggest(&self, latin_word: &str) -> Result<Vec<Suggestion>, VocabError> {
+        let mut suggestions = suggest(
+            &self.dictionary,
+            &sanitize_word(latin_word),
+            self.suggestions,
+        )?;
+
+        for suggestion in suggestions.iter_mut() {
+            self.finish_latin_definitions(&mut suggestion.definitions)?;
+        }
+        // every reading of the form can be filtered out
+        suggestions.retain(|suggestion| !suggestion.definitions.is_empty());
+
+        Ok(suggestions)
+    }
+
+    // ranks, cuts and post processes the definitions of a Latin word with the translator's options
+    fn finish_latin_definitions(
+        &self,
+        definitions: &mut Vec<LatinTranslationInfo>,
+    ) -> Result<(), VocabError> {
+        definitions.retain(|definition| self.filter.allows(&definition.word));
+        rank_latin_definitions(definitions, self.classical_only);
+        definitions.truncate(self.max);
+
+        if self.sort {
+            sort_latin_definitions(definitions);
+        }
+        post_process_latin_definitions(definitions)?;
+        if self.vowel_quantity {
+            mark_vowel_quantity(definitions);
+        }
+
+        Ok(())
+    }
+
+    /**
+     * Finds how a Latin word can be built from dictionary words with prefixes and suffixes, best first.
+     * Readings of the word without any prefix or suffix are left to `latin_to_english`.
+     */
+    pub fn derive(&self, latin_word: &str) -> Result<Vec<Derivation>, VocabError> {
+        let mut derivations = find_derivations(&self.dictionary, &sanitize_word(latin_word));
+        derivations.truncate(self.max);
+
+        for derivation in derivations.iter_mut() {
+            let definition = std::slice::from_mut(&mut derivation.definition);
+            post_process_latin_definitions(definition)?;
+            if self.vowel_quantity {
+                mark_vowel_quantity(definition);
+            }
+        }
+
+        Ok(derivations)
+    }
+
+    /**
+     * Translates a single English word, the word is sanitized first.
+     */
+    pub fn english_to_latin(
+        &self,
+        english_word: &str,
+    ) -> Result<Vec<EnglishTranslationInfo>, VocabError> {
+        // every word of a phrase is sanitized on its own, ex: "To go out!" -> "to go out"
+        let english_word = english_word
+            .split_whitespace()
+            .map(sanitize_word)
+            .filter(|word| !word.is_empty())
+            .collect::<Vec<String>>()
+            .join(" ");
+        let mut definitions = translate_english_to_latin(
+            &self.dictionary,
+            &english_word,
+            self.max,
+            self.sort,
+            &self.filter,
+        );
+        post_process_english_definitions(&mut definitions)?;
+
+        Ok(definitions)
+    }
+
+    /**
+     * Translates every space separated word of a Latin text.
+     */
+    pub fn translate_latin(&self, latin_text: &str) -> Result<Vec<Translation>, VocabError> {
+        latin_text
+            .split(' ')
+            .map(|word| self.latin_translation(word))
+            .collect()
+    }
+
+    /**
+     * Translates a Latin sentence, picking the most likely reading of every word
+     * from how it agrees with its neighbours (case, number, gender, prepositions and subject verb number).
+     */
+    pub fn analyze_sentence(&self, latin_text: &str) -> Result<SentenceAnalysis, VocabError> {
+        self.analyze_tokens(&tokenize(latin_text))
+    }
+
+    /**
+     * Same as `analyze_sentence` for text that is already tokenized, ex: a clause read from a stream
+     */
+    pub fn analyze_tokens(&self, tokens: &[Token]) -> Result<SentenceAnalysis, VocabError> {
+        let definitions = tokens
+            .iter()
+            .map(|token| self.latin_to_english(&token.word))
+            .collect::<Result<Vec<_>, VocabError>>()?;
+
+        Ok(analyze_sentence(tokens, definitions))
+    }
+
+    /**
+     * Translates every space separated word of an English text.
+     */
+    pub fn translate_english(&self, english_text: &str) -> Result<Vec<Translation>, VocabError> {
+        english_text
+            .split(' ')
+            .map(|word| {
+                Ok(Translation::new(
+                    word.to_string(),
+                    TranslationType::English(self.english_to_latin(word)?),
+                ))
+            })
+            .collect()
+    }
+
+    /**
+     * Translates every comma separated phrase of an English text, ex: "to go out, the king".
+     */
+    pub fn translate_english_phrases(
+        &self,
+        english_text: &str,
+    ) -> Result<Vec<Translation>, VocabError> {
+        english_text
+            .split(',')
+            .map(str::trim)
+            .filter(|phrase| !phrase.is_empty())
+            .map(|phrase| {
+                Ok(Translation::new(
+                    phrase.to_string(),
+                    TranslationType::English(self.english_to_latin(phrase)?),
+                ))
+            })
+            .collect()
+    }
+
+    /**
+     * Gets a list of dictionary entries that fit the filter, see `getList` in the CLI for what each option does.
+     * The translator's own filter is not used for lists.
+     */
+    pub fn get_list(
+        &self,
+        word_type: WordType,
+        filter: &WordFilter,
+        amount: Option<i32>,
+        random: bool,
+    ) -> Result<OutputList, VocabError> {
+        get_list(&self.dictionary, word_type, filter, amount, random)
+    }
+
+    /**
+     * Generates the principle parts of a dictionary entry, ex: amo, amare, amavi, amatus
+     */
+    pub fn principle_parts(&self, word: &LatinWordInfo) -> Result<Vec<String>, VocabError> {
+        let mut word = word.clone();
+        word.generate_principle_parts()?;
+        Ok(word.parts)
+    }
+
+    /**
+     * Generates every inflected form of a dictionary entry.
+     * Translated words already have their principle parts, so the original entry is looked up by id when there is one.
+     */
+    pub fn inflect(&self, word: &LatinWordInfo) -> Result<Paradigm, VocabError> {
+        match self.dictionary.get_latin_word_by_id(word.id) {
+            Some(entry) if word.id != 0 => inflect(&self.dictionary, entry),
+            _ => inflect(&self.dictionary, word),
+        }
+    }
+
+    /**
+     * Generates every inflected form of the dictionary entry with the id, None if there is no such entry
+     */
+    pub fn inflect_by_id(&self, id: i32) -> Result<Option<Paradigm>, VocabError> {
+        match self.dictionary.get_latin_word_by_id(id) {
+            Some(word) => Ok(Some(inflect(&self.dictionary, word)?)),
+            None => Ok(None),
+        }
+    }
+
+    /**
+     * The Latin entry with the id, with its stems, principle parts and the English entries that point at it.
+     * None if there is no such entry
+     */
+    pub fn lookup(&self, id: i32) -> Result<Option<DictionaryEntry>, VocabError> {
+        lookup_latin(&self.dictionary, id)
+    }
+
+    /**
+     * Every English entry that translates to the Latin entry with the id
+     */
+    pub fn lookup_english(&self, wid: i32) -> Vec<EnglishWordInfo> {
+        lookup_english(&self.dictionary, wid)
+    }
+
+    /**
+     * Searches the senses of the Latin entries the translator's filter allows, ex: ship OR boat, "go out", /\bsword\b/
+     * See `SenseQuery` for the query syntax. At most max entries are returned, the closest matches first.
+     */
+    pub fn search_senses(&self, query: &str, max: usize) -> Result<Vec<SenseMatch>, VocabError> {
+        search_senses(
+            &self.dictionary,
+            &SenseQuery::parse(query)?,
+            &self.filter,
+            max,
+        )
+    }
+
+    /**
+     * Generates the forms of a dictionary entry that fit a description, ex: "3rd person plural perfect active indicative".
+     * The most common ending comes first, variant endings after it.
+     */
+    pub fn form(&self, word: &LatinWordInfo, description: &str) -> Result<Paradigm, VocabError> {
+        let target = parse_form_description(description)?;
+        match self.dictionary.get_latin_word_by_id(word.id) {
+            Some(entry) if word.id != 0 => generate_form(&self.dictionary, entry, &target),
+            _ => generate_form(&self.dictionary, word, &target),
+        }
+    }
+
+    /**
+     * Same as `form` for the dictionary entry with the id, None if there is no such entry
+     */
+    pub fn form_by_id(&self, id: i32, description: &str) -> Result<Option<Paradigm>, VocabError> {
+        let target = parse_form_description(description)?;
+        match self.dictionary.get_latin_word_by_id(id) {
+            Some(word) => Ok(Some(generate_form(&self.dictionary, word, &target)?)),
+            None => Ok(None),
+        }
+    }
+
+    /**
+     * Keeps the forms that fit a description in paradigms from `inflect`, like `form` does for a single entry
+     */
+    pub fn select_form(
+        &self,
+        paradigms: Vec<Paradigm>,
+        description: &str,
+    ) -> Result<Vec<Paradigm>, VocabError> {
+        let target = parse_form_description(description)?;
+        Ok(paradigms
+            .into_iter()
+            .map(|mut paradigm| {
+                keep_form(&mut paradigm, &target);
+                paradigm
+            })
+            .collect())
+    }
+
+    /**
+     * Random questions about the entries the filter allows, split between the kinds, ex: 10 latin to english and parsing questions.
+     * Fewer questions are returned when there are not enough entries to ask about.
+     */
+    pub fn quiz(&self, kinds: &[QuizKind], amount: usize) -> Result<Vec<Question>, VocabError> {
+        make_questions(&self.dictionary, kinds, &self.filter, amount)
+    }
+
+    /**
+     * If the answer to a quiz question is right
+     */
+    pub fn grade(&self, question: &Question, answer: &str) -> Result<bool, VocabError> {
+        question.grade(&self.dictionary, answer)
+    }
+
+    /**
+     * Random entries with senses the filter allows, without repeats or the excluded ids,
+     * ex: words to add to a study deck that are not in it yet
+     */
+    pub fn random_entries(
+        &self,
+        amount: usize,
+        exclude: &[i32],
+    ) -> Result<Vec<LatinWordInfo>, VocabError> {
+        random_latin_entries(&self.dictionary, &self.filter, amount, exclude)
+    }
+
+    /**
+     * A latin to english question about the entry of a study card, graded like a quiz question.
+     * None if the entry is no longer in the dictionary
+     */
+    pub fn study_question(&self, card: &Card) -> Result<Option<Question>, VocabError> {
+        match self.dictionary.get_latin_word_by_id(card.id) {
+            Some(entry) => Question::new(&self.dictionary, QuizKind::LatinToEnglish, entry),
+            None => Ok(None),
+        }
+    }
+
+    /**
+     * The cards of the deck as notes Anki can import, with the principle parts and senses as fields
+     */
+    pub fn export_deck(&self, deck: &Deck, format: ExportFormat) -> Result<String, VocabError> {
+        export_deck(&self.dictionary, deck, format)
+    }
+}
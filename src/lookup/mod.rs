@@ -0,0 +1,123 @@
+use crate::dictionary_structures::dictionary_values::{EnglishWordInfo, Form, LatinWordInfo, Stem};
+use crate::error::VocabError;
+use crate::translators::{DisplayType, Structure};
+use crate::utils::data::Dictionary;
+use serde::Serialize;
+
+/**
+ * A Latin dictionary entry with everything that points at it: its stems and the English words that translate to it
+ */
+#[derive(Debug, Clone, Serialize)]
+pub struct DictionaryEntry {
+    pub word: LatinWordInfo,
+    pub stems: Vec<Stem>,
+    pub english_words: Vec<EnglishWordInfo>,
+}
+
+/**
+ * Finds the Latin entry with the id, with its principle parts generated. None if there is no such entry
+ */
+pub fn lookup_latin(
+    dictionary: &Dictionary,
+    id: i32,
+) -> Result<Option<DictionaryEntry>, VocabError> {
+    let entry = match dictionary.get_latin_word_by_id(id) {
+        Some(entry) => entry,
+        None => return Ok(None),
+    };
+
+    let mut word = entry.clone();
+    word.form
+        .str_form_to_long_form(word.pos, Structure::LatinWordInfo);
+    word.generate_principle_parts()?;
+
+    let stems = dictionary
+        .get_stems_by_wid(id)
+        .into_iter()
+        .map(|stem| {
+            let mut stem = stem.clone();
+            stem.form.str_form_to_long_form(stem.pos, Structure::Stem);
+            stem
+        })
+        .collect();
+
+    Ok(Some(DictionaryEntry {
+        word,
+        stems,
+        english_words: lookup_english(dictionary, id),
+    }))
+}
+
+/**
+ * Every English entry with the wid, the id of the Latin entry it translates to
+ */
+pub fn lookup_english(dictionary: &Dictionary, wid: i32) -> Vec<EnglishWordInfo> {
+    dictionary
+        .get_english_words_by_wid(wid)
+        .into_iter()
+        .cloned()
+        .collect()
+}
+
+impl DictionaryEntry {
+    pub fn display(&self, display_type: DisplayType) {
+        match display_type {
+            DisplayType::Pretty(detailed) => {
+                println!("{}: {}", self.word.id, self.word.parts.join(", "));
+                println!("{}", self.word.pos.as_str());
+                match &self.word.form {
+                    Form::StrForm(form) => println!("{}", form),
+                    Form::LongForm(form) => println!("{}", form.as_clean_str()),
+                }
+                if detailed {
+                    println!("{}", self.word.info.as_str());
+                }
+                println!("{}", self.word.senses.join("; "));
+
+                if !self.stems.is_empty() {
+                    println!("\nstems:");
+                    for (index, stem) in self.stems.iter().enumerate() {
+                        let form = match &stem.form {
+                            Form::StrForm(form) => form.to_string(),
+                            Form::LongForm(form) => form.as_clean_str(),
+                        };
+                        println!(
+                            "  {}. {} | {} {}",
+                            index + 1,
+                            stem.orth,
+                            stem.pos.as_str(),
+                            form
+                        );
+                    }
+                }
+
+                if !self.english_words.is_empty() {
+                    println!("\nenglish:");
+                    display_english_words(&self.english_words);
+                }
+                println!();
+            }
+            DisplayType::Json => {
+                println!(
+                    "{}",
+                    serde_json::to_string_pretty(&self).unwrap_or_default()
+                );
+            }
+        }
+    }
+}
+
+/**
+ * One line per English entry: the word, its part of speech and how frequent it is
+ */
+pub fn display_english_words(english_words: &[EnglishWordInfo]) {
+    for english_word in english_words {
+        println!(
+            "  {} | {} | {} (wid {})",
+            english_word.orth,
+            english_word.pos.as_str(),
+            english_word.frequency_type.as_str(),
+            english_word.wid
+        );
+    }
+}
@@ -0,0 +1,453 @@
+use std::collections::HashMap;
+use std::io::{BufRead, Write};
+
+use serde_json::{json, Value};
+use vocab_vault::dictionary_structures::dictionary_values::Form;
+use vocab_vault::{
+    tokenize, LatinTranslationInfo, Token, Translation, TranslationType, Translator, VocabError,
+};
+
+// json-rpc error codes
+const PARSE_ERROR: i32 = -32700;
+const METHOD_NOT_FOUND: i32 = -32601;
+const INTERNAL_ERROR: i32 = -32603;
+// the diagnostic severity of unknown words
+const WARNING: u8 = 2;
+// full document text on every change
+const FULL_SYNC: u8 = 1;
+// translations are kept between changes, the cache is cleared when it gets this big
+const MAX_CACHED: usize = 20_000;
+
+/**
+ * A word of an open document. The line starts at 0 and start and end are counted in utf-16 code units,
+ * the way positions are sent by the client.
+ */
+struct Word {
+    token: Token,
+    line: usize,
+    start: usize,
+    end: usize,
+}
+
+/**
+ * A language server for Latin documents over stdin and stdout.
+ * Every word of an open document is translated when it is opened or changed:
+ * hovers show the definitions of a word, unknown words are warnings and their spelling suggestions are quick fixes.
+ */
+pub struct LanguageServer {
+    translator: Translator,
+    documents: HashMap<String, Vec<Word>>,
+    translations: HashMap<String, Translation>,
+    shut_down: bool,
+}
+
+impl LanguageServer {
+    pub fn new(translator: Translator) -> LanguageServer {
+        LanguageServer {
+            translator,
+            documents: HashMap::new(),
+            translations: HashMap::new(),
+            shut_down: false,
+        }
+    }
+
+    /**
+     * Handles messages until the client sends exit or closes stdin
+     */
+    pub fn run(&mut self) -> Result<(), VocabError> {
+        let stdin = std::io::stdin();
+        let mut input = stdin.lock();
+
+        while let Some(body) = read_message(&mut input)? {
+            // a malformed message is answered with a parse error, the server keeps reading
+            let message: Value = match serde_json::from_slice(&body) {
+                Ok(message) => message,
+                Err(error) => {
+                    write_message(&json!({
+                        "jsonrpc": "2.0",
+                        "id": Value::Null,
+                        "error": { "code": PARSE_ERROR, "message": error.to_string() },
+                    }))?;
+                    continue;
+                }
+            };
+            if message["method"] == "exit" {
+                // exiting without a shutdown request first is an error
+                if !self.shut_down {
+                    std::process::exit(1);
+                }
+                break;
+            }
+            self.handle(&message)?;
+        }
+
+        Ok(())
+    }
+
+    fn handle(&mut self, message: &Value) -> Result<(), VocabError> {
+        let method = message["method"].as_str().unwrap_or_default();
+        let params = &message["params"];
+
+        let id = match message.get("id") {
+            Some(id) if !method.is_empty() => id,
+            // responses to requests the server never sent
+            Some(_) => return Ok(()),
+            None => {
+                // a notification, errors can only be logged
+                if let Err(error) = self.notify(method, params) {
+                    eprintln!("{}: {}", method, error);
+                }
+                return Ok(());
+            }
+        };
+
+        let result = match method {
+            "initialize" => Ok(json!({
+                "capabilities": {
+                    "textDocumentSync": FULL_SYNC,
+                    "hoverProvider": true,
+                    "codeActionProvider": true,
+                },
+                "serverInfo": {
+                    "name": env!("CARGO_PKG_NAME"),
+                    "version": env!("CARGO_PKG_VERSION"),
+                },
+            })),
+            "shutdown" => {
+                self.shut_down = true;
+                Ok(Value::Null)
+            }
+            "textDocument/hover" => self.hover(params),
+            "textDocument/codeAction" => self.code_actions(params),
+            _ => {
+                return write_message(&json!({
+                    "jsonrpc": "2.0",
+                    "id": id,
+                    "error": { "code": METHOD_NOT_FOUND, "message": format!("Unknown method: {}", method) },
+                }))
+            }
+        };
+
+        match result {
+            Ok(result) => write_message(&json!({ "jsonrpc": "2.0", "id": id, "result": result })),
+            Err(error) => write_message(&json!({
+                "jsonrpc": "2.0",
+                "id": id,
+                "error": { "code": INTERNAL_ERROR, "message": error.to_string() },
+            })),
+        }
+    }
+
+    fn notify(&mut self, method: &str, params: &Value) -> Result<(), VocabError> {
+        let uri = params["textDocument"]["uri"].as_str().unwrap_or_default();
+
+        match method {
+            "textDocument/didOpen" => {
+                let text = params["textDocument"]["text"].as_str().unwrap_or_default();
+                self.update(uri, text)
+            }
+            "textDocument/didChange" => {
+                // with full sync the last change is the whole document
+                let changes = params["contentChanges"].as_array();
+                match changes.and_then(|changes| changes.last()) {
+                    Some(change) => self.update(uri, change["text"].as_str().unwrap_or_default()),
+                    None => Ok(()),
+                }
+            }
+            "textDocument/didClose" => {
+                self.documents.remove(uri);
+                publish_diagnostics(uri, Vec::new())
+            }
+            _ => Ok(()),
+        }
+    }
+
+    // translates every word of the document that is not cached yet and publishes the unknown words
+    fn update(&mut self, uri: &str, text: &str) -> Result<(), VocabError> {
+        if self.translations.len() > MAX_CACHED {
+            self.translations.clear();
+        }
+
+        let lines: Vec<&str> = text.split('\n').collect();
+        let mut words = Vec::new();
+        for token in tokenize(text) {
+            // numbers are not words
+            if token.word.chars().any(|c| c.is_ascii_digit()) {
+                continue;
+            }
+            if !self.translations.contains_key(&token.word) {
+                let translation = self.translator.latin_translation(&token.word)?;
+                self.translations.insert(token.word.clone(), translation);
+            }
+
+            let line = token.line - 1;
+            let start = utf16_len(
+                lines
+                    .get(line)
+                    .map(|line| line.chars().take(token.column - 1).collect::<String>())
+                    .as_deref()
+                    .unwrap_or_default(),
+            );
+            let end = start + utf16_len(&token.word);
+            words.push(Word {
+                token,
+                line,
+                start,
+                end,
+            });
+        }
+
+        let diagnostics = words
+            .iter()
+            .filter_map(|word| self.diagnostic(word))
+            .collect();
+        self.documents.insert(uri.to_string(), words);
+        publish_diagnostics(uri, diagnostics)
+    }
+
+    fn hover(&self, params: &Value) -> Result<Value, VocabError> {
+        let word = match self.word_at(params) {
+            Some(word) => word,
+            None => return Ok(Value::Null),
+        };
+        let definitions = self.definitions(word);
+        if definitions.is_empty() {
+            return Ok(Value::Null);
+        }
+
+        let contents = definitions
+            .iter()
+            .map(hover_text)
+            .collect::<Vec<String>>()
+            .join("\n\n---\n\n");
+
+        Ok(json!({
+            "contents": { "kind": "markdown", "value": contents },
+            "range": range(word),
+        }))
+    }
+
+    fn code_actions(&self, params: &Value) -> Result<Value, VocabError> {
+        let uri = params["textDocument"]["uri"].as_str().unwrap_or_default();
+        let words = match self.documents.get(uri) {
+            Some(words) => words,
+            None => return Ok(json!([])),
+        };
+        let start = position(&params["range"]["start"]);
+        let end = position(&params["range"]["end"]);
+
+        let mut actions = Vec::new();
+        for word in words {
+            if (word.line, word.end) < start || (word.line, word.start) > end {
+                continue;
+            }
+            let diagnostic = match self.diagnostic(word) {
+                Some(diagnostic) => diagnostic,
+                None => continue,
+            };
+
+            for (index, suggestion) in self.suggestions(word).iter().enumerate() {
+                let replacement = match_case(&word.token.word, suggestion);
+                actions.push(json!({
+                    "title": format!("Replace with {}", replacement),
+                    "kind": "quickfix",
+                    "diagnostics": [diagnostic],
+                    "isPreferred": index == 0,
+                    "edit": {
+                        "changes": {
+                            uri: [{ "range": range(word), "newText": replacement }],
+                        },
+                    },
+                }));
+            }
+        }
+
+        Ok(Value::Array(actions))
+    }
+
+    // an unknown word, with its closest spellings in the message
+    fn diagnostic(&self, word: &Word) -> Option<Value> {
+        if !self.definitions(word).is_empty() {
+            return None;
+        }
+
+        let suggestions = self.suggestions(word);
+        let message = match suggestions.is_empty() {
+            true => format!("Unknown word: {}", word.token.word),
+            false => format!(
+                "Unknown word: {}, did you mean {}?",
+                word.token.word,
+                suggestions.join(", ")
+            ),
+        };
+
+        Some(json!({
+            "range": range(word),
+            "severity": WARNING,
+            "source": env!("CARGO_PKG_NAME"),
+            "message": message,
+        }))
+    }
+
+    fn definitions(&self, word: &Word) -> &[LatinTranslationInfo] {
+        match self.translations.get(&word.token.word) {
+            Some(Translation {
+                definitions: TranslationType::Latin(definitions),
+                ..
+            }) => definitions,
+            _ => &[],
+        }
+    }
+
+    fn suggestions(&self, word: &Word) -> Vec<&str> {
+        self.translations
+            .get(&word.token.word)
+            .and_then(|translation| translation.suggestions.as_ref())
+            .map(|suggestions| {
+                suggestions
+                    .iter()
+                    .map(|suggestion| suggestion.word.as_str())
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    fn word_at(&self, params: &Value) -> Option<&Word> {
+        let uri = params["textDocument"]["uri"].as_str()?;
+        let (line, character) = position(&params["position"]);
+
+        self.documents
+            .get(uri)?
+            .iter()
+            .find(|word| word.line == line && word.start <= character && character <= word.end)
+    }
+}
+
+/**
+ * The principle parts, part of speech, form, the forms the word was read as and the senses of a definition, as markdown
+ */
+fn hover_text(definition: &LatinTranslationInfo) -> String {
+    let word = &definition.word;
+    let mut lines = Vec::new();
+
+    if let Some(tricks) = &definition.tricks {
+        for trick in tricks {
+            lines.push(format!("_{}_", trick));
+        }
+    }
+
+    let parts = match word.parts.is_empty() {
+        true => word.orth.clone(),
+        false => word.parts.join(", "),
+    };
+    lines.push(format!("**{}** ({})", parts, word.pos.as_str()));
+
+    let form = form_str(&word.form);
+    if !form.is_empty() {
+        lines.push(form);
+    }
+
+    if let Some(inflections) = &definition.inflections {
+        let mut forms: Vec<String> = Vec::new();
+        for inflection in inflections {
+            let form = form_str(&inflection.form);
+            if form.is_empty() {
+                continue;
+            }
+            let form = format!("`{}.{}` {}", definition.stem.orth, inflection.ending, form);
+            if !forms.contains(&form) {
+                forms.push(form);
+            }
+        }
+        lines.extend(forms);
+    }
+
+    lines.push(word.senses.join("; "));
+    // two trailing spaces end a markdown line without a paragraph break
+    lines.join("  \n")
+}
+
+fn form_str(form: &Form) -> String {
+    match form {
+        Form::StrForm(form) => form.as_str().to_string(),
+        Form::LongForm(form) => form.as_clean_str(),
+    }
+}
+
+fn range(word: &Word) -> Value {
+    json!({
+        "start": { "line": word.line, "character": word.start },
+        "end": { "line": word.line, "character": word.end },
+    })
+}
+
+fn position(position: &Value) -> (usize, usize) {
+    (
+        position["line"].as_u64().unwrap_or_default() as usize,
+        position["character"].as_u64().unwrap_or_default() as usize,
+    )
+}
+
+fn utf16_len(text: &str) -> usize {
+    text.chars().map(char::len_utf16).sum()
+}
+
+// a suggestion for a capitalized word is capitalized, ex: Puelam -> Puellam
+fn match_case(word: &str, suggestion: &str) -> String {
+    let mut suggestion_chars = suggestion.chars();
+    match (word.chars().next(), suggestion_chars.next()) {
+        (Some(first), Some(suggestion_first)) if first.is_uppercase() => suggestion_first
+            .to_uppercase()
+            .chain(suggestion_chars)
+            .collect(),
+        _ => suggestion.to_string(),
+    }
+}
+
+fn publish_diagnostics(uri: &str, diagnostics: Vec<Value>) -> Result<(), VocabError> {
+    write_message(&json!({
+        "jsonrpc": "2.0",
+        "method": "textDocument/publishDiagnostics",
+        "params": { "uri": uri, "diagnostics": diagnostics },
+    }))
+}
+
+/**
+ * Reads one message: headers, an empty line and a body of Content-Length bytes. None at the end of the input
+ */
+fn read_message(input: &mut impl BufRead) -> Result<Option<Vec<u8>>, VocabError> {
+    let mut content_length: Option<usize> = None;
+    let mut header = String::new();
+
+    loop {
+        header.clear();
+        if input.read_line(&mut header)? == 0 {
+            return Ok(None);
+        }
+        let header = header.trim();
+        if header.is_empty() {
+            // a blank line before any header is skipped
+            if content_length.is_some() {
+                break;
+            }
+            continue;
+        }
+        if let Some((name, value)) = header.split_once(':') {
+            if name.eq_ignore_ascii_case("Content-Length") {
+                content_length = value.trim().parse().ok();
+            }
+        }
+    }
+
+    let mut body = vec![0; content_length.unwrap_or_default()];
+    input.read_exact(&mut body)?;
+    Ok(Some(body))
+}
+
+fn write_message(message: &Value) -> Result<(), VocabError> {
+    let body = serde_json::to_string(message)?;
+    let mut stdout = std::io::stdout().lock();
+    write!(stdout, "Content-Length: {}\r\n\r\n{}", body.len(), body)?;
+    stdout.flush()?;
+    Ok(())
+}
@@ -1,24 +1,57 @@
 pub mod cli;
-pub mod dictionary_structures;
-pub mod translators;
-pub mod use_data;
-pub mod utils;
+pub mod lsp;
+pub mod server;
+pub mod tui;
 
-use std::io::Write;
+use std::io::{BufRead, IsTerminal, Write};
+use std::path::Path;
 
 use cli::{Arg, Cli, Command};
-use dictionary_structures::dictionary_keys::PartOfSpeech;
-use translators::english_to_latin::translate_english_to_latin;
-use translators::latin_to_english::translate_latin_to_english;
-use translators::{DisplayType, Language, Translation, TranslationType};
-use use_data::{get_list, WordType};
-use utils::data::{get_english_dictionary, get_latin_dictionary};
-use utils::sanitize_word;
+use lsp::LanguageServer;
+use serde::Serialize;
+use vocab_vault::dictionary_structures::dictionary_keys::{
+    Age, Area, Frequency, Geography, Source,
+};
+use vocab_vault::lookup::display_english_words;
+use vocab_vault::study::today;
+use vocab_vault::translators::{DisplayType, Language};
+use vocab_vault::use_data::parse_keys;
+use vocab_vault::{
+    tokenize, Card, Deck, ExportFormat, LatinWordInfo, Orthography, OutputList, Paradigm,
+    PartOfSpeech, Question, QuizKind, Rating, Token, Translation, TranslationType, Translator,
+    VocabError, WordFilter, WordType,
+};
 
 use crate::cli::ArgValue;
-//TODO: add a command for searching a word by id in english or latin dictionary
 //TODO: display the amount of time it took for a command to execute
 fn main() {
+    if let Err(error) = run() {
+        eprintln!("{}", error);
+        std::process::exit(exit_code(&error));
+    }
+}
+
+/**
+ * Usage errors exit with 2, problems with the dictionary data with 3 and anything else with 1
+ */
+fn exit_code(error: &VocabError) -> i32 {
+    match error {
+        VocabError::MissingArgument(_)
+        | VocabError::InvalidArgument { .. }
+        | VocabError::MissingRequiredArgument { .. }
+        | VocabError::CommandNotFound(_)
+        | VocabError::InvalidWordType(_)
+        | VocabError::InvalidPartOfSpeech(_) => 2,
+        VocabError::MissingNValue { .. }
+        | VocabError::MissingPrinciplePartValue { .. }
+        | VocabError::NoPrinciplePartEndings(_)
+        | VocabError::NoGenerator(_)
+        | VocabError::DataLoad { .. } => 3,
+        VocabError::LanguageMismatch { .. } | VocabError::Io(_) | VocabError::Json(_) => 1,
+    }
+}
+
+fn run() -> Result<(), VocabError> {
     let global_args_for_translation = vec![
         Arg::new()
             .with_name("words")
@@ -47,19 +80,106 @@ fn main() {
             .with_long("detailed")
             .with_help("Adds more information to the pretty output")
             .requires("pretty"),
+        Arg::new()
+            .with_name("file")
+            .with_short('f')
+            .with_long("file")
+            .with_value_name("FILE")
+            .with_help("Translates every word of a file line by line, - reads from stdin"),
+    ];
+
+    let filter_args = vec![
+        Arg::new()
+            .with_name("age")
+            .with_short('A')
+            .with_long("age")
+            .with_value_name("AGE")
+            .with_help("The ages to include, separated by commas, ex: classical,late"),
+        Arg::new()
+            .with_name("area")
+            .with_short('R')
+            .with_long("area")
+            .with_value_name("AREA")
+            .with_help("The areas to include, separated by commas, ex: legal,warfare"),
+        Arg::new()
+            .with_name("geo")
+            .with_short('G')
+            .with_long("geo")
+            .with_value_name("GEO")
+            .with_help("The regions to include, separated by commas, ex: britain,rome"),
+        Arg::new()
+            .with_name("min-freq")
+            .with_short('F')
+            .with_long("min-freq")
+            .with_value_name("FREQ")
+            .with_help("The rarest frequency to include, ex: common"),
+        Arg::new()
+            .with_name("source")
+            .with_short('S')
+            .with_long("source")
+            .with_value_name("SOURCE")
+            .with_help("The dictionaries to include, separated by commas, ex: lewis-short,oxford"),
     ];
 
     let cli = Cli::new().with_default_command("tui").with_commands(vec![
         Command::new("transEng", "Translate english to latin")
-            .with_args(&global_args_for_translation),
+            .with_args(&global_args_for_translation)
+            .with_args(&filter_args)
+            .with_arg(
+                Arg::new()
+                    .with_name("phrases")
+                    .with_short('P')
+                    .with_long("phrases")
+                    .with_help("Reads the words as phrases separated by commas, ex: \"to go out, the king\""),
+            ),
         Command::new("transLat", "Translate latin to english")
             .with_args(&global_args_for_translation)
+            .with_args(&filter_args)
             .with_arg(
                 Arg::new()
                     .with_name("tricks")
                     .with_short('t')
                     .with_long("tricks")
                     .with_help("Will attempt to use various tricks to find the translation"),
+            )
+            .with_arg(
+                Arg::new()
+                    .with_name("context")
+                    .with_short('c')
+                    .with_long("context")
+                    .with_help("Reads the words as a sentence and ranks each word's readings by how they fit their neighbours"),
+            )
+            .with_arg(
+                Arg::new()
+                .with_name("classical-only")
+                .with_short('C')
+                .with_long("classical-only")
+                .with_help("Hides archaic, early, late and medieval inflections"),
+            )
+            .with_arg(
+                Arg::new()
+                .with_name("quantity")
+                .with_short('q')
+                .with_long("quantity")
+                .with_help("Marks the long vowels of principle parts and endings with macrons"),
+            )
+            .with_arg(
+                Arg::new()
+                .with_name("orthography")
+                .with_short('o')
+                .with_long("orthography")
+                .with_value_name("RULES")
+                .default("all")
+                .with_help("The spellings to normalize, separated by commas: j, uv, ligatures, k, all or none"),
+            )
+            .with_arg(
+                Arg::new()
+                .with_name("suggestions")
+                .with_short('n')
+                .with_long("suggestions")
+                .with_value_name("NUMBER")
+                .default("3")
+                .with_help("The number of spellings to suggest for words that are not found, 0 for none"),
             ),
         Command::new("getList", "Gets a list of words based on the options provided")
             .with_arg(
@@ -130,7 +250,349 @@ fn main() {
                 .with_long("to")
                 .with_value_name("TO")
                 .with_help("The file to export the results to"),
+            )
+            .with_args(&filter_args),
+        Command::new("inflect", "Generates every inflected form of a latin word")
+            .with_arg(
+                Arg::new()
+                .with_name("word")
+                .with_value_name("WORD")
+                .with_help("A latin word in any form, or the id of a dictionary entry"),
+            )
+            .with_arg(
+                Arg::new()
+                .with_name("pretty")
+                .with_short('p')
+                .with_long("pretty")
+                .with_help("Prints the forms as tables"),
+            )
+            .with_arg(
+                Arg::new()
+                .with_name("detailed")
+                .with_short('d')
+                .with_long("detailed")
+                .with_help("Adds the word info and senses to the tables")
+                .requires("pretty"),
+            ),
+        Command::new("lookup", "Gets a dictionary entry by id")
+            .with_arg(
+                Arg::new()
+                .with_name("id")
+                .with_value_name("ID")
+                .with_help("The id of a latin entry, or the wid of english entries"),
+            )
+            .with_arg(
+                Arg::new()
+                .with_name("english")
+                .with_short('e')
+                .with_long("english")
+                .with_help("Gets every english entry with the wid instead of the latin entry"),
+            )
+            .with_arg(
+                Arg::new()
+                .with_name("pretty")
+                .with_short('p')
+                .with_long("pretty")
+                .with_help("Prints the output in a pretty format"),
+            )
+            .with_arg(
+                Arg::new()
+                .with_name("detailed")
+                .with_short('d')
+                .with_long("detailed")
+                .with_help("Adds the word info to the pretty output")
+                .requires("pretty"),
             ),
+        Command::new("search", "Searches the senses of latin entries")
+            .with_arg(
+                Arg::new()
+                .with_name("query")
+                .with_value_name("QUERY")
+                .with_help("Words that must all be in the senses, \"quoted phrases\", /regexes/ and OR between alternatives, ex: ship OR \"war vessel\""),
+            )
+            .with_arg(
+                Arg::new()
+                .with_name("max")
+                .with_short('m')
+                .with_long("max")
+                .with_value_name("MAX")
+                .default("20")
+                .with_help("The maximum number of entries"),
+            )
+            .with_arg(
+                Arg::new()
+                .with_name("pos")
+                .with_short('P')
+                .with_long("pos")
+                .with_value_name("POS")
+                .with_help("The part of speeches to include, separated by commas"),
+            )
+            .with_arg(
+                Arg::new()
+                .with_name("freq")
+                .with_short('f')
+                .with_long("freq")
+                .with_value_name("FREQ")
+                .with_help("The frequencies to include, separated by commas, ex: very-frequent,frequent,common"),
+            )
+            .with_args(&filter_args)
+            .with_arg(
+                Arg::new()
+                .with_name("pretty")
+                .with_short('p')
+                .with_long("pretty")
+                .with_help("Prints the output in a pretty format"),
+            )
+            .with_arg(
+                Arg::new()
+                .with_name("detailed")
+                .with_short('d')
+                .with_long("detailed")
+                .with_help("Adds the word info to the pretty output")
+                .requires("pretty"),
+            ),
+        Command::new("form", "Generates a form of a latin word from its description")
+            .with_arg(
+                Arg::new()
+                .with_name("word")
+                .with_value_name("WORD FORM")
+                .with_help("A latin word in any form or the id of a dictionary entry, then the form, ex: amo 3rd person plural perfect active indicative"),
+            )
+            .with_arg(
+                Arg::new()
+                .with_name("pretty")
+                .with_short('p')
+                .with_long("pretty")
+                .with_help("Prints one line per form"),
+            ),
+        Command::new("derive", "Shows how a latin word is built from a dictionary word with prefixes and suffixes")
+            .with_arg(
+                Arg::new()
+                .with_name("word")
+                .with_value_name("WORD")
+                .with_help("The latin word to split"),
+            )
+            .with_arg(
+                Arg::new()
+                .with_name("max")
+                .with_short('m')
+                .with_long("max")
+                .with_value_name("MAX")
+                .default("6")
+                .with_help("The maximum number of derivations"),
+            )
+            .with_arg(
+                Arg::new()
+                .with_name("pretty")
+                .with_short('p')
+                .with_long("pretty")
+                .with_help("Prints each derivation step by step"),
+            ),
+        Command::new("build-data", "Generates the json dictionaries from the raw Whitaker's Words files")
+            .with_arg(
+                Arg::new()
+                .with_name("input")
+                .with_short('i')
+                .with_long("input")
+                .with_value_name("DIR")
+                .with_help("The folder with the raw files, defaults to Whitakers-Words-Data"),
+            )
+            .with_arg(
+                Arg::new()
+                .with_name("output")
+                .with_short('o')
+                .with_long("output")
+                .with_value_name("DIR")
+                .with_help("The folder to write the json files to, defaults to dictionary"),
+            ),
+        Command::new("quiz", "Drills vocabulary, principle parts and parsing with random words")
+            .with_arg(
+                Arg::new()
+                    .with_name("kinds")
+                    .with_short('k')
+                    .with_long("kinds")
+                    .with_value_name("KINDS")
+                    .with_help("The questions to ask, separated by commas: latin, english, parts, parsing (default: all)"),
+            )
+            .with_arg(
+                Arg::new()
+                    .with_name("amount")
+                    .with_short('a')
+                    .with_long("amount")
+                    .with_value_name("AMOUNT")
+                    .default("10")
+                    .with_help("The number of questions"),
+            )
+            .with_arg(
+                Arg::new()
+                    .with_name("pos")
+                    .with_short('p')
+                    .with_long("pos")
+                    .with_value_name("POS")
+                    .with_help("The part of speeches to include, separated by commas"),
+            )
+            .with_arg(
+                Arg::new()
+                    .with_name("max")
+                    .with_short('m')
+                    .with_long("max")
+                    .with_value_name("MAX")
+                    .with_help("The maximum word length"),
+            )
+            .with_arg(
+                Arg::new()
+                    .with_name("min")
+                    .with_short('n')
+                    .with_long("min")
+                    .with_value_name("MIN")
+                    .with_help("The minimum word length"),
+            )
+            .with_arg(
+                Arg::new()
+                    .with_name("exact")
+                    .with_short('e')
+                    .with_long("exact")
+                    .with_value_name("EXACT")
+                    .with_help("The exact word length"),
+            )
+            .with_args(&filter_args),
+        Command::new("study", "Reviews a deck of words with spaced repetition, words are added to it by orth or id")
+            .with_arg(
+                Arg::new()
+                .with_name("action")
+                .with_value_name("ACTION WORDS")
+                .with_help("review (default), add, remove, list or export, then the words or ids to add or remove, ex: add amo, rex, 1234"),
+            )
+            .with_arg(
+                Arg::new()
+                    .with_name("deck")
+                    .with_short('d')
+                    .with_long("deck")
+                    .with_value_name("DECK")
+                    .default("default")
+                    .with_help("The name of the deck"),
+            )
+            .with_arg(
+                Arg::new()
+                    .with_name("amount")
+                    .with_short('a')
+                    .with_long("amount")
+                    .with_value_name("AMOUNT")
+                    .default("20")
+                    .with_help("The most cards to review"),
+            )
+            .with_arg(
+                Arg::new()
+                    .with_name("random")
+                    .with_short('r')
+                    .with_long("random")
+                    .with_value_name("COUNT")
+                    .with_help("Adds random words that fit the filter"),
+            )
+            .with_arg(
+                Arg::new()
+                    .with_name("output")
+                    .with_short('o')
+                    .with_long("output")
+                    .with_value_name("FILE")
+                    .with_help("The file to export to, prints the deck if missing"),
+            )
+            .with_arg(
+                Arg::new()
+                    .with_name("format")
+                    .with_short('f')
+                    .with_long("format")
+                    .with_value_name("FORMAT")
+                    .default("tsv")
+                    .with_help("The export format: tsv or csv"),
+            )
+            .with_arg(
+                Arg::new()
+                    .with_name("pos")
+                    .with_short('p')
+                    .with_long("pos")
+                    .with_value_name("POS")
+                    .with_help("The part of speeches to include, separated by commas"),
+            )
+            .with_arg(
+                Arg::new()
+                    .with_name("max")
+                    .with_short('m')
+                    .with_long("max")
+                    .with_value_name("MAX")
+                    .with_help("The maximum word length"),
+            )
+            .with_arg(
+                Arg::new()
+                    .with_name("min")
+                    .with_short('n')
+                    .with_long("min")
+                    .with_value_name("MIN")
+                    .with_help("The minimum word length"),
+            )
+            .with_arg(
+                Arg::new()
+                    .with_name("exact")
+                    .with_short('e')
+                    .with_long("exact")
+                    .with_value_name("EXACT")
+                    .with_help("The exact word length"),
+            )
+            .with_args(&filter_args),
+        Command::new("serve", "Serves the translations as json over http on localhost")
+            .with_arg(
+                Arg::new()
+                    .with_name("port")
+                    .with_short('p')
+                    .with_long("port")
+                    .with_value_name("PORT")
+                    .default("8080")
+                    .with_help("The port to listen on"),
+            )
+            .with_arg(
+                Arg::new()
+                    .with_name("max")
+                    .with_short('m')
+                    .with_long("max")
+                    .with_value_name("MAX")
+                    .default("6")
+                    .with_help("The maximum number of translations per definition"),
+            )
+            .with_arg(
+                Arg::new()
+                    .with_name("sort")
+                    .with_short('s')
+                    .with_long("sort")
+                    .with_help("Sort the output by word frequency"),
+            )
+            .with_arg(
+                Arg::new()
+                    .with_name("tricks")
+                    .with_short('t')
+                    .with_long("tricks")
+                    .with_help("Will attempt to use various tricks to find the translation"),
+            )
+            .with_args(&filter_args),
+        Command::new("lsp", "Starts a language server for latin documents over stdio")
+            .with_arg(
+                Arg::new()
+                    .with_name("max")
+                    .with_short('m')
+                    .with_long("max")
+                    .with_value_name("MAX")
+                    .default("6")
+                    .with_help("The maximum number of definitions per word"),
+            )
+            .with_arg(
+                Arg::new()
+                    .with_name("suggestions")
+                    .with_short('n')
+                    .with_long("suggestions")
+                    .with_value_name("COUNT")
+                    .default("3")
+                    .with_help("The number of corrections offered for an unknown word"),
+            )
+            .with_args(&filter_args),
         Command::new("help", "Helps you")
             .with_arg(
                 Arg::new()
@@ -141,220 +603,1134 @@ fn main() {
         Command::new("tui", "Starts the tui (.help for info)"),
     ]);
 
-    let command = cli.match_commands();
+    let command = cli.match_commands()?;
+    if command.asks_for_help() {
+        return cli.help(Some(command.name.to_string()));
+    }
+    command.check_required_args()?;
 
     match command.name {
         "transEng" => {
-            let words = command.get_value().throw_if_none();
-            let max = command
-                .get_value_of("max")
-                .throw_if_none()
-                .parse::<usize>()
-                .unwrap();
+            let input = get_input(command.get_value(), command.get_value_of("file"))?;
+            let max = parse_number("max", &command.get_value_of("max").throw_if_none()?)?;
             let sort = command.has("sort");
             let pretty = command.has("pretty");
             let detailed = command.has("detailed");
+            let phrases = command.has("phrases");
+            let filter = parse_filter(&command_options(command))?;
+            let translator = Translator::new()?
+                .with_max(max)
+                .with_sort(sort)
+                .with_filter(filter);
 
-            english_to_latin(&words, max, sort, pretty, detailed);
+            match input {
+                Input::Words(words) => {
+                    english_to_latin(&translator, &words, phrases, pretty, detailed)?
+                }
+                Input::Stream(reader) => translate_stream(
+                    &translator,
+                    reader,
+                    Language::English,
+                    false,
+                    pretty,
+                    detailed,
+                )?,
+            }
         }
         "transLat" => {
-            let words = command.get_value().throw_if_none();
-            let max = command
-                .get_value_of("max")
-                .throw_if_none()
-                .parse::<usize>()
-                .unwrap();
+            let input = get_input(command.get_value(), command.get_value_of("file"))?;
+            let max = parse_number("max", &command.get_value_of("max").throw_if_none()?)?;
             let sort = command.has("sort");
             let pretty = command.has("pretty");
             let detailed = command.has("detailed");
             let tricks = command.has("tricks");
+            let context = command.has("context");
+            let classical_only = command.has("classical-only");
+            let vowel_quantity = command.has("quantity");
+            let orthography =
+                Orthography::from_rules(&command.get_value_of("orthography").throw_if_none()?)?;
+            let suggestions = parse_number(
+                "suggestions",
+                &command.get_value_of("suggestions").throw_if_none()?,
+            )?;
+            let translator = Translator::new()?
+                .with_max(max)
+                .with_tricks(tricks)
+                .with_sort(sort)
+                .with_classical_only(classical_only)
+                .with_vowel_quantity(vowel_quantity)
+                .with_orthography(orthography)
+                .with_suggestions(suggestions)
+                .with_filter(parse_filter(&command_options(command))?);
 
-            latin_to_english(&words, max, tricks, sort, pretty, detailed);
+            match input {
+                Input::Words(words) if context => {
+                    analyze_sentence(&translator, &words, pretty, detailed)?
+                }
+                Input::Words(words) => latin_to_english(&translator, &words, pretty, detailed)?,
+                Input::Stream(reader) => translate_stream(
+                    &translator,
+                    reader,
+                    Language::Latin,
+                    context,
+                    pretty,
+                    detailed,
+                )?,
+            }
         }
         "getList" => {
-            let type_of_words = command.get_value().throw_if_none();
-            let pos = command.get_value_of("pos");
-            let max = command.get_value_of("max");
-            let min = command.get_value_of("min");
-            let exact = command.get_value_of("exact");
+            let type_of_words = command.get_value().throw_if_none()?;
             let amount = command.get_value_of("amount");
             let random = command.has("random");
             let display = command.has("display");
             let to = command.get_value_of("to");
 
-            if !WordType::is_valid_word_type(&type_of_words) {
-                println!(
-                    "Invalid type of words. Please use `help` to see the available types of words."
-                );
-                return;
-            }
+            let word_type = type_of_words
+                .parse::<WordType>()
+                .map_err(|_| VocabError::InvalidWordType(type_of_words.clone()))?;
+            let filter = parse_list_filter(&command_options(command))?;
 
-            let word_type = WordType::from_str(type_of_words.as_str()).unwrap_or_else(|e| {
-                println!("{}", e);
-                std::process::exit(0);
-            });
-
-            let pos_list = match pos {
-                ArgValue::Present(pos) => {
-                    let pos_list: Vec<PartOfSpeech> = pos
-                        .split(",")
-                        .map(|pos| PartOfSpeech::dict_key_to_part_of_speech(pos))
-                        .collect();
-                    Some(pos_list)
-                }
+            let amount = match amount {
+                ArgValue::Present(amount) => Some(parse_number("amount", &amount)? as i32),
                 ArgValue::Missing(_) => None,
             };
 
-            if pos_list.is_some() && pos_list.as_ref().unwrap().contains(&PartOfSpeech::Unknown) {
-                println!("Invalid part of speech entered.");
-                println!("Please use the following: noun, verb, participle, adjective, preposition, pronoun, interjection, numeral, conjunction, adverb, number, supine, packon, tackon, prefix, suffix");
-                std::process::exit(0);
-            }
-
-            let max = match max {
-                ArgValue::Present(max) => Some(max.parse::<usize>().unwrap() as i32),
+            let to = match to {
+                ArgValue::Present(to) => Some(to),
                 ArgValue::Missing(_) => None,
             };
 
-            let min = match min {
-                ArgValue::Present(min) => Some(min.parse::<usize>().unwrap() as i32),
-                ArgValue::Missing(_) => None,
-            };
+            let list = Translator::new()?.get_list(word_type, &filter, amount, random)?;
 
-            let exact = match exact {
-                ArgValue::Present(exact) => Some(exact.parse::<usize>().unwrap() as i32),
-                ArgValue::Missing(_) => None,
-            };
+            if display {
+                println!("{}", serde_json::to_string_pretty(&list)?);
+            }
 
-            let amount = match amount {
-                ArgValue::Present(amount) => Some(amount.parse::<usize>().unwrap() as i32),
-                ArgValue::Missing(_) => None,
-            };
+            if let Some(to) = to {
+                export_list(&list, to)?;
+            }
+        }
+        "inflect" => {
+            let word = command.get_value().throw_if_none()?;
+            let pretty = command.has("pretty");
+            let detailed = command.has("detailed");
 
-            let to = match to {
-                ArgValue::Present(to) => Some(to),
-                ArgValue::Missing(_) => None,
+            let translator = Translator::new()?.with_tricks(true);
+            inflect(&translator, &word, pretty, detailed)?;
+        }
+        "lookup" => {
+            let id = parse_number("id", &command.get_value().throw_if_none()?)? as i32;
+            let english = command.has("english");
+            let pretty = command.has("pretty");
+            let detailed = command.has("detailed");
+
+            lookup(&Translator::new()?, id, english, pretty, detailed)?;
+        }
+        "search" => {
+            let query = command.get_value().throw_if_none()?;
+            let max = parse_number("max", &command.get_value_of("max").throw_if_none()?)?;
+            let pretty = command.has("pretty");
+            let detailed = command.has("detailed");
+
+            let mut filter = parse_filter(&command_options(command))?;
+            if let Some(pos) = command.get_value_of("pos").to_option() {
+                filter = filter.with_pos(parse_pos_list(&pos)?);
+            }
+            if let Some(freq) = command.get_value_of("freq").to_option() {
+                filter = filter.with_freq(parse_keys(
+                    "freq",
+                    &freq,
+                    Frequency::dict_key_to_frequency,
+                    Frequency::as_str,
+                )?);
+            }
+
+            let translator = Translator::new()?.with_filter(filter);
+            search(&translator, &query, max, pretty, detailed)?;
+        }
+        "form" => {
+            let value = command.get_value().throw_if_none()?;
+            let pretty = command.has("pretty");
+
+            let (word, description) = parse_form(&value)?;
+
+            let translator = Translator::new()?.with_tricks(true);
+            form(&translator, word, description, pretty)?;
+        }
+        "derive" => {
+            let word = command.get_value().throw_if_none()?;
+            let max = parse_number("max", &command.get_value_of("max").throw_if_none()?)?;
+            let pretty = command.has("pretty");
+
+            let translator = Translator::new()?.with_max(max);
+            derive(&translator, &word, pretty)?;
+        }
+        "build-data" => {
+            let input = command
+                .get_value_of("input")
+                .to_option()
+                .unwrap_or("Whitakers-Words-Data".to_string());
+            let output = command
+                .get_value_of("output")
+                .to_option()
+                .unwrap_or("dictionary".to_string());
+
+            build_data(&input, &output)?;
+        }
+        "quiz" => {
+            let kinds = match command.get_value_of("kinds").to_option() {
+                Some(kinds) => parse_quiz_kinds(&kinds)?,
+                None => QuizKind::all(),
             };
+            let amount = parse_number("amount", &command.get_value_of("amount").throw_if_none()?)?;
+            let translator =
+                Translator::new()?.with_filter(parse_list_filter(&command_options(command))?);
 
-            get_list(
-                word_type, pos_list, max, min, exact, amount, random, display, to,
-            );
+            quiz(&translator, &kinds, amount)?;
         }
-        "help" => {
-            let command = command.get_value().to_option();
-            cli.help(command);
-        }
-        "tui" => {
-            let mut input = String::new();
-            let mut language = Language::Latin;
-            loop {
-                print!("> ");
-                input.clear();
-                std::io::stdout().flush().unwrap();
-                std::io::stdin().read_line(&mut input).unwrap();
-                let input = input.trim();
-
-                match input {
-                    ".exit" | ".quit" | "q" => {
-                        break;
-                    }
-                    ".help" => {
-                        println!("Commands:");
-                        println!(".help - Displays this message");
-                        println!(".exit - Exits the program");
-                        println!(".switch - Switches between latin and english");
-                        println!("enter a word to translate it")
-                    }
-                    ".switch" => {
-                        language = match language {
-                            Language::Latin => Language::English,
-                            Language::English => Language::Latin,
-                        };
-                        println!("Switched to {:?}", language.as_str());
+        "study" => {
+            let action = command.get_value().to_option().unwrap_or_default();
+            let (action, words) = action.split_once(' ').unwrap_or((&action, ""));
+            let deck = command.get_value_of("deck").throw_if_none()?;
+            let translator =
+                Translator::new()?.with_filter(parse_list_filter(&command_options(command))?);
+
+            match action {
+                "" | "review" => {
+                    let amount =
+                        parse_number("amount", &command.get_value_of("amount").throw_if_none()?)?;
+                    study(&translator, &deck, amount)?;
+                }
+                "add" => {
+                    let random = match command.get_value_of("random").to_option() {
+                        Some(random) => parse_number("random", &random)?,
+                        None => 0,
+                    };
+                    if words.trim().is_empty() && random == 0 {
+                        return Err(VocabError::MissingArgument("words".to_string()));
                     }
-                    ".clear" => {
-                        print!("\x1B[2J\x1B[1;1H");
+                    add_to_deck(&translator, &deck, words, random)?;
+                }
+                "remove" => {
+                    if words.trim().is_empty() {
+                        return Err(VocabError::MissingArgument("words".to_string()));
                     }
-                    _ => match language {
-                        Language::Latin => {
-                            latin_to_english(input, 6, true, true, true, false);
-                        }
-                        Language::English => {
-                            english_to_latin(input, 6, true, true, true);
+                    remove_from_deck(&translator, &deck, words)?;
+                }
+                "list" => display_deck(&Deck::open(&deck)?),
+                "export" => {
+                    let format: ExportFormat =
+                        command.get_value_of("format").throw_if_none()?.parse()?;
+                    let exported = translator.export_deck(&Deck::open(&deck)?, format)?;
+                    match command.get_value_of("output").to_option() {
+                        Some(output) => {
+                            std::fs::write(&output, exported)?;
+                            println!("Exported {} to {}", deck, output);
                         }
-                    },
+                        None => print!("{}", exported),
+                    }
+                }
+                _ => {
+                    return Err(VocabError::InvalidArgument {
+                        name: "action".to_string(),
+                        value: action.to_string(),
+                    })
                 }
             }
         }
-        _ => {
-            println!("Invalid command. Please use `help` to see the available commands.");
+        "serve" => {
+            let port = parse_number("port", &command.get_value_of("port").throw_if_none()?)?;
+            let port = u16::try_from(port).map_err(|_| VocabError::InvalidArgument {
+                name: "port".to_string(),
+                value: port.to_string(),
+            })?;
+            let max = parse_number("max", &command.get_value_of("max").throw_if_none()?)?;
+            let translator = Translator::new()?
+                .with_max(max)
+                .with_sort(command.has("sort"))
+                .with_tricks(command.has("tricks"))
+                .with_filter(parse_filter(&command_options(command))?);
+
+            server::serve(translator, port)?;
         }
+        "lsp" => {
+            let max = parse_number("max", &command.get_value_of("max").throw_if_none()?)?;
+            let suggestions = parse_number(
+                "suggestions",
+                &command.get_value_of("suggestions").throw_if_none()?,
+            )?;
+            let translator = Translator::new()?
+                .with_max(max)
+                .with_tricks(true)
+                .with_suggestions(suggestions)
+                .with_filter(parse_filter(&command_options(command))?);
+
+            LanguageServer::new(translator).run()?;
+        }
+        "help" => {
+            let command = command.get_value().to_option();
+            cli.help(command)?;
+        }
+        "tui" => tui::run(Translator::new()?)?,
+        _ => return Err(VocabError::CommandNotFound(command.name.to_string())),
     }
+
+    Ok(())
 }
 
-//TODO: get dictionaries here, to not repeat getting them for each word
-fn latin_to_english(
-    latin_text: &str,
-    max: usize,
-    tricks: bool,
-    sort: bool,
+fn parse_number(name: &str, value: &str) -> Result<usize, VocabError> {
+    value
+        .parse::<usize>()
+        .map_err(|_| VocabError::InvalidArgument {
+            name: name.to_string(),
+            value: value.to_string(),
+        })
+}
+
+/**
+ * Reads the value of an option by name, so the parsing below works for the command line and for query strings
+ */
+type Options<'a> = dyn Fn(&'static str) -> Option<String> + 'a;
+
+fn command_options<'a>(command: &'a Command) -> impl Fn(&'static str) -> Option<String> + 'a {
+    |name| command.get_value_of(name).to_option()
+}
+
+/**
+ * The age, area, geo, min-freq and source options shared by the translation and list commands
+ */
+fn parse_filter(options: &Options) -> Result<WordFilter, VocabError> {
+    let mut filter = WordFilter::new();
+
+    if let Some(age) = options("age") {
+        filter = filter.with_age(parse_keys("age", &age, Age::dict_key_to_age, Age::as_str)?);
+    }
+    if let Some(area) = options("area") {
+        filter = filter.with_area(parse_keys(
+            "area",
+            &area,
+            Area::dict_key_to_area,
+            Area::as_str,
+        )?);
+    }
+    if let Some(geo) = options("geo") {
+        filter = filter.with_geo(parse_keys(
+            "geo",
+            &geo,
+            Geography::dict_key_to_geography,
+            Geography::as_str,
+        )?);
+    }
+    if let Some(value) = options("min-freq") {
+        let min_freq = parse_keys(
+            "min-freq",
+            &value,
+            Frequency::dict_key_to_frequency,
+            Frequency::as_str,
+        )?;
+        match min_freq.as_slice() {
+            [min_freq] => filter = filter.with_min_freq(*min_freq),
+            _ => {
+                return Err(VocabError::InvalidArgument {
+                    name: "min-freq".to_string(),
+                    value,
+                })
+            }
+        }
+    }
+    if let Some(source) = options("source") {
+        filter = filter.with_source(parse_keys(
+            "source",
+            &source,
+            Source::dict_key_to_source,
+            Source::as_str,
+        )?);
+    }
+
+    Ok(filter)
+}
+
+/**
+ * The filter of getList: the shared filter options with the part of speech and the word lengths
+ */
+fn parse_list_filter(options: &Options) -> Result<WordFilter, VocabError> {
+    let mut filter = parse_filter(options)?;
+
+    if let Some(pos) = options("pos") {
+        filter = filter.with_pos(parse_pos_list(&pos)?);
+    }
+    if let Some(max) = options("max") {
+        filter = filter.with_max_length(parse_number("max", &max)?);
+    }
+    if let Some(min) = options("min") {
+        filter = filter.with_min_length(parse_number("min", &min)?);
+    }
+    if let Some(exact) = options("exact") {
+        filter = filter.with_exact_length(parse_number("exact", &exact)?);
+    }
+
+    Ok(filter)
+}
+
+/**
+ * The word is everything before the first space or comma, ex: "rex, ablative plural"
+ */
+fn parse_form(value: &str) -> Result<(&str, &str), VocabError> {
+    value
+        .split_once([' ', ','])
+        .ok_or(VocabError::MissingArgument("form".to_string()))
+}
+
+fn parse_quiz_kinds(kinds: &str) -> Result<Vec<QuizKind>, VocabError> {
+    let mut quiz_kinds = Vec::new();
+    for kind in kinds
+        .split(',')
+        .map(str::trim)
+        .filter(|kind| !kind.is_empty())
+    {
+        let kind: QuizKind = kind.parse()?;
+        if !quiz_kinds.contains(&kind) {
+            quiz_kinds.push(kind);
+        }
+    }
+
+    match quiz_kinds.is_empty() {
+        true => Err(VocabError::InvalidArgument {
+            name: "kinds".to_string(),
+            value: kinds.to_string(),
+        }),
+        false => Ok(quiz_kinds),
+    }
+}
+
+fn parse_pos_list(pos: &str) -> Result<Vec<PartOfSpeech>, VocabError> {
+    let mut pos_list: Vec<PartOfSpeech> = Vec::new();
+    for pos in pos.split(",") {
+        match PartOfSpeech::dict_key_to_part_of_speech(pos) {
+            PartOfSpeech::Unknown => return Err(VocabError::InvalidPartOfSpeech(pos.to_string())),
+            part_of_speech => pos_list.push(part_of_speech),
+        }
+    }
+    Ok(pos_list)
+}
+
+enum Input {
+    Words(String),
+    Stream(Box<dyn BufRead>),
+}
+
+/**
+ * The words on the command line, or a file with --file.
+ * Stdin is read with `--file -`, or when there are no words and stdin is not a terminal.
+ */
+fn get_input(words: ArgValue, file: ArgValue) -> Result<Input, VocabError> {
+    if let ArgValue::Present(file) = file {
+        if file == "-" {
+            return Ok(Input::Stream(Box::new(std::io::stdin().lock())));
+        }
+        let file = std::fs::File::open(&file).map_err(|error| {
+            VocabError::Io(std::io::Error::new(
+                error.kind(),
+                format!("{}: {}", file, error),
+            ))
+        })?;
+        return Ok(Input::Stream(Box::new(std::io::BufReader::new(file))));
+    }
+
+    match words {
+        ArgValue::Present(words) if !words.is_empty() => Ok(Input::Words(words)),
+        _ if !std::io::stdin().is_terminal() => {
+            Ok(Input::Stream(Box::new(std::io::stdin().lock())))
+        }
+        words => words.throw_if_none().map(Input::Words),
+    }
+}
+
+/**
+ * A result with the position of its word in the input
+ */
+#[derive(Serialize)]
+struct Located<T: Serialize> {
+    line: usize,
+    column: usize,
+    #[serde(flatten)]
+    result: T,
+}
+
+/**
+ * Translates the input as it is read, every word is printed as soon as it is translated:
+ * one JSON object per line, or the pretty output after the word's position.
+ * With context the words are analyzed a clause at a time, since agreement is never checked across clauses.
+ */
+fn translate_stream(
+    translator: &Translator,
+    mut reader: Box<dyn BufRead>,
+    language: Language,
+    context: bool,
     pretty_output: bool,
     detailed_pretty_output: bool,
-) {
-    let latin_words: Vec<&str> = latin_text.split(" ").collect();
-    let mut translations: Vec<Translation> = Vec::new();
+) -> Result<(), VocabError> {
+    let mut clause: Vec<Token> = Vec::new();
+    let mut buffer: Vec<u8> = Vec::new();
+    let mut line_number = 0;
+
+    loop {
+        buffer.clear();
+        if reader.read_until(b'\n', &mut buffer)? == 0 {
+            break;
+        }
+        line_number += 1;
+        // invalid utf-8 is replaced instead of ending the whole batch at the first bad line
+        let line = String::from_utf8_lossy(&buffer);
+        let line = line.trim_end_matches(['\n', '\r']);
+
+        for mut token in tokenize(line) {
+            token.line = line_number;
+
+            if context {
+                let ends_clause = token.ends_clause;
+                clause.push(token);
+                if ends_clause {
+                    print_clause(translator, &clause, pretty_output, detailed_pretty_output)?;
+                    clause.clear();
+                }
+                continue;
+            }
+
+            let translation = match language {
+                Language::Latin => translator.latin_translation(&token.word)?,
+                Language::English => Translation::new(
+                    token.word.to_string(),
+                    TranslationType::English(translator.english_to_latin(&token.word)?),
+                ),
+            };
+
+            if pretty_output {
+                println!("{}:{}", token.line, token.column);
+                translation.display(DisplayType::Pretty(detailed_pretty_output));
+            } else {
+                print_json_line(&token, translation)?;
+            }
+        }
+    }
+
+    if !clause.is_empty() {
+        print_clause(translator, &clause, pretty_output, detailed_pretty_output)?;
+    }
 
-    for word in latin_words {
-        let mut definitions = translate_latin_to_english(&sanitize_word(word), tricks);
-        definitions.truncate(max);
-        let mut translation =
-            Translation::new(word.to_string(), TranslationType::Latin(definitions));
+    Ok(())
+}
+
+fn print_clause(
+    translator: &Translator,
+    clause: &[Token],
+    pretty_output: bool,
+    detailed_pretty_output: bool,
+) -> Result<(), VocabError> {
+    let analysis = translator.analyze_tokens(clause)?;
 
-        translation.post_process(Language::Latin, sort);
-        translations.push(translation);
+    for (token, token_analysis) in clause.iter().zip(analysis.tokens) {
+        if pretty_output {
+            println!("{}:{}", token.line, token.column);
+            token_analysis.display(DisplayType::Pretty(detailed_pretty_output));
+        } else {
+            print_json_line(token, token_analysis)?;
+        }
     }
 
+    Ok(())
+}
+
+fn print_json_line<T: Serialize>(token: &Token, result: T) -> Result<(), VocabError> {
+    let located = Located {
+        line: token.line,
+        column: token.column,
+        result,
+    };
+    // written instead of printed so a closed pipe (ex: | head) ends the stream with an error instead of a panic
+    writeln!(std::io::stdout(), "{}", serde_json::to_string(&located)?)?;
+    Ok(())
+}
+
+fn latin_to_english(
+    translator: &Translator,
+    latin_text: &str,
+    pretty_output: bool,
+    detailed_pretty_output: bool,
+) -> Result<(), VocabError> {
+    let translations = translator.translate_latin(latin_text)?;
+
     if pretty_output {
         for translation in translations {
             translation.display(DisplayType::Pretty(detailed_pretty_output));
         }
     } else {
-        println!("{}", serde_json::to_string_pretty(&translations).unwrap());
+        println!("{}", serde_json::to_string_pretty(&translations)?);
+    }
+
+    Ok(())
+}
+
+fn analyze_sentence(
+    translator: &Translator,
+    latin_text: &str,
+    pretty_output: bool,
+    detailed_pretty_output: bool,
+) -> Result<(), VocabError> {
+    let analysis = translator.analyze_sentence(latin_text)?;
+
+    if pretty_output {
+        analysis.display(DisplayType::Pretty(detailed_pretty_output));
+    } else {
+        println!("{}", serde_json::to_string_pretty(&analysis)?);
     }
+
+    Ok(())
 }
 
 fn english_to_latin(
+    translator: &Translator,
     english_text: &str,
-    max: usize,
-    sort: bool,
+    phrases: bool,
     pretty_output: bool,
     detailed_pretty_output: bool,
-) {
-    let english_words: Vec<&str> = english_text.split(" ").collect();
-    let mut translations: Vec<Translation> = Vec::new();
-
-    let latin_dictionary = get_latin_dictionary();
-    let english_dictionary = get_english_dictionary();
-
-    for word in english_words {
-        let definitions = translate_english_to_latin(
-            &english_dictionary,
-            &latin_dictionary,
-            &sanitize_word(word),
-            max,
-            sort,
-        );
-        let mut translation =
-            Translation::new(word.to_string(), TranslationType::English(definitions));
-        translation.post_process(Language::English, sort);
-        translations.push(translation);
-    }
+) -> Result<(), VocabError> {
+    let translations = match phrases {
+        true => translator.translate_english_phrases(english_text)?,
+        false => translator.translate_english(english_text)?,
+    };
 
     if pretty_output {
         for translation in translations {
             translation.display(DisplayType::Pretty(detailed_pretty_output));
         }
     } else {
-        println!("{}", serde_json::to_string_pretty(&translations).unwrap());
+        println!("{}", serde_json::to_string_pretty(&translations)?);
+    }
+
+    Ok(())
+}
+
+fn inflect(
+    translator: &Translator,
+    word: &str,
+    pretty_output: bool,
+    detailed_pretty_output: bool,
+) -> Result<(), VocabError> {
+    let paradigms = inflect_word(translator, word)?;
+
+    if pretty_output {
+        if paradigms.is_empty() {
+            println!("No entries found for {}", word);
+        }
+        for paradigm in paradigms {
+            paradigm.display(DisplayType::Pretty(detailed_pretty_output));
+        }
+    } else {
+        println!("{}", serde_json::to_string_pretty(&paradigms)?);
+    }
+
+    Ok(())
+}
+
+/**
+ * A number is looked up as a dictionary id, anything else is translated and every entry it could come from is inflected
+ */
+fn inflect_word(translator: &Translator, word: &str) -> Result<Vec<Paradigm>, VocabError> {
+    match word.parse::<i32>() {
+        Ok(id) => Ok(translator.inflect_by_id(id)?.into_iter().collect()),
+        Err(_) => {
+            let mut ids: Vec<i32> = Vec::new();
+            let mut paradigms = Vec::new();
+            for definition in translator.latin_to_english(word)? {
+                if definition.word.id != 0 && ids.contains(&definition.word.id) {
+                    continue;
+                }
+                ids.push(definition.word.id);
+                paradigms.push(translator.inflect(&definition.word)?);
+            }
+            Ok(paradigms)
+        }
+    }
+}
+
+fn lookup(
+    translator: &Translator,
+    id: i32,
+    english: bool,
+    pretty_output: bool,
+    detailed_pretty_output: bool,
+) -> Result<(), VocabError> {
+    if english {
+        let english_words = translator.lookup_english(id);
+        if !pretty_output {
+            println!("{}", serde_json::to_string_pretty(&english_words)?);
+        } else if english_words.is_empty() {
+            println!("No english entries found for {}", id);
+        } else {
+            display_english_words(&english_words);
+        }
+        return Ok(());
+    }
+
+    match translator.lookup(id)? {
+        Some(entry) if pretty_output => {
+            entry.display(DisplayType::Pretty(detailed_pretty_output));
+        }
+        Some(entry) => entry.display(DisplayType::Json),
+        None if pretty_output => println!("No entry found for {}", id),
+        None => println!("null"),
+    }
+
+    Ok(())
+}
+
+fn search(
+    translator: &Translator,
+    query: &str,
+    max: usize,
+    pretty_output: bool,
+    detailed_pretty_output: bool,
+) -> Result<(), VocabError> {
+    let matches = translator.search_senses(query, max)?;
+
+    if pretty_output {
+        if matches.is_empty() {
+            println!("No entries found for {}", query);
+        }
+        for sense_match in matches {
+            sense_match.display(DisplayType::Pretty(detailed_pretty_output));
+        }
+    } else {
+        println!("{}", serde_json::to_string_pretty(&matches)?);
+    }
+
+    Ok(())
+}
+
+/**
+ * Like inflect, every entry the word could come from gets the form
+ */
+fn form(
+    translator: &Translator,
+    word: &str,
+    description: &str,
+    pretty_output: bool,
+) -> Result<(), VocabError> {
+    let paradigms = translator.select_form(inflect_word(translator, word)?, description)?;
+
+    if pretty_output {
+        if paradigms.is_empty() {
+            println!("No entries found for {}", word);
+        }
+        for paradigm in paradigms {
+            paradigm.display_forms();
+        }
+    } else {
+        println!("{}", serde_json::to_string_pretty(&paradigms)?);
+    }
+
+    Ok(())
+}
+
+fn derive(translator: &Translator, word: &str, pretty_output: bool) -> Result<(), VocabError> {
+    let derivations = translator.derive(word)?;
+
+    if !pretty_output {
+        println!("{}", serde_json::to_string_pretty(&derivations)?);
+        return Ok(());
+    }
+
+    if derivations.is_empty() {
+        println!("No derivations found for {}", word);
+    }
+    for derivation in &derivations {
+        let definition = &derivation.definition;
+        println!(
+            "{} ({}), {}",
+            definition.word.orth,
+            definition.word.pos.as_str(),
+            definition.word.senses.join(" ")
+        );
+        for line in derivation.explanation() {
+            println!("  {}", line);
+        }
+        println!();
+    }
+
+    Ok(())
+}
+
+/**
+ * Asks the questions one at a time and grades each answer, an empty answer skips a question and .exit ends the quiz early.
+ * Ends with the score for each kind of question and the answers that were missed.
+ */
+fn quiz(translator: &Translator, kinds: &[QuizKind], amount: usize) -> Result<(), VocabError> {
+    let questions = translator.quiz(kinds, amount)?;
+    if questions.is_empty() {
+        println!("No words fit the filter");
+        return Ok(());
+    }
+
+    let mut results: Vec<(&Question, bool)> = Vec::new();
+    let mut answer = String::new();
+    for (index, question) in questions.iter().enumerate() {
+        println!(
+            "{}/{} {}",
+            index + 1,
+            questions.len(),
+            question.kind.as_str()
+        );
+        println!("{}", question.prompt);
+        print!("> ");
+        std::io::stdout().flush()?;
+
+        answer.clear();
+        if std::io::stdin().read_line(&mut answer)? == 0 || answer.trim() == ".exit" {
+            println!();
+            break;
+        }
+
+        let correct = translator.grade(question, answer.trim())?;
+        match correct {
+            true => println!("Correct: {}\n", question.answer),
+            false => println!("Wrong: {}\n", question.answer),
+        }
+        results.push((question, correct));
+    }
+
+    display_quiz_summary(&results);
+    Ok(())
+}
+
+fn display_quiz_summary(results: &[(&Question, bool)]) {
+    let score = |results: &[&(&Question, bool)]| {
+        let correct = results.iter().filter(|(_, correct)| *correct).count();
+        format!("{}/{}", correct, results.len())
+    };
+
+    let all: Vec<&(&Question, bool)> = results.iter().collect();
+    let percent = match all.len() {
+        0 => 0,
+        total => all.iter().filter(|(_, correct)| *correct).count() * 100 / total,
+    };
+    println!("Score: {} ({}%)", score(&all), percent);
+
+    for kind in QuizKind::all() {
+        let of_kind: Vec<&(&Question, bool)> = results
+            .iter()
+            .filter(|(question, _)| question.kind == kind)
+            .collect();
+        if !of_kind.is_empty() {
+            println!("  {}: {}", kind.as_str(), score(&of_kind));
+        }
+    }
+
+    let missed: Vec<&Question> = results
+        .iter()
+        .filter(|(_, correct)| !correct)
+        .map(|(question, _)| *question)
+        .collect();
+    if !missed.is_empty() {
+        println!("\nReview:");
+        for question in missed {
+            println!("  {}\n    {}", question.prompt, question.answer);
+        }
+    }
+}
+
+/**
+ * Reviews the cards that are due, the answer is graded like a quiz question and then rated from again to easy.
+ * The deck is saved after every card, .exit or an empty input stream stops early
+ */
+fn study(translator: &Translator, name: &str, amount: usize) -> Result<(), VocabError> {
+    let mut deck = Deck::open(name)?;
+    let today = today();
+    if deck.cards.is_empty() {
+        println!(
+            "The {} deck is empty, add words with: study add <WORDS>",
+            name
+        );
+        return Ok(());
+    }
+
+    let due: Vec<usize> = deck.due(today).into_iter().take(amount).collect();
+    if due.is_empty() {
+        let next_due = deck.next_due().unwrap_or(today);
+        println!(
+            "Nothing to review, the next card is due {}",
+            due_in(next_due, today)
+        );
+        return Ok(());
+    }
+
+    let mut ratings: Vec<Rating> = Vec::new();
+    let mut answer = String::new();
+    for (number, &index) in due.iter().enumerate() {
+        let question = match translator.study_question(&deck.cards[index])? {
+            Some(question) => question,
+            None => {
+                eprintln!("{} is not in the dictionary", deck.cards[index].word);
+                continue;
+            }
+        };
+        println!("{}/{} {}", number + 1, due.len(), question.prompt);
+        print!("> ");
+        std::io::stdout().flush()?;
+
+        answer.clear();
+        if std::io::stdin().read_line(&mut answer)? == 0 || answer.trim() == ".exit" {
+            println!();
+            break;
+        }
+
+        // an empty answer shows the senses, the rating can still say it was known
+        let correct = translator.grade(&question, answer.trim())?;
+        let default = match correct {
+            true => {
+                println!("Correct: {}", question.answer);
+                Rating::Good
+            }
+            false => {
+                println!("Wrong: {}", question.answer);
+                Rating::Again
+            }
+        };
+        let rating = match read_rating(default)? {
+            Some(rating) => rating,
+            None => break,
+        };
+
+        let card = &mut deck.cards[index];
+        card.review(rating, today);
+        deck.save()?;
+        println!("Next review {}\n", due_in(deck.cards[index].due, today));
+        ratings.push(rating);
+    }
+
+    let counts: Vec<String> = Rating::all()
+        .iter()
+        .map(|rating| {
+            let count = ratings.iter().filter(|rated| *rated == rating).count();
+            format!("{} {}", rating.as_str(), count)
+        })
+        .collect();
+    println!("Reviewed {} cards: {}", ratings.len(), counts.join(", "));
+
+    match (deck.due(today).len(), deck.next_due()) {
+        (0, Some(next_due)) => println!("The next card is due {}", due_in(next_due, today)),
+        (0, None) => {}
+        (left, _) => println!("{} cards are still due today", left),
+    }
+    Ok(())
+}
+
+// None when the input ends, an empty line is the default rating
+fn read_rating(default: Rating) -> Result<Option<Rating>, VocabError> {
+    let ratings: Vec<String> = Rating::all()
+        .iter()
+        .enumerate()
+        .map(|(index, rating)| format!("{} {}", index + 1, rating.as_str()))
+        .collect();
+
+    let mut line = String::new();
+    loop {
+        print!("{} [{}]: ", ratings.join(", "), default.as_str());
+        std::io::stdout().flush()?;
+
+        line.clear();
+        if std::io::stdin().read_line(&mut line)? == 0 || line.trim() == ".exit" {
+            println!();
+            return Ok(None);
+        }
+        match line.trim() {
+            "" => return Ok(Some(default)),
+            rating => match rating.parse::<Rating>() {
+                Ok(rating) => return Ok(Some(rating)),
+                Err(_) => println!("{} is not a rating", rating),
+            },
+        }
+    }
+}
+
+fn due_in(due: u64, today: u64) -> String {
+    match due.saturating_sub(today) {
+        0 => "today".to_string(),
+        1 => "tomorrow".to_string(),
+        days => format!("in {} days", days),
+    }
+}
+
+/**
+ * Adds the entries of the words (or ids) and random entries that fit the filter to the deck as new cards
+ */
+fn add_to_deck(
+    translator: &Translator,
+    name: &str,
+    words: &str,
+    random: usize,
+) -> Result<(), VocabError> {
+    let mut deck = Deck::open(name)?;
+    let today = today();
+
+    let mut entries: Vec<LatinWordInfo> = Vec::new();
+    for word in split_study_words(words) {
+        let found = study_entries(translator, word)?;
+        if found.is_empty() {
+            println!("No entries found for {}", word);
+        }
+        entries.extend(found);
     }
+    if random > 0 {
+        // words already in the deck, or given with the command, are not picked again
+        let mut exclude: Vec<i32> = deck.cards.iter().map(|card| card.id).collect();
+        exclude.extend(entries.iter().map(|entry| entry.id));
+        let picked = translator.random_entries(random, &exclude)?;
+        if picked.is_empty() {
+            println!("No words that are not in the deck fit the filter");
+        } else if picked.len() < random {
+            println!(
+                "Only {} words that are not in the deck fit the filter",
+                picked.len()
+            );
+        }
+        entries.extend(picked);
+    }
+
+    for entry in entries {
+        let word = match entry.parts.is_empty() {
+            true => entry.orth.clone(),
+            false => entry.parts.join(", "),
+        };
+        match deck.add(Card::new(entry.id, &word, today)) {
+            true => println!("Added {}", word),
+            false => println!("{} is already in the deck", word),
+        }
+    }
+
+    deck.save()?;
+    println!("{} has {} cards", name, deck.cards.len());
+    Ok(())
+}
+
+fn remove_from_deck(translator: &Translator, name: &str, words: &str) -> Result<(), VocabError> {
+    let mut deck = Deck::open(name)?;
+
+    for word in split_study_words(words) {
+        let mut removed = false;
+        for entry in study_entries(translator, word)? {
+            if let Some(card) = deck.remove(entry.id) {
+                println!("Removed {}", card.word);
+                removed = true;
+            }
+        }
+        if !removed {
+            println!("{} is not in the deck", word);
+        }
+    }
+
+    deck.save()?;
+    println!("{} has {} cards", name, deck.cards.len());
+    Ok(())
+}
+
+// words can be separated by commas or spaces, ex: "amo, rex 1234"
+fn split_study_words(words: &str) -> impl Iterator<Item = &str> {
+    words
+        .split([',', ' '])
+        .map(str::trim)
+        .filter(|word| !word.is_empty())
+}
+
+/**
+ * The entry with the id, or every entry the latin word can be a form of, ex: amat -> amo
+ */
+fn study_entries(translator: &Translator, word: &str) -> Result<Vec<LatinWordInfo>, VocabError> {
+    if let Ok(id) = word.parse::<i32>() {
+        return Ok(translator
+            .lookup(id)?
+            .map(|entry| entry.word)
+            .into_iter()
+            .collect());
+    }
+
+    let mut entries: Vec<LatinWordInfo> = Vec::new();
+    for definition in translator.latin_to_english(word)? {
+        // words found with tricks or in the unique words have no entry to review
+        if definition.word.id == 0 || entries.iter().any(|entry| entry.id == definition.word.id) {
+            continue;
+        }
+        entries.push(definition.word);
+    }
+    Ok(entries)
+}
+
+fn display_deck(deck: &Deck) {
+    let today = today();
+    let mut cards: Vec<&Card> = deck.cards.iter().collect();
+    cards.sort_by_key(|card| card.due);
+
+    println!(
+        "{}: {} cards, {} due today",
+        deck.name,
+        cards.len(),
+        deck.due(today).len()
+    );
+    for card in cards {
+        println!(
+            "  {:<40} id {}, due {}, {} reviews, {} lapses, ease {:.2}",
+            card.word,
+            card.id,
+            due_in(card.due, today),
+            card.reviews,
+            card.lapses,
+            card.ease
+        );
+    }
+}
+
+fn build_data(input: &str, output: &str) -> Result<(), VocabError> {
+    let report = vocab_vault::build_data::build_data(Path::new(input), Path::new(output))?;
+
+    for path in &report.written {
+        println!("Wrote {}", path.display());
+    }
+
+    for (source, outputs) in &report.missing {
+        eprintln!(
+            "Skipped {}, {} was not found",
+            outputs.join(", "),
+            source.display()
+        );
+    }
+
+    Ok(())
+}
+
+fn export_list(list: &OutputList, to: String) -> Result<(), VocabError> {
+    let mut file_path = to;
+
+    if !file_path.ends_with(".json") {
+        file_path.push_str(".json");
+    }
+
+    if std::path::Path::new(&file_path).exists() {
+        println!("File already exists, do you want to overwrite it? (y/n)");
+        let mut input = String::new();
+        std::io::stdin().read_line(&mut input)?;
+        if input.trim() != "y" {
+            return Ok(());
+        }
+    }
+
+    let path = std::path::Path::new(&file_path);
+
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+
+    let file = std::fs::File::create(&file_path)?;
+    serde_json::to_writer_pretty(file, &list)?;
+    println!("File created successfully at {}", file_path);
+    Ok(())
 }
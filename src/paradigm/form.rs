@@ -0,0 +1,178 @@
+use crate::dictionary_structures::dictionary_keys::{
+    Comparison, Declension, Gender, Mood, Number, PartOfSpeech, Tense, Voice,
+};
+use crate::dictionary_structures::dictionary_values::{Form, LatinWordInfo, LongForm};
+use crate::error::VocabError;
+use crate::paradigm::{inflect, Paradigm};
+use crate::utils::data::Dictionary;
+
+/**
+ * Reads a grammatical description into the form to generate, ex: "3rd person plural perfect active indicative",
+ * "ablative plural", "nom sg fem comparative". Words can be in any order and abbreviated the way
+ * Whitaker's Words abbreviates them, "participle" and "supine" ask for those instead of the finite verb forms.
+ */
+pub fn parse_form_description(description: &str) -> Result<LongForm, VocabError> {
+    let mut form = LongForm::new();
+    let words: Vec<String> = description
+        .split(|c: char| c.is_whitespace() || c == ',' || c == ';')
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+        .collect();
+
+    let mut index = 0;
+    while index < words.len() {
+        let word = words[index].as_str();
+        let next = words.get(index + 1).map(String::as_str);
+        index += 1;
+
+        form = match word {
+            "nominative" | "nom" => form.set_declension(Declension::Nominative),
+            "genitive" | "gen" => form.set_declension(Declension::Genitive),
+            "dative" | "dat" => form.set_declension(Declension::Dative),
+            "accusative" | "acc" => form.set_declension(Declension::Accusative),
+            "ablative" | "abl" => form.set_declension(Declension::Ablative),
+            "vocative" | "voc" => form.set_declension(Declension::Vocative),
+            "locative" | "loc" => form.set_declension(Declension::Locative),
+            "singular" | "sing" | "sg" => form.set_number(Number::Singular),
+            "plural" | "pl" => form.set_number(Number::Plural),
+            "masculine" | "masc" => form.set_gender(Gender::Masculine),
+            "feminine" | "fem" => form.set_gender(Gender::Feminine),
+            "neuter" | "neut" => form.set_gender(Gender::Neuter),
+            "future" | "fut" if next == Some("perfect") => {
+                index += 1;
+                form.set_tense(Tense::FuturePerfect)
+            }
+            "present" | "pres" => form.set_tense(Tense::Present),
+            "imperfect" | "impf" => form.set_tense(Tense::Imperfect),
+            "future" | "fut" => form.set_tense(Tense::Future),
+            "perfect" | "perf" => form.set_tense(Tense::Perfect),
+            "pluperfect" | "plup" => form.set_tense(Tense::Pluperfect),
+            "futp" => form.set_tense(Tense::FuturePerfect),
+            "active" | "act" => form.set_voice(Voice::Active),
+            "passive" | "pass" => form.set_voice(Voice::Passive),
+            "indicative" | "ind" => form.set_mood(Mood::Indicative),
+            "subjunctive" | "subj" | "sub" => form.set_mood(Mood::Subjunctive),
+            "imperative" | "imp" => form.set_mood(Mood::Imperative),
+            "infinitive" | "inf" => form.set_mood(Mood::Infinitive),
+            "1st" | "first" | "1" => form.set_person("1st person"),
+            "2nd" | "second" | "2" => form.set_person("2nd person"),
+            "3rd" | "third" | "3" => form.set_person("3rd person"),
+            "positive" | "pos" => form.set_comparison(Comparison::Positive),
+            "comparative" | "comp" => form.set_comparison(Comparison::Comparative),
+            "superlative" | "super" | "sup" => form.set_comparison(Comparison::Superlative),
+            "participle" | "ppl" => form.set_part_of_speech(PartOfSpeech::Participle),
+            "supine" => form.set_part_of_speech(PartOfSpeech::Supine),
+            "person" => form,
+            _ => {
+                return Err(VocabError::InvalidArgument {
+                    name: "form".to_string(),
+                    value: word.to_string(),
+                })
+            }
+        };
+    }
+
+    Ok(form)
+}
+
+/**
+ * Generates the forms of a dictionary entry that fit the target, the most common ending first
+ * and the variant endings after it, ex: amaverunt, amavere.
+ * The word must be an entry from the dictionary, like for `inflect`.
+ */
+pub fn generate_form(
+    dictionary: &Dictionary,
+    word: &LatinWordInfo,
+    target: &LongForm,
+) -> Result<Paradigm, VocabError> {
+    let mut paradigm = inflect(dictionary, word)?;
+    keep_form(&mut paradigm, target);
+    Ok(paradigm)
+}
+
+/**
+ * Keeps the forms of a paradigm that fit the target, the most common ending first.
+ * Participles and supines are only kept when the target asks for them.
+ */
+pub fn keep_form(paradigm: &mut Paradigm, target: &LongForm) {
+    paradigm.forms.retain(|inflected_form| {
+        let form_matches = match &inflected_form.form {
+            Form::LongForm(form) => fits_target(form, target),
+            Form::StrForm(_) => false,
+        };
+        let pos_matches = match target.part_of_speech {
+            Some(pos) => inflected_form.pos == pos,
+            None => !matches!(
+                inflected_form.pos,
+                PartOfSpeech::Participle | PartOfSpeech::Supine
+            ),
+        };
+        form_matches && pos_matches
+    });
+    // stable, so the variants of one form stay in paradigm order
+    paradigm
+        .forms
+        .sort_by_key(|inflected_form| inflected_form.rank());
+}
+
+impl Paradigm {
+    /**
+     * One line per generated form, other endings for the same form are marked as variants with how rare they are
+     */
+    pub fn display_forms(&self) {
+        println!("{}", self.word.parts.join(", "));
+        if self.forms.is_empty() {
+            println!("No such form");
+        }
+
+        let mut descriptions: Vec<String> = Vec::new();
+        for inflected_form in &self.forms {
+            let description = match &inflected_form.form {
+                Form::LongForm(form) => form.as_clean_str(),
+                Form::StrForm(form) => form.to_string(),
+            };
+            let mut line = format!("{} | {}", inflected_form.orth, description);
+
+            if descriptions.contains(&description) {
+                let mut rarity = vec!["variant"];
+                if inflected_form.rank().1 {
+                    rarity.push(inflected_form.age.as_str());
+                }
+                if inflected_form.freq.as_number() > 1 {
+                    rarity.push(inflected_form.freq.as_str());
+                }
+                line += &format!(" ({})", rarity.join(", "));
+            } else {
+                descriptions.push(description);
+            }
+            println!("{}", line);
+        }
+        println!();
+    }
+}
+
+// every part of the target has to be in the form, an unknown or common gender fits any gender
+pub(crate) fn fits_target(form: &LongForm, target: &LongForm) -> bool {
+    fn fits<T>(value: &Option<T>, target: &Option<T>, as_str: fn(&T) -> &'static str) -> bool {
+        match (value, target) {
+            (_, None) => true,
+            (Some(value), Some(target)) => as_str(value) == as_str(target),
+            (None, Some(_)) => false,
+        }
+    }
+
+    let gender_fits = match (&form.gender, &target.gender) {
+        (Some(Gender::Unknown), _) => true,
+        (Some(Gender::Common), Some(Gender::Masculine | Gender::Feminine)) => true,
+        (gender, target_gender) => fits(gender, target_gender, Gender::as_str),
+    };
+
+    gender_fits
+        && fits(&form.declension, &target.declension, Declension::as_str)
+        && fits(&form.number, &target.number, Number::as_str)
+        && fits(&form.tense, &target.tense, Tense::as_str)
+        && fits(&form.voice, &target.voice, Voice::as_str)
+        && fits(&form.mood, &target.mood, Mood::as_str)
+        && fits(&form.comparison, &target.comparison, Comparison::as_str)
+        && (target.person.is_none() || form.person == target.person)
+}
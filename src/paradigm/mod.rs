@@ -0,0 +1,599 @@
+use crate::dictionary_structures::dictionary_keys::{Age, Frequency, Mood, Number, PartOfSpeech};
+use crate::dictionary_structures::dictionary_values::{Form, Inflection, LatinWordInfo, NValue};
+use crate::error::VocabError;
+use crate::translators::{DisplayType, Structure};
+use crate::utils::data::Dictionary;
+use serde::{Serialize, Serializer};
+
+pub mod form;
+
+const CASE_ORDER: [&str; 7] = [
+    "nominative",
+    "genitive",
+    "dative",
+    "accusative",
+    "ablative",
+    "vocative",
+    "locative",
+];
+
+// perfect system passives are made with the perfect passive participle and a form of esse
+const PERFECT_PASSIVES: [(&str, &str, [&str; 6]); 5] = [
+    (
+        "PERF",
+        "IND",
+        ["sum", "es", "est", "sumus", "estis", "sunt"],
+    ),
+    (
+        "PLUP",
+        "IND",
+        ["eram", "eras", "erat", "eramus", "eratis", "erant"],
+    ),
+    (
+        "FUTP",
+        "IND",
+        ["ero", "eris", "erit", "erimus", "eritis", "erunt"],
+    ),
+    (
+        "PERF",
+        "SUB",
+        ["sim", "sis", "sit", "simus", "sitis", "sint"],
+    ),
+    (
+        "PLUP",
+        "SUB",
+        ["essem", "esses", "esset", "essemus", "essetis", "essent"],
+    ),
+];
+
+#[derive(Debug, Clone)]
+pub struct InflectedForm {
+    pub orth: String,
+    pub pos: PartOfSpeech,
+    pub form: Form,
+    // of the ending, forms made with esse are as common as esse
+    pub age: Age,
+    pub freq: Frequency,
+}
+
+impl InflectedForm {
+    /**
+     * Lower is more common, the same order as `Inflection::rank`
+     */
+    pub fn rank(&self) -> (u8, bool) {
+        (
+            self.freq.as_number(),
+            !matches!(self.age, Age::Classical | Age::UsedThroughoutAges),
+        )
+    }
+}
+
+impl Serialize for InflectedForm {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut map = serde_json::Map::new();
+        map.insert(
+            "orth".to_string(),
+            serde_json::Value::String(self.orth.to_string()),
+        );
+        map.insert(
+            "pos".to_string(),
+            serde_json::Value::String(self.pos.as_str().to_string()),
+        );
+        map.insert(
+            "form".to_string(),
+            match &self.form {
+                Form::StrForm(form) => serde_json::Value::String(form.to_string()),
+                Form::LongForm(long_form) => {
+                    serde_json::to_value(long_form).map_err(serde::ser::Error::custom)?
+                }
+            },
+        );
+        map.insert(
+            "age".to_string(),
+            serde_json::Value::String(self.age.as_str().to_string()),
+        );
+        map.insert(
+            "freq".to_string(),
+            serde_json::Value::String(self.freq.as_str().to_string()),
+        );
+        serde_json::Value::Object(map).serialize(serializer)
+    }
+}
+
+/**
+ * Every inflected form of a dictionary entry
+ */
+#[derive(Debug, Clone, Serialize)]
+pub struct Paradigm {
+    pub word: LatinWordInfo,
+    pub forms: Vec<InflectedForm>,
+}
+
+/**
+ * Generates the full paradigm of a dictionary entry.
+ * The word must be an entry from the dictionary, with its stems in `parts`, not its principle parts.
+ *
+ * Every inflection that fits the part of speech and n values of the word is added to the stem its stem key points to.
+ * Words that can't be inflected (adverbs, prepositions, conjunctions, ...) only have their dictionary form.
+ */
+pub fn inflect(dictionary: &Dictionary, word: &LatinWordInfo) -> Result<Paradigm, VocabError> {
+    let word_form = match &word.form {
+        Form::StrForm(form) => form.split_whitespace().collect::<Vec<&str>>(),
+        Form::LongForm(_) => Vec::new(),
+    };
+    let (word_n_1, word_n_2) = n_values(&word.n);
+
+    let mut forms: Vec<(String, PartOfSpeech, String, Age, Frequency)> = Vec::new();
+    for inflection in &dictionary.inflections {
+        if !inflection_fits_word(inflection, word, &word_form, word_n_1, word_n_2) {
+            continue;
+        }
+
+        let stem_index = (inflection.stem_key as usize).checked_sub(1);
+        let stem = match stem_index.and_then(|index| word.parts.get(index)) {
+            Some(stem) if stem != "zzz" => stem,
+            _ => continue,
+        };
+
+        let inflection_form = match &inflection.form {
+            Form::StrForm(form) => form.to_string(),
+            Form::LongForm(_) => continue,
+        };
+
+        for form in expand_genders(inflection.pos, &inflection_form) {
+            let orth = format!("{}{}", stem, inflection.ending);
+            if !forms
+                .iter()
+                .any(|(o, p, f, _, _)| *o == orth && *p == inflection.pos && *f == form)
+            {
+                forms.push((orth, inflection.pos, form, inflection.age, inflection.freq));
+            }
+        }
+    }
+
+    if word.pos == PartOfSpeech::Verb {
+        // listed with the other finite forms, before the participles
+        let position = forms
+            .iter()
+            .rposition(|(_, pos, _, _, _)| *pos == PartOfSpeech::Verb)
+            .map(|index| index + 1)
+            .unwrap_or(forms.len());
+        let compound_forms = compound_verb_forms(word, &word_form);
+        forms.splice(
+            position..position,
+            compound_forms.into_iter().map(|(orth, pos, form)| {
+                (
+                    orth,
+                    pos,
+                    form,
+                    Age::UsedThroughoutAges,
+                    Frequency::VeryFrequent,
+                )
+            }),
+        );
+    }
+
+    let mut paradigm_word = word.clone();
+    paradigm_word
+        .form
+        .str_form_to_long_form(word.pos, Structure::LatinWordInfo);
+    paradigm_word.generate_principle_parts()?;
+
+    if forms.is_empty() {
+        forms.push((
+            paradigm_word.orth.to_string(),
+            word.pos,
+            String::new(),
+            word.info.age,
+            word.info.freq,
+        ));
+    }
+
+    let forms = forms
+        .into_iter()
+        .map(|(orth, pos, form, age, freq)| {
+            let mut form = Form::StrForm(form);
+            form.str_form_to_long_form(pos, Structure::Inflection);
+            InflectedForm {
+                orth,
+                pos,
+                form,
+                age,
+                freq,
+            }
+        })
+        .collect();
+
+    Ok(Paradigm {
+        word: paradigm_word,
+        forms,
+    })
+}
+
+fn n_values(n: &Option<Vec<NValue>>) -> (i8, i8) {
+    match n {
+        Some(n) => (
+            n.first().map(|n| n.get_n_value_1()).unwrap_or(0),
+            n.get(1).map(|n| n.get_n_value_2()).unwrap_or(0),
+        ),
+        None => (0, 0),
+    }
+}
+
+fn inflection_fits_word(
+    inflection: &Inflection,
+    word: &LatinWordInfo,
+    word_form: &[&str],
+    word_n_1: i8,
+    word_n_2: i8,
+) -> bool {
+    let takes_part_of_speech = match word.pos {
+        PartOfSpeech::Verb => matches!(
+            inflection.pos,
+            PartOfSpeech::Verb | PartOfSpeech::Participle | PartOfSpeech::Supine
+        ),
+        PartOfSpeech::Noun
+        | PartOfSpeech::Pronoun
+        | PartOfSpeech::Adjective
+        | PartOfSpeech::Numeral => inflection.pos == word.pos,
+        _ => false,
+    };
+    if !takes_part_of_speech {
+        return false;
+    }
+
+    // an n value of 0 applies to every declension or conjugation
+    let (inflection_n_1, inflection_n_2) = n_values(&inflection.n);
+    if inflection_n_1 != 0
+        && (inflection_n_1 != word_n_1 || (inflection_n_2 != 0 && inflection_n_2 != word_n_2))
+    {
+        return false;
+    }
+
+    let inflection_form = match &inflection.form {
+        Form::StrForm(form) => form.split_whitespace().collect::<Vec<&str>>(),
+        Form::LongForm(_) => return false,
+    };
+    let word_type = word_form.get(2).copied().unwrap_or("X");
+
+    match inflection.pos {
+        // NOM S M
+        PartOfSpeech::Noun => {
+            let gender = inflection_form.get(2).copied().unwrap_or("X");
+            gender == "X"
+                || gender == word_type
+                || (gender == "C" && matches!(word_type, "M" | "F"))
+                || (word_type == "C" && matches!(gender, "M" | "F"))
+        }
+        // NOM S M POS
+        PartOfSpeech::Adjective => {
+            let comparison = inflection_form.get(3).copied().unwrap_or("X");
+            !matches!(word_type, "COMP" | "SUPER") || comparison == word_type
+        }
+        // PRES ACTIVE IND 3 S
+        PartOfSpeech::Verb => {
+            let tense = inflection_form.first().copied().unwrap_or("X");
+            let voice = inflection_form.get(1).copied().unwrap_or("X");
+            let person = inflection_form.get(3).copied().unwrap_or("0");
+            let number = inflection_form.get(4).copied().unwrap_or("X");
+            let perfect_system = matches!(tense, "PERF" | "PLUP" | "FUTP");
+
+            match word_type {
+                "DEP" => voice != "ACTIVE",
+                "SEMIDEP" => {
+                    (perfect_system && voice != "ACTIVE") || (!perfect_system && voice != "PASSIVE")
+                }
+                "IMPERS" => person == "0" || (person == "3" && number == "S"),
+                "PERFDEF" => perfect_system,
+                _ => true,
+            }
+        }
+        _ => true,
+    }
+}
+
+/**
+ * Inflections with an X (all) or C (masculine and feminine) gender are listed once per gender,
+ * so that every gender gets a full table.
+ * Nouns only have one gender, so they are left as is.
+ */
+fn expand_genders(pos: PartOfSpeech, form: &str) -> Vec<String> {
+    if pos == PartOfSpeech::Noun || pos == PartOfSpeech::Supine {
+        return vec![form.to_string()];
+    }
+
+    let mut form_array = form.split_whitespace().collect::<Vec<&str>>();
+    let genders: &[&str] = match form_array.get(2) {
+        Some(&"X") => &["M", "F", "N"],
+        Some(&"C") => &["M", "F"],
+        _ => return vec![form.to_string()],
+    };
+
+    genders
+        .iter()
+        .map(|gender| {
+            form_array[2] = gender;
+            form_array.join(" ")
+        })
+        .collect()
+}
+
+/**
+ * The forms that are made with a participle and esse: the perfect system passives and the future active and perfect passive infinitives
+ */
+fn compound_verb_forms(
+    word: &LatinWordInfo,
+    word_form: &[&str],
+) -> Vec<(String, PartOfSpeech, String)> {
+    let mut forms = Vec::new();
+    let word_type = word_form.get(2).copied().unwrap_or("X");
+    if word_type == "PERFDEF" {
+        return forms;
+    }
+
+    let participle_stem = match word.parts.get(3) {
+        Some(stem) if stem != "zzz" => stem,
+        _ => return forms,
+    };
+
+    for (tense, mood, esse_forms) in PERFECT_PASSIVES {
+        for (index, esse_form) in esse_forms.iter().enumerate() {
+            // impersonal verbs only have the third person singular, ex: itum est
+            if word_type == "IMPERS" && index != 2 {
+                continue;
+            }
+
+            let (ending, number) = match (word_type, index < 3) {
+                ("IMPERS", _) => ("um", "S"),
+                (_, true) => ("us", "S"),
+                (_, false) => ("i", "P"),
+            };
+            forms.push((
+                format!("{}{} {}", participle_stem, ending, esse_form),
+                PartOfSpeech::Verb,
+                format!("{} PASSIVE {} {} {}", tense, mood, index % 3 + 1, number),
+            ));
+        }
+    }
+    let ending = if word_type == "IMPERS" { "um" } else { "us" };
+    forms.push((
+        format!("{}{} esse", participle_stem, ending),
+        PartOfSpeech::Verb,
+        "PERF PASSIVE INF 0 X".to_string(),
+    ));
+    forms.push((
+        format!("{}ur{} esse", participle_stem, ending),
+        PartOfSpeech::Verb,
+        "FUT ACTIVE INF 0 X".to_string(),
+    ));
+
+    forms
+}
+
+// the finite moods, then the infinitives, participles and supines, each by tense, voice and mood
+type TableOrder = (u8, usize, usize, usize);
+
+fn table_order(inflected_form: &InflectedForm) -> TableOrder {
+    let form = match &inflected_form.form {
+        Form::LongForm(form) => form,
+        Form::StrForm(_) => return (0, usize::MAX, usize::MAX, usize::MAX),
+    };
+
+    let group = match (inflected_form.pos, form.mood) {
+        (PartOfSpeech::Participle, _) => 2,
+        (PartOfSpeech::Supine, _) => 3,
+        (_, Some(Mood::Infinitive)) => 1,
+        _ => 0,
+    };
+    (
+        group,
+        form.tense.map_or(usize::MAX, |tense| tense as usize),
+        form.voice.map_or(usize::MAX, |voice| voice as usize),
+        form.mood.map_or(usize::MAX, |mood| mood as usize),
+    )
+}
+
+impl Paradigm {
+    /**
+     * Groups the forms into tables, each table has a row per case or person and a column per number
+     */
+    pub fn tables(&self) -> Vec<ParadigmTable> {
+        let mut tables: Vec<ParadigmTable> = Vec::new();
+        let mut table_orders: Vec<TableOrder> = Vec::new();
+
+        for inflected_form in &self.forms {
+            let (title, row, number) = match &inflected_form.form {
+                Form::LongForm(form) => {
+                    let known = |value: &str| value != "unknown" && !value.is_empty();
+                    let mut title: Vec<&str> = Vec::new();
+                    let row;
+
+                    if let Some(declension) = form.declension.filter(|d| known(d.as_str())) {
+                        if let Some(comparison) = form.comparison {
+                            title.push(comparison.as_str());
+                        }
+                        if let Some(numeral) = form.numeral {
+                            title.push(numeral.as_str());
+                        }
+                        if inflected_form.pos == PartOfSpeech::Participle {
+                            title.push(form.tense.map(|t| t.as_str()).unwrap_or(""));
+                            title.push(form.voice.map(|v| v.as_str()).unwrap_or(""));
+                            title.push("participle");
+                        }
+                        if inflected_form.pos == PartOfSpeech::Supine {
+                            title.push("supine");
+                        } else if inflected_form.pos != PartOfSpeech::Noun {
+                            title.push(form.gender.map(|g| g.as_str()).unwrap_or(""));
+                        }
+                        row = declension.as_str().to_string();
+                    } else if let Some(mood) = form.mood.filter(|m| known(m.as_str())) {
+                        let tense = form.tense.map(|t| t.as_str()).unwrap_or("");
+                        let voice = form.voice.map(|v| v.as_str()).unwrap_or("");
+                        if mood.as_str() == "infinitive" {
+                            title.push(mood.as_str());
+                            row = format!("{} {}", tense, voice);
+                        } else {
+                            title.extend([tense, voice, mood.as_str()]);
+                            row = form.person.clone().unwrap_or_default();
+                        }
+                    } else {
+                        row = String::new();
+                    }
+
+                    let title = title
+                        .into_iter()
+                        .filter(|value| known(value))
+                        .collect::<Vec<&str>>()
+                        .join(" ");
+                    (title, row, form.number.unwrap_or(Number::Unknown))
+                }
+                Form::StrForm(form) => (String::new(), form.to_string(), Number::Unknown),
+            };
+
+            let table = match tables.iter_mut().position(|table| table.title == title) {
+                Some(index) => &mut tables[index],
+                None => {
+                    table_orders.push(table_order(inflected_form));
+                    tables.push(ParadigmTable {
+                        title,
+                        rows: Vec::new(),
+                    });
+                    tables.last_mut().unwrap()
+                }
+            };
+            table.add(row, number, &inflected_form.orth);
+        }
+
+        // the inflections are in INFLECTS.LAT order, which puts the perfect system of some verbs first
+        let mut ordered: Vec<(TableOrder, ParadigmTable)> =
+            table_orders.into_iter().zip(tables).collect();
+        ordered.sort_by_key(|(order, _)| *order);
+        let mut tables: Vec<ParadigmTable> = ordered.into_iter().map(|(_, table)| table).collect();
+
+        // the inflections are not in case order, persons and other rows are sorted by name
+        for table in &mut tables {
+            table.rows.sort_by_key(|row| {
+                let case = CASE_ORDER.iter().position(|case| *case == row.label);
+                (case.unwrap_or(CASE_ORDER.len()), row.label.to_string())
+            });
+        }
+
+        tables
+    }
+
+    pub fn display(&self, display_type: DisplayType) {
+        match display_type {
+            DisplayType::Pretty(detailed) => {
+                println!("{}", self.word.parts.join(", "));
+                println!("{}", self.word.pos.as_str());
+                match &self.word.form {
+                    Form::StrForm(form) => println!("{}", form),
+                    Form::LongForm(form) => println!("{}", form.as_clean_str()),
+                }
+                if detailed {
+                    println!("{}", self.word.info.as_str());
+                    println!("{}", self.word.senses.join("; "));
+                }
+
+                for table in self.tables() {
+                    println!();
+                    table.display();
+                }
+                println!();
+            }
+            DisplayType::Json => {
+                println!(
+                    "{}",
+                    serde_json::to_string_pretty(&self).unwrap_or_default()
+                );
+            }
+        }
+    }
+}
+
+/**
+ * One table of a paradigm, ex: present active indicative, or positive masculine
+ * Each row holds the singular and plural forms, forms without a number are in `other`
+ */
+pub struct ParadigmTable {
+    pub title: String,
+    pub rows: Vec<ParadigmRow>,
+}
+
+pub struct ParadigmRow {
+    pub label: String,
+    pub singular: Vec<String>,
+    pub plural: Vec<String>,
+    pub other: Vec<String>,
+}
+
+impl ParadigmTable {
+    fn add(&mut self, label: String, number: Number, orth: &str) {
+        let row = match self.rows.iter_mut().position(|row| row.label == label) {
+            Some(index) => &mut self.rows[index],
+            None => {
+                self.rows.push(ParadigmRow {
+                    label,
+                    singular: Vec::new(),
+                    plural: Vec::new(),
+                    other: Vec::new(),
+                });
+                self.rows.last_mut().unwrap()
+            }
+        };
+
+        let cell = match number {
+            Number::Singular => &mut row.singular,
+            Number::Plural => &mut row.plural,
+            Number::Unknown => &mut row.other,
+        };
+        if !cell.iter().any(|existing| existing == orth) {
+            cell.push(orth.to_string());
+        }
+    }
+
+    pub fn display(&self) {
+        let has_numbers = self
+            .rows
+            .iter()
+            .any(|row| !row.singular.is_empty() || !row.plural.is_empty());
+        let label_width = self
+            .rows
+            .iter()
+            .map(|row| row.label.len())
+            .max()
+            .unwrap_or(0);
+        let singular_width = self
+            .rows
+            .iter()
+            .map(|row| row.singular.join(" / ").len())
+            .max()
+            .unwrap_or(0)
+            .max("singular".len());
+
+        if !self.title.is_empty() {
+            println!("{}", self.title);
+        }
+        if has_numbers {
+            println!(
+                "  {:<label_width$}  {:<singular_width$}  plural",
+                "", "singular"
+            );
+        }
+        for row in &self.rows {
+            if has_numbers {
+                println!(
+                    "  {:<label_width$}  {:<singular_width$}  {}",
+                    row.label,
+                    row.singular.join(" / "),
+                    row.plural.join(" / ")
+                );
+            } else {
+                println!("  {:<label_width$}  {}", row.label, row.other.join(" / "));
+            }
+        }
+    }
+}
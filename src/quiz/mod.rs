@@ -0,0 +1,329 @@
+use crate::dictionary_structures::dictionary_keys::{
+    Declension, Mood, Number, PartOfSpeech, Tense, Voice,
+};
+use crate::dictionary_structures::dictionary_values::{Form, LatinWordInfo, LongForm};
+use crate::error::VocabError;
+use crate::paradigm::form::{fits_target, parse_form_description};
+use crate::paradigm::inflect;
+use crate::search::index::tokenize;
+use crate::translators::english_to_latin::lemmatize;
+use crate::translators::latin_to_english::orthography::Orthography;
+use crate::translators::latin_to_english::translate_latin_to_english;
+use crate::translators::Structure;
+use crate::use_data::{random_latin_entries, WordFilter};
+use crate::utils::data::Dictionary;
+use crate::utils::sanitize_word;
+use rand::seq::SliceRandom;
+use std::str::FromStr;
+
+// left out when an answer is compared to a sense, ex: "to love" is "love"
+const STOP_WORDS: [&str; 4] = ["to", "a", "an", "the"];
+// the parts of speech drilled on their principle parts and on their inflected forms,
+// the principle parts of adjectives are written with endings, ex: bona -um
+const WITH_PRINCIPLE_PARTS: [PartOfSpeech; 2] = [PartOfSpeech::Noun, PartOfSpeech::Verb];
+const INFLECTED: [PartOfSpeech; 3] = [
+    PartOfSpeech::Noun,
+    PartOfSpeech::Verb,
+    PartOfSpeech::Adjective,
+];
+// the rarest ending asked about in parsing drills
+const MAX_PARSING_FREQUENCY: u8 = 3;
+
+/**
+ * What a quiz question asks for
+ */
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum QuizKind {
+    /// The senses of a Latin word
+    LatinToEnglish,
+    /// The Latin word with the senses
+    EnglishToLatin,
+    /// Every principle part of a noun or verb
+    PrincipleParts,
+    /// The case and number (or person, tense, voice and mood) of an inflected form
+    Parsing,
+}
+
+impl QuizKind {
+    pub fn all() -> Vec<QuizKind> {
+        vec![
+            QuizKind::LatinToEnglish,
+            QuizKind::EnglishToLatin,
+            QuizKind::PrincipleParts,
+            QuizKind::Parsing,
+        ]
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            QuizKind::LatinToEnglish => "latin to english",
+            QuizKind::EnglishToLatin => "english to latin",
+            QuizKind::PrincipleParts => "principle parts",
+            QuizKind::Parsing => "parsing",
+        }
+    }
+
+    // the drills of inflected words only ask about the parts of speech they work for
+    fn filter(&self, filter: &WordFilter) -> WordFilter {
+        let drilled: &[PartOfSpeech] = match self {
+            QuizKind::PrincipleParts => &WITH_PRINCIPLE_PARTS,
+            QuizKind::Parsing => &INFLECTED,
+            QuizKind::LatinToEnglish | QuizKind::EnglishToLatin => return filter.clone(),
+        };
+        let pos = drilled
+            .iter()
+            .copied()
+            .filter(|pos| filter.allows_pos(pos))
+            .collect();
+        filter.clone().with_pos(pos)
+    }
+}
+
+impl FromStr for QuizKind {
+    type Err = VocabError;
+
+    fn from_str(s: &str) -> Result<QuizKind, VocabError> {
+        match s {
+            "latin" | "latin-english" => Ok(QuizKind::LatinToEnglish),
+            "english" | "english-latin" => Ok(QuizKind::EnglishToLatin),
+            "parts" | "principle-parts" | "principal-parts" => Ok(QuizKind::PrincipleParts),
+            "parsing" | "parse" => Ok(QuizKind::Parsing),
+            _ => Err(VocabError::InvalidArgument {
+                name: "kinds".to_string(),
+                value: s.to_string(),
+            }),
+        }
+    }
+}
+
+/**
+ * A question about a Latin entry, with the answer shown after it is graded
+ */
+#[derive(Debug, Clone)]
+pub struct Question {
+    pub kind: QuizKind,
+    pub prompt: String,
+    /// The entry asked about, with its principle parts and long form
+    pub word: LatinWordInfo,
+    pub answer: String,
+    // every form the word asked about in a parsing drill can be
+    forms: Vec<LongForm>,
+}
+
+/**
+ * Picks random entries the filter allows with getList and makes questions about them, in a random order.
+ * The questions are split evenly between the kinds, fewer are returned when there are not enough entries.
+ */
+pub fn make_questions(
+    dictionary: &Dictionary,
+    kinds: &[QuizKind],
+    filter: &WordFilter,
+    amount: usize,
+) -> Result<Vec<Question>, VocabError> {
+    let mut questions = Vec::new();
+
+    for (index, kind) in kinds.iter().enumerate() {
+        // the first kinds get the rest
+        let count = amount / kinds.len() + usize::from(index < amount % kinds.len());
+        if count == 0 {
+            continue;
+        }
+
+        for word in random_latin_entries(dictionary, &kind.filter(filter), count, &[])? {
+            if let Some(question) = Question::new(dictionary, *kind, &word)? {
+                questions.push(question);
+            }
+        }
+    }
+
+    questions.shuffle(&mut rand::thread_rng());
+    Ok(questions)
+}
+
+impl Question {
+    /**
+     * None if the word can't be asked about this way, ex: the principle parts of a word with only one
+     */
+    pub(crate) fn new(
+        dictionary: &Dictionary,
+        kind: QuizKind,
+        word: &LatinWordInfo,
+    ) -> Result<Option<Question>, VocabError> {
+        // the entry, the listed word has its principle parts in place of its stems
+        let entry = match dictionary.get_latin_word_by_id(word.id) {
+            Some(entry) => entry,
+            None => return Ok(None),
+        };
+        let mut long_word = entry.clone();
+        long_word
+            .form
+            .str_form_to_long_form(long_word.pos, Structure::LatinWordInfo);
+        long_word.generate_principle_parts()?;
+
+        let parts = long_word.parts.join(", ");
+        let senses = long_word.senses.join("; ");
+        let pos = long_word.pos.as_str();
+        let mut forms = Vec::new();
+
+        let (prompt, answer) = match kind {
+            QuizKind::LatinToEnglish => (format!("{} ({})", parts, pos), senses),
+            QuizKind::EnglishToLatin => (format!("{} ({})", senses, pos), parts),
+            QuizKind::PrincipleParts => {
+                if principle_parts(&long_word.parts).len() < 2 {
+                    return Ok(None);
+                }
+                let first = long_word.parts.first().cloned().unwrap_or_default();
+                (format!("{} ({}), {}", first, pos, senses), parts)
+            }
+            QuizKind::Parsing => {
+                let paradigm = inflect(dictionary, entry)?;
+                let common_forms: Vec<_> = paradigm
+                    .forms
+                    .iter()
+                    .filter(|form| {
+                        let (freq, rare_age) = form.rank();
+                        form.pos == entry.pos
+                            && freq <= MAX_PARSING_FREQUENCY
+                            && !rare_age
+                            && matches!(form.form, Form::LongForm(_))
+                    })
+                    .collect();
+                let asked = match common_forms.choose(&mut rand::thread_rng()) {
+                    Some(asked) => asked,
+                    None => return Ok(None),
+                };
+
+                // a form can be read several ways, ex: regi is dative or locative
+                let mut descriptions: Vec<String> = Vec::new();
+                for form in &paradigm.forms {
+                    if let (true, Form::LongForm(long_form)) =
+                        (form.orth == asked.orth && form.pos == entry.pos, &form.form)
+                    {
+                        // the gender of a noun is the gender of the word, not of the form
+                        let mut shown_form = long_form.clone();
+                        if entry.pos == PartOfSpeech::Noun {
+                            shown_form.gender = None;
+                        }
+                        let description = shown_form.as_clean_str();
+                        if !descriptions.contains(&description) {
+                            descriptions.push(description);
+                        }
+                        forms.push(long_form.clone());
+                    }
+                }
+                (
+                    format!("{}, a form of {} ({})", asked.orth, parts, pos),
+                    descriptions.join(" or "),
+                )
+            }
+        };
+
+        Ok(Some(Question {
+            kind,
+            prompt,
+            word: long_word,
+            answer,
+            forms,
+        }))
+    }
+
+    /**
+     * Latin to english answers are matched against the senses, inflected English words count as their dictionary form.
+     * English to latin answers are any principle part or another word with one of the senses.
+     * Principle parts can leave out the first one, and parsing answers can be in any order and abbreviated, ex: "abl pl".
+     */
+    pub fn grade(&self, dictionary: &Dictionary, answer: &str) -> Result<bool, VocabError> {
+        if answer.trim().is_empty() {
+            return Ok(false);
+        }
+
+        Ok(match self.kind {
+            QuizKind::LatinToEnglish => answer
+                .split([',', ';'])
+                .filter(|part| !part.trim().is_empty())
+                .all(|part| matches_senses(dictionary, &self.word.senses, part)),
+            QuizKind::EnglishToLatin => {
+                let answer = sanitize_word(answer);
+                if principle_parts(&self.word.parts).contains(&answer) {
+                    return Ok(true);
+                }
+                let definitions =
+                    translate_latin_to_english(dictionary, &answer, false, &Orthography::new())?;
+                definitions.iter().any(|definition| {
+                    sense_parts(&self.word.senses)
+                        .any(|part| matches_senses(dictionary, &definition.word.senses, part))
+                })
+            }
+            QuizKind::PrincipleParts => {
+                let parts = principle_parts(&self.word.parts);
+                let answer: Vec<String> = answer
+                    .split([',', ' '])
+                    .map(sanitize_word)
+                    .filter(|part| !part.is_empty())
+                    .collect();
+                answer == parts || answer == parts[1..]
+            }
+            QuizKind::Parsing => match parse_form_description(answer) {
+                Ok(target) => self
+                    .forms
+                    .iter()
+                    .any(|form| fits_target(form, &target) && names_every_feature(form, &target)),
+                // not a description, ex: a typo
+                Err(_) => false,
+            },
+        })
+    }
+}
+
+// every word of the principle parts that exist, sanitized, ex: "---" is left out and "secutus sum" is two words
+fn principle_parts(parts: &[String]) -> Vec<String> {
+    parts
+        .iter()
+        .flat_map(|part| part.split_whitespace())
+        .map(sanitize_word)
+        .filter(|part| !part.is_empty())
+        .collect()
+}
+
+fn sense_parts(senses: &[String]) -> impl Iterator<Item = &str> {
+    senses.iter().flat_map(|sense| sense.split([',', ';']))
+}
+
+// if every word of the answer is in one part of a sense, ex: "child" is in "(female) child"
+fn matches_senses(dictionary: &Dictionary, senses: &[String], answer: &str) -> bool {
+    let answer_words = content_words(dictionary, answer);
+    !answer_words.is_empty()
+        && sense_parts(senses).any(|part| {
+            let part_words = content_words(dictionary, part);
+            answer_words.iter().all(|word| part_words.contains(word))
+        })
+}
+
+fn content_words(dictionary: &Dictionary, text: &str) -> Vec<String> {
+    tokenize(text)
+        .into_iter()
+        .filter(|word| !STOP_WORDS.contains(&word.as_str()))
+        .map(|word| lemmatize(dictionary, &word).unwrap_or(word))
+        .collect()
+}
+
+// the answer has to name the case, number, tense, voice, mood and person the form has, the gender is optional
+fn names_every_feature(form: &LongForm, answer: &LongForm) -> bool {
+    fn named<T>(value: &Option<T>, answer: &Option<T>, as_str: fn(&T) -> &'static str) -> bool {
+        let known = value
+            .as_ref()
+            .is_some_and(|value| as_str(value) != "unknown");
+        !known || answer.is_some()
+    }
+    let has_person = form
+        .person
+        .as_deref()
+        .is_some_and(|person| !person.is_empty() && person != "unknown");
+
+    named(&form.declension, &answer.declension, Declension::as_str)
+        && named(&form.number, &answer.number, Number::as_str)
+        && named(&form.tense, &answer.tense, Tense::as_str)
+        && named(&form.voice, &answer.voice, Voice::as_str)
+        && named(&form.mood, &answer.mood, Mood::as_str)
+        && (!has_person || answer.person.is_some())
+}
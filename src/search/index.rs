@@ -0,0 +1,86 @@
+use crate::dictionary_structures::dictionary_values::LatinWordInfo;
+use crate::utils::data::Dictionary;
+use std::collections::HashMap;
+
+/**
+ * An inverted index over the senses of the Latin dictionary: every lowercase word used in a sense,
+ * with the Latin entries (indexes into `Dictionary::latin_words`) that use it, in dictionary order.
+ * The extension senses of an entry are indexed with it.
+ */
+pub struct SenseIndex {
+    postings: HashMap<String, Vec<usize>>,
+}
+
+impl SenseIndex {
+    pub fn build(dictionary: &Dictionary) -> SenseIndex {
+        let mut postings: HashMap<String, Vec<usize>> = HashMap::new();
+
+        for (index, word) in dictionary.latin_words.iter().enumerate() {
+            for sense in word_senses(dictionary, word) {
+                for token in tokenize(sense) {
+                    let entries = postings.entry(token).or_default();
+                    if entries.last() != Some(&index) {
+                        entries.push(index);
+                    }
+                }
+            }
+        }
+
+        SenseIndex { postings }
+    }
+
+    /**
+     * The entries with a sense using the word, the word must be lowercase
+     */
+    pub fn get(&self, token: &str) -> &[usize] {
+        match self.postings.get(token) {
+            Some(entries) => entries,
+            None => &[],
+        }
+    }
+
+    /**
+     * The entries with a sense using a word that starts with the prefix, in dictionary order
+     */
+    pub fn get_prefix(&self, prefix: &str) -> Vec<usize> {
+        let mut entries: Vec<usize> = self
+            .postings
+            .iter()
+            .filter(|(token, _)| token.starts_with(prefix))
+            .flat_map(|(_, entries)| entries.iter().copied())
+            .collect();
+        entries.sort_unstable();
+        entries.dedup();
+        entries
+    }
+}
+
+/**
+ * The senses of an entry followed by its extension senses,
+ * the senses of the next entry when they start with | also apply to this one
+ */
+pub fn word_senses<'a>(dictionary: &'a Dictionary, word: &'a LatinWordInfo) -> Vec<&'a String> {
+    let mut senses: Vec<&String> = word.senses.iter().collect();
+
+    if let Some(next_word) = dictionary.get_latin_word_by_id(word.id + 1) {
+        if next_word
+            .senses
+            .first()
+            .is_some_and(|sense| sense.starts_with('|'))
+        {
+            senses.extend(next_word.senses.iter());
+        }
+    }
+
+    senses
+}
+
+/**
+ * The lowercase words of a text, ex: "(female) child" -> ["female", "child"]
+ */
+pub fn tokenize(text: &str) -> Vec<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+        .collect()
+}
@@ -0,0 +1,96 @@
+pub mod index;
+pub mod query;
+
+use crate::dictionary_structures::dictionary_values::{Form, LatinWordInfo};
+use crate::error::VocabError;
+use crate::translators::{DisplayType, Structure};
+use crate::use_data::WordFilter;
+use crate::utils::data::Dictionary;
+use index::word_senses;
+use query::SenseQuery;
+use serde::Serialize;
+
+/**
+ * A Latin entry found by searching the senses, with its senses that matched the query
+ */
+#[derive(Debug, Clone, Serialize)]
+pub struct SenseMatch {
+    pub word: LatinWordInfo,
+    pub senses: Vec<String>,
+}
+
+/**
+ * Finds the Latin entries with senses matching the query that the filter allows, with their principle parts generated.
+ * Entries with a sense that is exactly a searched word or phrase come first, then the most frequent,
+ * at most max entries are returned.
+ */
+pub fn search_senses(
+    dictionary: &Dictionary,
+    query: &SenseQuery,
+    filter: &WordFilter,
+    max: usize,
+) -> Result<Vec<SenseMatch>, VocabError> {
+    let index = dictionary.get_sense_index();
+
+    // (not a whole sense, frequency, entry, matched senses)
+    let mut found: Vec<(bool, u8, usize, Vec<&String>)> = Vec::new();
+    for entry in query.candidates(index, dictionary.latin_words.len()) {
+        let word = &dictionary.latin_words[entry];
+        if !filter.allows(word) {
+            continue;
+        }
+
+        let senses = query.matching_senses(&word_senses(dictionary, word));
+        if senses.is_empty() {
+            continue;
+        }
+        let whole_sense = senses.iter().any(|sense| query.is_whole_sense(sense));
+        found.push((!whole_sense, word.info.freq.as_number(), entry, senses));
+    }
+
+    found.sort_by_key(|(partial, freq, entry, _)| (*partial, *freq, *entry));
+    found.truncate(max);
+
+    found
+        .into_iter()
+        .map(|(_, _, entry, senses)| {
+            let mut word = dictionary.latin_words[entry].clone();
+            word.form
+                .str_form_to_long_form(word.pos, Structure::LatinWordInfo);
+            word.generate_principle_parts()?;
+
+            Ok(SenseMatch {
+                word,
+                senses: senses.into_iter().cloned().collect(),
+            })
+        })
+        .collect()
+}
+
+impl SenseMatch {
+    pub fn display(&self, display_type: DisplayType) {
+        match display_type {
+            DisplayType::Pretty(detailed) => {
+                let form = match &self.word.form {
+                    Form::StrForm(form) => form.to_string(),
+                    Form::LongForm(form) => form.as_clean_str(),
+                };
+                println!("{}: {}", self.word.id, self.word.parts.join(", "));
+                println!("{} {}", self.word.pos.as_str(), form);
+                if detailed {
+                    println!("{}", self.word.info.as_str());
+                }
+                for sense in &self.senses {
+                    println!("  {}", sense);
+                }
+                println!();
+            }
+            DisplayType::Json => {
+                println!(
+                    "{}",
+                    serde_json::to_string_pretty(&self).unwrap_or_default()
+                );
+            }
+        }
+    }
+}
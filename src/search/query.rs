@@ -0,0 +1,280 @@
+use crate::error::VocabError;
+use crate::search::index::{tokenize, SenseIndex};
+use regex::{Regex, RegexBuilder};
+
+/**
+ * One term of a search query
+ */
+pub enum Term {
+    /// A word of a sense, ex: ship
+    Word(String),
+    /// The start of a word of a sense, written with a trailing *, ex: ship* for ships and shipwreck
+    Prefix(String),
+    /// Words that follow each other in one sense, written in double quotes, ex: "go out"
+    Phrase(Vec<String>),
+    /// A case insensitive regex matched against each whole sense, written between slashes, ex: /\bsword\b/
+    Regex(Regex),
+}
+
+/**
+ * A parsed search query. Terms separated by spaces (or AND) must all be in the senses of an entry,
+ * groups of terms separated by OR are alternatives, ex: ship OR "war vessel" OR /^boat/.
+ * AND binds tighter than OR and there are no parentheses.
+ */
+pub struct SenseQuery {
+    clauses: Vec<Vec<Term>>,
+}
+
+impl SenseQuery {
+    pub fn parse(query: &str) -> Result<SenseQuery, VocabError> {
+        let invalid = |value: &str| VocabError::InvalidArgument {
+            name: "query".to_string(),
+            value: value.to_string(),
+        };
+
+        let mut clauses: Vec<Vec<Term>> = vec![Vec::new()];
+        let mut rest = query.trim_start();
+
+        while !rest.is_empty() {
+            let term = if let Some(quoted) = rest.strip_prefix('"') {
+                let (phrase, after) = quoted.split_once('"').ok_or_else(|| invalid(rest))?;
+                rest = after;
+                words_term(tokenize(phrase), false)
+            } else if let Some(pattern) = rest.strip_prefix('/') {
+                let end = closing_slash(pattern).ok_or_else(|| invalid(rest))?;
+                let regex = RegexBuilder::new(&pattern[..end].replace("\\/", "/"))
+                    .case_insensitive(true)
+                    .build()
+                    .map_err(|error| invalid(&error.to_string()))?;
+                rest = &pattern[end + 1..];
+                Some(Term::Regex(regex))
+            } else {
+                let end = rest.find(char::is_whitespace).unwrap_or(rest.len());
+                let (word, after) = rest.split_at(end);
+                rest = after;
+                match word {
+                    "OR" => {
+                        clauses.push(Vec::new());
+                        None
+                    }
+                    "AND" => None,
+                    _ => words_term(tokenize(word.trim_end_matches('*')), word.ends_with('*')),
+                }
+            };
+
+            if let Some(term) = term {
+                if let Some(clause) = clauses.last_mut() {
+                    clause.push(term);
+                }
+            }
+            rest = rest.trim_start();
+        }
+
+        clauses.retain(|clause| !clause.is_empty());
+        if clauses.is_empty() {
+            return Err(invalid(query));
+        }
+
+        Ok(SenseQuery { clauses })
+    }
+
+    /**
+     * The entries that can match the query, in dictionary order.
+     * A group of terms with only regexes can't use the index, so every entry is a candidate for it.
+     */
+    pub fn candidates(&self, index: &SenseIndex, entry_count: usize) -> Vec<usize> {
+        let mut candidates: Vec<usize> = Vec::new();
+
+        for clause in &self.clauses {
+            let mut clause_candidates: Option<Vec<usize>> = None;
+            for term in clause {
+                let entries = match term {
+                    Term::Word(word) => index.get(word).to_vec(),
+                    Term::Prefix(prefix) => index.get_prefix(prefix),
+                    Term::Phrase(words) => words
+                        .iter()
+                        .map(|word| index.get(word).to_vec())
+                        .reduce(|entries, word_entries| intersect(&entries, &word_entries))
+                        .unwrap_or_default(),
+                    Term::Regex(_) => continue,
+                };
+                clause_candidates = Some(match clause_candidates {
+                    Some(clause_candidates) => intersect(&clause_candidates, &entries),
+                    None => entries,
+                });
+            }
+            candidates.extend(clause_candidates.unwrap_or_else(|| (0..entry_count).collect()));
+        }
+
+        candidates.sort_unstable();
+        candidates.dedup();
+        candidates
+    }
+
+    /**
+     * The senses that make an entry match the query, in order, empty if the entry does not match.
+     * When several groups of terms match, the senses of all of them are returned.
+     */
+    pub fn matching_senses<'a>(&self, senses: &[&'a String]) -> Vec<&'a String> {
+        let tokens: Vec<Vec<String>> = senses.iter().map(|sense| tokenize(sense)).collect();
+        let mut matched = vec![false; senses.len()];
+
+        for clause in &self.clauses {
+            let mut clause_matched = vec![false; senses.len()];
+            let all_terms_found = clause.iter().all(|term| {
+                let mut found = false;
+                for (index, sense) in senses.iter().enumerate() {
+                    if term.matches(sense, &tokens[index]) {
+                        clause_matched[index] = true;
+                        found = true;
+                    }
+                }
+                found
+            });
+
+            if all_terms_found {
+                for (index, sense_matched) in clause_matched.into_iter().enumerate() {
+                    matched[index] |= sense_matched;
+                }
+            }
+        }
+
+        senses
+            .iter()
+            .zip(matched)
+            .filter(|(_, matched)| *matched)
+            .map(|(sense, _)| *sense)
+            .collect()
+    }
+
+    /**
+     * If a part of the sense between commas or semicolons is exactly one of the searched words or phrases,
+     * ex: "ship" in "ship, vessel" but not in "crew of a ship"
+     */
+    pub fn is_whole_sense(&self, sense: &str) -> bool {
+        sense.split([',', ';']).any(|part| {
+            let part_words = tokenize(part);
+            self.clauses.iter().flatten().any(|term| match term {
+                Term::Word(word) => part_words.len() == 1 && &part_words[0] == word,
+                Term::Phrase(words) => &part_words == words,
+                Term::Prefix(_) | Term::Regex(_) => false,
+            })
+        })
+    }
+}
+
+impl Term {
+    fn matches(&self, sense: &str, sense_words: &[String]) -> bool {
+        match self {
+            Term::Word(word) => sense_words.contains(word),
+            Term::Prefix(prefix) => sense_words.iter().any(|word| word.starts_with(prefix)),
+            Term::Phrase(words) => sense_words
+                .windows(words.len())
+                .any(|window| window == words.as_slice()),
+            Term::Regex(regex) => regex.is_match(sense),
+        }
+    }
+}
+
+// a word written with punctuation inside is searched as a phrase, ex: horse-drawn
+fn words_term(mut words: Vec<String>, prefix: bool) -> Option<Term> {
+    match words.len() {
+        0 => None,
+        1 if prefix => words.pop().map(Term::Prefix),
+        1 => words.pop().map(Term::Word),
+        _ => Some(Term::Phrase(words)),
+    }
+}
+
+// the first slash that is not escaped with a backslash
+fn closing_slash(pattern: &str) -> Option<usize> {
+    let mut escaped = false;
+    for (index, c) in pattern.char_indices() {
+        match c {
+            '/' if !escaped => return Some(index),
+            '\\' => escaped = !escaped,
+            _ => escaped = false,
+        }
+    }
+    None
+}
+
+// both lists are sorted
+fn intersect(a: &[usize], b: &[usize]) -> Vec<usize> {
+    a.iter()
+        .filter(|entry| b.binary_search(entry).is_ok())
+        .copied()
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // each group of terms, ex: [["word ship", "prefix sail"], ["phrase war vessel"]]
+    fn parsed(query: &str) -> Vec<Vec<String>> {
+        let query = SenseQuery::parse(query).unwrap();
+        query
+            .clauses
+            .iter()
+            .map(|clause| {
+                clause
+                    .iter()
+                    .map(|term| match term {
+                        Term::Word(word) => format!("word {}", word),
+                        Term::Prefix(prefix) => format!("prefix {}", prefix),
+                        Term::Phrase(words) => format!("phrase {}", words.join(" ")),
+                        Term::Regex(regex) => format!("regex {}", regex.as_str()),
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn parses_words_and_prefixes() {
+        assert_eq!(parsed("Ship  sail*"), [["word ship", "prefix sail"]]);
+    }
+
+    #[test]
+    fn parses_quoted_phrases() {
+        assert_eq!(
+            parsed("\"go out\" \"Ship\" horse-drawn"),
+            [["phrase go out", "word ship", "phrase horse drawn"]]
+        );
+    }
+
+    #[test]
+    fn parses_regexes() {
+        assert_eq!(parsed(r"/\bsword\b/"), [[r"regex \bsword\b"]]);
+        assert_eq!(parsed(r"/a\/b/ c"), [["regex a/b", "word c"]]);
+
+        let query = SenseQuery::parse("/^sword/").unwrap();
+        let sense = "Sword, blade".to_string();
+        assert_eq!(query.matching_senses(&[&sense]), [&sense]);
+    }
+
+    #[test]
+    fn splits_groups_on_or() {
+        assert_eq!(
+            parsed("ship AND boat OR \"war vessel\""),
+            [vec!["word ship", "word boat"], vec!["phrase war vessel"]]
+        );
+        assert_eq!(parsed("OR ship OR OR"), [["word ship"]]);
+    }
+
+    #[test]
+    fn treats_field_prefixes_and_negation_as_words() {
+        // filters are separate arguments and there is no negation, the punctuation is not part of any word
+        assert_eq!(parsed("pos:noun"), [["phrase pos noun"]]);
+        assert_eq!(parsed("-ship"), [["word ship"]]);
+        assert_eq!(parsed("NOT ship"), [["word not", "word ship"]]);
+    }
+
+    #[test]
+    fn rejects_malformed_queries() {
+        for query in ["", "   ", "OR AND", "\"go out", "/sword", "/[/", "*** \"\""] {
+            assert!(SenseQuery::parse(query).is_err(), "{:?} was parsed", query);
+        }
+    }
+}
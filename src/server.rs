@@ -0,0 +1,329 @@
+use std::collections::HashMap;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::sync::mpsc::sync_channel;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use serde::Serialize;
+use vocab_vault::{Translator, VocabError, WordType};
+
+use crate::{exit_code, inflect_word, parse_list_filter, parse_number};
+
+// the most words /list returns, a whole dictionary is several megabytes of json
+const MAX_LIST_AMOUNT: i32 = 1000;
+// a client that has not sent its request by then is dropped instead of holding its thread
+const READ_TIMEOUT: Duration = Duration::from_secs(10);
+const WRITE_TIMEOUT: Duration = Duration::from_secs(10);
+// the most bytes read for the request line and headers, longer requests are refused
+const MAX_REQUEST_SIZE: u64 = 16 * 1024;
+// the connections handled at once, the others wait to be accepted
+const WORKERS: usize = 8;
+
+/**
+ * A response with its status code and json body
+ */
+struct Response {
+    status: u16,
+    body: String,
+}
+
+impl Response {
+    fn json<T: Serialize>(value: &T) -> Result<Response, VocabError> {
+        Ok(Response {
+            status: 200,
+            body: serde_json::to_string_pretty(value)?,
+        })
+    }
+
+    fn error(status: u16, message: &str) -> Response {
+        Response {
+            status,
+            body: serde_json::json!({ "error": message }).to_string(),
+        }
+    }
+
+    fn reason(&self) -> &'static str {
+        match self.status {
+            200 => "OK",
+            400 => "Bad Request",
+            404 => "Not Found",
+            405 => "Method Not Allowed",
+            431 => "Request Header Fields Too Large",
+            _ => "Internal Server Error",
+        }
+    }
+}
+
+/**
+ * Serves the translator as json over http on localhost, WORKERS threads handle the connections.
+ * The dictionaries are loaded once and shared by every request.
+ */
+pub fn serve(translator: Translator, port: u16) -> Result<(), VocabError> {
+    let listener = TcpListener::bind(("127.0.0.1", port))?;
+    let translator = Arc::new(translator);
+    println!("Listening on http://127.0.0.1:{}", port);
+
+    // when every worker is busy and the queue is full, accepting waits for a free worker
+    let (sender, receiver) = sync_channel::<TcpStream>(WORKERS);
+    let receiver = Arc::new(Mutex::new(receiver));
+    for _ in 0..WORKERS {
+        let translator = Arc::clone(&translator);
+        let receiver = Arc::clone(&receiver);
+        std::thread::spawn(move || loop {
+            let stream = match receiver.lock() {
+                Ok(receiver) => match receiver.recv() {
+                    Ok(stream) => stream,
+                    Err(_) => return,
+                },
+                Err(_) => return,
+            };
+            // a panic while answering is a 500, the worker keeps taking connections
+            let panic_stream = stream.try_clone();
+            match catch_unwind(AssertUnwindSafe(|| handle_connection(&translator, stream))) {
+                // a client that hangs up should not stop the server
+                Ok(Err(error)) => eprintln!("{}", error),
+                Ok(Ok(())) => {}
+                Err(_) => {
+                    let response = Response::error(500, "the request could not be answered");
+                    if let Ok(mut stream) = panic_stream {
+                        if let Err(error) = write_response(&mut stream, &response) {
+                            eprintln!("{}", error);
+                        }
+                    }
+                }
+            }
+        });
+    }
+
+    for stream in listener.incoming() {
+        match stream {
+            Ok(stream) => {
+                if sender.send(stream).is_err() {
+                    break;
+                }
+            }
+            Err(error) => eprintln!("{}", error),
+        }
+    }
+
+    Ok(())
+}
+
+fn handle_connection(translator: &Translator, mut stream: TcpStream) -> Result<(), VocabError> {
+    stream.set_read_timeout(Some(READ_TIMEOUT))?;
+    stream.set_write_timeout(Some(WRITE_TIMEOUT))?;
+    let mut reader = BufReader::new((&stream).take(MAX_REQUEST_SIZE));
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+
+    // the headers are not used, but have to be read before answering
+    let mut header = request_line.clone();
+    while header.ends_with('\n') {
+        header.clear();
+        reader.read_line(&mut header)?;
+        if header.trim().is_empty() {
+            break;
+        }
+    }
+    // a line that was cut off at the limit never ends
+    let too_large = !header.ends_with('\n') && reader.get_ref().limit() == 0;
+
+    let mut parts = request_line.split_whitespace();
+    let method = parts.next().unwrap_or_default();
+    let target = parts.next().unwrap_or_default();
+    let (path, query) = target.split_once('?').unwrap_or((target, ""));
+
+    let response = match method {
+        _ if too_large => Response::error(431, "the request line and headers are too large"),
+        "GET" => route(translator, path, &parse_query(query)),
+        _ => Response::error(405, "only GET requests are supported"),
+    };
+    eprintln!("{} {} {}", method, target, response.status);
+
+    write_response(&mut stream, &response)
+}
+
+fn write_response(stream: &mut TcpStream, response: &Response) -> Result<(), VocabError> {
+    write!(
+        stream,
+        "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nAccess-Control-Allow-Origin: *\r\nConnection: close\r\n\r\n{}",
+        response.status,
+        response.reason(),
+        response.body.len(),
+        response.body
+    )?;
+    stream.flush()?;
+    Ok(())
+}
+
+fn route(translator: &Translator, path: &str, query: &HashMap<String, String>) -> Response {
+    let result = match path.trim_end_matches('/') {
+        "/latin" => latin(translator, query),
+        "/english" => english(translator, query),
+        "/list" => list(translator, query),
+        "/inflect" => inflect(translator, query),
+        _ => return Response::error(404, &format!("no endpoint at {}", path)),
+    };
+
+    result.unwrap_or_else(|error| {
+        let status = match exit_code(&error) {
+            2 => 400,
+            _ => 500,
+        };
+        Response::error(status, &error.to_string())
+    })
+}
+
+/**
+ * GET /latin?word=puellam amat, the same json as transLat
+ */
+fn latin(translator: &Translator, query: &HashMap<String, String>) -> Result<Response, VocabError> {
+    let word = required(query, "word")?;
+    Response::json(&translator.translate_latin(word)?)
+}
+
+/**
+ * GET /english?word=girl, the same json as transEng. With phrases the words are read as phrases separated by commas
+ */
+fn english(
+    translator: &Translator,
+    query: &HashMap<String, String>,
+) -> Result<Response, VocabError> {
+    let word = required(query, "word")?;
+    let translations = match query.contains_key("phrases") {
+        true => translator.translate_english_phrases(word)?,
+        false => translator.translate_english(word)?,
+    };
+    Response::json(&translations)
+}
+
+/**
+ * GET /list?type=latin&amount=10&random, takes the same filters as getList, ex: pos=noun&min-freq=common.
+ * At most MAX_LIST_AMOUNT words are returned, also when no amount is given
+ */
+fn list(translator: &Translator, query: &HashMap<String, String>) -> Result<Response, VocabError> {
+    let type_of_words = required(query, "type")?;
+    let word_type = type_of_words
+        .parse::<WordType>()
+        .map_err(|_| VocabError::InvalidWordType(type_of_words.to_string()))?;
+    let filter = parse_list_filter(&|name| query.get(name).cloned())?;
+    let amount = match query.get("amount") {
+        Some(amount) => match parse_number("amount", amount)? {
+            0 => {
+                return Err(VocabError::InvalidArgument {
+                    name: "amount".to_string(),
+                    value: amount.to_string(),
+                })
+            }
+            amount => amount.min(MAX_LIST_AMOUNT as usize) as i32,
+        },
+        None => MAX_LIST_AMOUNT,
+    };
+    let random = query.get("random").is_some_and(|random| random != "false");
+
+    Response::json(&translator.get_list(word_type, &filter, Some(amount), random)?)
+}
+
+/**
+ * GET /inflect?word=rex, the same json as inflect, the word can also be a dictionary id.
+ * A word or id without an entry is a 404
+ */
+fn inflect(
+    translator: &Translator,
+    query: &HashMap<String, String>,
+) -> Result<Response, VocabError> {
+    let word = required(query, "word")?;
+    let paradigms = inflect_word(translator, word)?;
+    if paradigms.is_empty() {
+        return Ok(Response::error(
+            404,
+            &format!("no dictionary entry for {}", word),
+        ));
+    }
+    Response::json(&paradigms)
+}
+
+fn required<'a>(query: &'a HashMap<String, String>, name: &str) -> Result<&'a String, VocabError> {
+    query
+        .get(name)
+        .filter(|value| !value.trim().is_empty())
+        .ok_or_else(|| VocabError::MissingArgument(name.to_string()))
+}
+
+/**
+ * The key value pairs of a query string, ex: "word=puellam+amat&random" -> {word: "puellam amat", random: ""}
+ */
+fn parse_query(query: &str) -> HashMap<String, String> {
+    query
+        .split('&')
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| {
+            let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+            (percent_decode(key), percent_decode(value))
+        })
+        .collect()
+}
+
+// + is a space, %XX is a byte, invalid escapes are kept as they are
+fn percent_decode(value: &str) -> String {
+    let bytes = value.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut index = 0;
+
+    while index < bytes.len() {
+        let escaped = match bytes[index] {
+            b'%' => bytes
+                .get(index + 1..index + 3)
+                .filter(|hex| hex.iter().all(u8::is_ascii_hexdigit))
+                .and_then(|hex| std::str::from_utf8(hex).ok())
+                .and_then(|hex| u8::from_str_radix(hex, 16).ok()),
+            _ => None,
+        };
+
+        match (escaped, bytes[index]) {
+            (Some(byte), _) => {
+                decoded.push(byte);
+                index += 3;
+            }
+            (None, b'+') => {
+                decoded.push(b' ');
+                index += 1;
+            }
+            (None, byte) => {
+                decoded.push(byte);
+                index += 1;
+            }
+        }
+    }
+
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decodes_escapes_and_plus() {
+        assert_eq!(percent_decode("puellam+amat"), "puellam amat");
+        assert_eq!(percent_decode("%C3%B1%2c"), "ñ,");
+    }
+
+    #[test]
+    fn keeps_invalid_escapes() {
+        assert_eq!(percent_decode("%+1"), "% 1");
+        assert_eq!(percent_decode("%zz%4"), "%zz%4");
+        assert_eq!(percent_decode("100%"), "100%");
+    }
+
+    #[test]
+    fn parses_query_pairs() {
+        let query = parse_query("word=puellam+amat&random&&type=%6Catin");
+        assert_eq!(query.len(), 3);
+        assert_eq!(query["word"], "puellam amat");
+        assert_eq!(query["random"], "");
+        assert_eq!(query["type"], "latin");
+    }
+}
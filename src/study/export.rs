@@ -0,0 +1,103 @@
+use super::Deck;
+use crate::error::VocabError;
+use crate::lookup::lookup_latin;
+use crate::utils::data::Dictionary;
+use std::str::FromStr;
+
+const COLUMNS: [&str; 4] = ["Latin", "Part of speech", "Senses", "Id"];
+
+/**
+ * The text formats Anki can import notes from
+ */
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ExportFormat {
+    Csv,
+    Tsv,
+}
+
+impl ExportFormat {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ExportFormat::Csv => "csv",
+            ExportFormat::Tsv => "tsv",
+        }
+    }
+
+    fn separator(&self) -> char {
+        match self {
+            ExportFormat::Csv => ',',
+            ExportFormat::Tsv => '\t',
+        }
+    }
+
+    // the name anki uses in the #separator header
+    fn separator_name(&self) -> &'static str {
+        match self {
+            ExportFormat::Csv => "comma",
+            ExportFormat::Tsv => "tab",
+        }
+    }
+}
+
+impl FromStr for ExportFormat {
+    type Err = VocabError;
+
+    fn from_str(s: &str) -> Result<ExportFormat, VocabError> {
+        match s {
+            "csv" => Ok(ExportFormat::Csv),
+            "tsv" | "txt" => Ok(ExportFormat::Tsv),
+            _ => Err(VocabError::InvalidArgument {
+                name: "format".to_string(),
+                value: s.to_string(),
+            }),
+        }
+    }
+}
+
+/**
+ * One note per card with the principle parts, part of speech, senses and id as fields.
+ * The header lines tell Anki the separator and column names, older versions skip them as comments.
+ */
+pub fn export_deck(
+    dictionary: &Dictionary,
+    deck: &Deck,
+    format: ExportFormat,
+) -> Result<String, VocabError> {
+    let separator = format.separator();
+    let mut output = format!(
+        "#separator:{}\n#html:false\n#columns:{}\n",
+        format.separator_name(),
+        COLUMNS.join(&separator.to_string())
+    );
+
+    for card in &deck.cards {
+        // an entry missing from the dictionary keeps the parts it was added with
+        let (parts, pos, senses) = match lookup_latin(dictionary, card.id)? {
+            Some(entry) => (
+                entry.word.parts.join(", "),
+                entry.word.pos.as_str().to_string(),
+                entry.word.senses.join("; "),
+            ),
+            None => (card.word.clone(), String::new(), String::new()),
+        };
+
+        let fields = [parts, pos, senses, card.id.to_string()];
+        let row: Vec<String> = fields
+            .iter()
+            .map(|field| quote_field(field, separator))
+            .collect();
+        output.push_str(&row.join(&separator.to_string()));
+        output.push('\n');
+    }
+
+    Ok(output)
+}
+
+// fields with the separator, a quote or a new line are quoted with their quotes doubled, ex: "bonus, -a, -um"
+fn quote_field(field: &str, separator: char) -> String {
+    if field.contains([separator, '"', '\n']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
@@ -0,0 +1,362 @@
+use crate::error::VocabError;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+pub mod export;
+
+pub use self::export::ExportFormat;
+
+const DATA_FOLDER: &str = "vocab-vault";
+const DECK_FOLDER: &str = "decks";
+const STARTING_EASE: f64 = 2.5;
+// SM-2 never lets a card get harder than this, or it would come back almost every day forever
+const MIN_EASE: f64 = 1.3;
+
+/**
+ * How well a card was remembered, from forgotten to effortless
+ */
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Rating {
+    Again,
+    Hard,
+    Good,
+    Easy,
+}
+
+impl Rating {
+    pub fn all() -> Vec<Rating> {
+        vec![Rating::Again, Rating::Hard, Rating::Good, Rating::Easy]
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Rating::Again => "again",
+            Rating::Hard => "hard",
+            Rating::Good => "good",
+            Rating::Easy => "easy",
+        }
+    }
+
+    /**
+     * The SM-2 quality of the response, below 3 is a lapse
+     */
+    pub fn quality(&self) -> u8 {
+        match self {
+            Rating::Again => 1,
+            Rating::Hard => 3,
+            Rating::Good => 4,
+            Rating::Easy => 5,
+        }
+    }
+}
+
+impl FromStr for Rating {
+    type Err = VocabError;
+
+    fn from_str(s: &str) -> Result<Rating, VocabError> {
+        match s {
+            "1" | "a" | "again" => Ok(Rating::Again),
+            "2" | "h" | "hard" => Ok(Rating::Hard),
+            "3" | "g" | "good" => Ok(Rating::Good),
+            "4" | "e" | "easy" => Ok(Rating::Easy),
+            _ => Err(VocabError::InvalidArgument {
+                name: "rating".to_string(),
+                value: s.to_string(),
+            }),
+        }
+    }
+}
+
+/**
+ * A dictionary entry in a deck with its review schedule. Days are counted from the unix epoch, see [`today`]
+ */
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Card {
+    /// The id of the Latin entry
+    pub id: i32,
+    /// The principle parts of the entry when it was added, ex: amo, amare, amavi, amatus
+    pub word: String,
+    pub ease: f64,
+    /// The days between the last review and the next
+    pub interval: u64,
+    /// The reviews in a row that were remembered
+    pub repetitions: u32,
+    pub due: u64,
+    pub reviews: u32,
+    pub lapses: u32,
+    pub last_review: Option<u64>,
+}
+
+impl Card {
+    /**
+     * A card that has never been reviewed, due today
+     */
+    pub fn new(id: i32, word: &str, today: u64) -> Card {
+        Card {
+            id,
+            word: word.to_string(),
+            ease: STARTING_EASE,
+            interval: 0,
+            repetitions: 0,
+            due: today,
+            reviews: 0,
+            lapses: 0,
+            last_review: None,
+        }
+    }
+
+    pub fn is_due(&self, today: u64) -> bool {
+        self.due <= today
+    }
+
+    /**
+     * Schedules the next review with SM-2: a lapse starts the card over the next day,
+     * otherwise it comes back after 1 day, then 6, then the last interval times the ease.
+     * The ease grows with easy answers and shrinks with hard ones.
+     */
+    pub fn review(&mut self, rating: Rating, today: u64) {
+        let quality = rating.quality();
+
+        if quality < 3 {
+            self.repetitions = 0;
+            self.interval = 1;
+            self.lapses += 1;
+        } else {
+            self.repetitions += 1;
+            self.interval = match self.repetitions {
+                1 => 1,
+                2 => 6,
+                _ => (self.interval as f64 * self.ease).round() as u64,
+            };
+        }
+
+        let missed = (5 - quality) as f64;
+        self.ease = (self.ease + 0.1 - missed * (0.08 + missed * 0.02)).max(MIN_EASE);
+        self.due = today + self.interval;
+        self.reviews += 1;
+        self.last_review = Some(today);
+    }
+}
+
+/**
+ * A named list of cards, saved as json in the user's data folder, ex: ~/.local/share/vocab-vault/decks/default.json
+ */
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Deck {
+    pub name: String,
+    pub cards: Vec<Card>,
+}
+
+impl Deck {
+    /**
+     * Loads the deck with the name, an empty deck if it has not been saved yet
+     */
+    pub fn open(name: &str) -> Result<Deck, VocabError> {
+        let path = deck_path(name)?;
+        if !path.exists() {
+            return Ok(Deck {
+                name: name.to_string(),
+                cards: Vec::new(),
+            });
+        }
+
+        let deck = std::fs::read_to_string(path)?;
+        Ok(serde_json::from_str(&deck)?)
+    }
+
+    pub fn save(&self) -> Result<(), VocabError> {
+        let path = deck_path(&self.name)?;
+        if let Some(folder) = path.parent() {
+            std::fs::create_dir_all(folder)?;
+        }
+        std::fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    pub fn contains(&self, id: i32) -> bool {
+        self.cards.iter().any(|card| card.id == id)
+    }
+
+    /**
+     * Adds the card, false if there already is a card for the entry
+     */
+    pub fn add(&mut self, card: Card) -> bool {
+        if self.contains(card.id) {
+            return false;
+        }
+        self.cards.push(card);
+        true
+    }
+
+    /**
+     * Removes the card for the entry, None if it is not in the deck
+     */
+    pub fn remove(&mut self, id: i32) -> Option<Card> {
+        let index = self.cards.iter().position(|card| card.id == id)?;
+        Some(self.cards.remove(index))
+    }
+
+    /**
+     * The indexes of the cards due by the day, the most overdue first and new cards last
+     */
+    pub fn due(&self, today: u64) -> Vec<usize> {
+        let mut due: Vec<usize> = (0..self.cards.len())
+            .filter(|&index| self.cards[index].is_due(today))
+            .collect();
+        due.sort_by_key(|&index| {
+            let card = &self.cards[index];
+            (card.reviews == 0, card.due)
+        });
+        due
+    }
+
+    /**
+     * The day the next card is due, None for an empty deck
+     */
+    pub fn next_due(&self) -> Option<u64> {
+        self.cards.iter().map(|card| card.due).min()
+    }
+}
+
+/**
+ * The current day, counted in whole days from the unix epoch
+ */
+pub fn today() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|time| time.as_secs() / (60 * 60 * 24))
+        .unwrap_or_default()
+}
+
+/**
+ * Where the deck with the name is saved. Names are plain file names, ex: "verbs", not "../verbs"
+ */
+pub fn deck_path(name: &str) -> Result<PathBuf, VocabError> {
+    let valid = !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_alphanumeric() || c == '-' || c == '_');
+    if !valid {
+        return Err(VocabError::InvalidArgument {
+            name: "deck".to_string(),
+            value: name.to_string(),
+        });
+    }
+
+    let data_dir = data_dir().ok_or_else(|| {
+        VocabError::Io(std::io::Error::new(
+            std::io::ErrorKind::NotFound,
+            "no data folder, set HOME (or APPDATA on windows)",
+        ))
+    })?;
+    Ok(data_dir
+        .join(DATA_FOLDER)
+        .join(DECK_FOLDER)
+        .join(format!("{}.json", name)))
+}
+
+// %APPDATA% on windows, ~/Library/Application Support on macos and $XDG_DATA_HOME or ~/.local/share elsewhere
+fn data_dir() -> Option<PathBuf> {
+    let var = |name: &str| {
+        std::env::var_os(name)
+            .filter(|value| !value.is_empty())
+            .map(PathBuf::from)
+    };
+
+    if cfg!(windows) {
+        var("APPDATA")
+    } else if cfg!(target_os = "macos") {
+        var("HOME").map(|home| home.join("Library").join("Application Support"))
+    } else {
+        var("XDG_DATA_HOME").or_else(|| var("HOME").map(|home| home.join(".local").join("share")))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_ease(card: &Card, ease: f64) {
+        assert!(
+            (card.ease - ease).abs() < 1e-9,
+            "ease {} is not {}",
+            card.ease,
+            ease
+        );
+    }
+
+    #[test]
+    fn first_repetition_comes_back_the_next_day() {
+        let mut card = Card::new(1, "amo, amare, amavi, amatus", 100);
+        assert!(card.is_due(100));
+
+        card.review(Rating::Good, 100);
+        assert_eq!(card.repetitions, 1);
+        assert_eq!(card.interval, 1);
+        assert_eq!(card.due, 101);
+        assert_eq!(card.reviews, 1);
+        assert_eq!(card.last_review, Some(100));
+        assert_ease(&card, STARTING_EASE);
+        assert!(!card.is_due(100));
+    }
+
+    #[test]
+    fn later_repetitions_grow_with_the_ease() {
+        let mut card = Card::new(1, "amo, amare, amavi, amatus", 100);
+        card.review(Rating::Good, 100);
+
+        card.review(Rating::Good, 101);
+        assert_eq!((card.repetitions, card.interval, card.due), (2, 6, 107));
+
+        card.review(Rating::Good, 107);
+        assert_eq!((card.repetitions, card.interval, card.due), (3, 15, 122));
+
+        // the interval uses the ease from before the review, 15 * 2.5 rounded
+        card.review(Rating::Easy, 122);
+        assert_eq!((card.repetitions, card.interval, card.due), (4, 38, 160));
+        assert_ease(&card, 2.6);
+
+        card.review(Rating::Hard, 160);
+        assert_eq!(card.interval, 99);
+        assert_ease(&card, 2.46);
+    }
+
+    #[test]
+    fn failed_review_starts_over() {
+        let mut card = Card::new(1, "amo, amare, amavi, amatus", 100);
+        card.review(Rating::Good, 100);
+        card.review(Rating::Good, 101);
+        card.review(Rating::Good, 107);
+
+        card.review(Rating::Again, 122);
+        assert_eq!(card.repetitions, 0);
+        assert_eq!(card.interval, 1);
+        assert_eq!(card.due, 123);
+        assert_eq!(card.lapses, 1);
+        assert_eq!(card.reviews, 4);
+        assert_ease(&card, 1.96);
+
+        card.review(Rating::Good, 123);
+        assert_eq!((card.repetitions, card.interval, card.due), (1, 1, 124));
+    }
+
+    #[test]
+    fn ease_stays_above_the_minimum() {
+        let mut card = Card::new(1, "amo, amare, amavi, amatus", 100);
+        for day in 100..110 {
+            card.review(Rating::Again, day);
+        }
+        assert_ease(&card, MIN_EASE);
+        assert_eq!(card.lapses, 10);
+
+        for day in 110..120 {
+            card.review(Rating::Hard, day);
+        }
+        assert_ease(&card, MIN_EASE);
+
+        card.review(Rating::Easy, 120);
+        assert_ease(&card, MIN_EASE + 0.1);
+    }
+}
@@ -0,0 +1,249 @@
+use crate::utils::data::Dictionary;
+
+// irregular forms that the suffix rules can't undo
+const EXCEPTIONS: &[(&str, &str)] = &[
+    ("am", "be"),
+    ("are", "be"),
+    ("is", "be"),
+    ("was", "be"),
+    ("were", "be"),
+    ("been", "be"),
+    ("being", "be"),
+    ("has", "have"),
+    ("had", "have"),
+    ("does", "do"),
+    ("did", "do"),
+    ("done", "do"),
+    ("went", "go"),
+    ("gone", "go"),
+    ("goes", "go"),
+    ("said", "say"),
+    ("made", "make"),
+    ("took", "take"),
+    ("taken", "take"),
+    ("came", "come"),
+    ("saw", "see"),
+    ("seen", "see"),
+    ("knew", "know"),
+    ("known", "know"),
+    ("gave", "give"),
+    ("given", "give"),
+    ("found", "find"),
+    ("thought", "think"),
+    ("told", "tell"),
+    ("became", "become"),
+    ("left", "leave"),
+    ("felt", "feel"),
+    ("brought", "bring"),
+    ("began", "begin"),
+    ("begun", "begin"),
+    ("kept", "keep"),
+    ("held", "hold"),
+    ("wrote", "write"),
+    ("written", "write"),
+    ("stood", "stand"),
+    ("heard", "hear"),
+    ("meant", "mean"),
+    ("met", "meet"),
+    ("ran", "run"),
+    ("paid", "pay"),
+    ("sat", "sit"),
+    ("spoke", "speak"),
+    ("spoken", "speak"),
+    ("lay", "lie"),
+    ("lain", "lie"),
+    ("led", "lead"),
+    ("grew", "grow"),
+    ("grown", "grow"),
+    ("lost", "lose"),
+    ("fell", "fall"),
+    ("fallen", "fall"),
+    ("sent", "send"),
+    ("built", "build"),
+    ("understood", "understand"),
+    ("drew", "draw"),
+    ("drawn", "draw"),
+    ("broke", "break"),
+    ("broken", "break"),
+    ("spent", "spend"),
+    ("rose", "rise"),
+    ("risen", "rise"),
+    ("drove", "drive"),
+    ("driven", "drive"),
+    ("bought", "buy"),
+    ("wore", "wear"),
+    ("worn", "wear"),
+    ("chose", "choose"),
+    ("chosen", "choose"),
+    ("sought", "seek"),
+    ("threw", "throw"),
+    ("thrown", "throw"),
+    ("caught", "catch"),
+    ("fought", "fight"),
+    ("taught", "teach"),
+    ("ate", "eat"),
+    ("eaten", "eat"),
+    ("fled", "flee"),
+    ("sang", "sing"),
+    ("sung", "sing"),
+    ("won", "win"),
+    ("slew", "slay"),
+    ("slain", "slay"),
+    ("bore", "bear"),
+    ("borne", "bear"),
+    ("struck", "strike"),
+    ("swore", "swear"),
+    ("sworn", "swear"),
+    ("hid", "hide"),
+    ("hidden", "hide"),
+    ("fed", "feed"),
+    ("dug", "dig"),
+    ("flew", "fly"),
+    ("flown", "fly"),
+    ("men", "man"),
+    ("women", "woman"),
+    ("children", "child"),
+    ("feet", "foot"),
+    ("teeth", "tooth"),
+    ("geese", "goose"),
+    ("mice", "mouse"),
+    ("oxen", "ox"),
+    ("people", "person"),
+    ("better", "good"),
+    ("best", "good"),
+    ("worse", "bad"),
+    ("worst", "bad"),
+    ("more", "much"),
+    ("most", "much"),
+    ("less", "little"),
+    ("least", "little"),
+    ("elder", "old"),
+    ("eldest", "old"),
+];
+
+// the ending removed and what replaces it, tried in order, ex: carried -> carry, loved -> love
+const SUFFIX_RULES: &[(&str, &str)] = &[
+    ("ies", "y"),
+    ("ves", "f"),
+    ("ves", "fe"),
+    ("es", ""),
+    ("s", ""),
+    ("ied", "y"),
+    ("ed", "e"),
+    ("ed", ""),
+    ("ying", "ie"),
+    ("ing", "e"),
+    ("ing", ""),
+    ("ily", "y"),
+    ("ally", "al"),
+    ("ly", "le"),
+    ("ly", ""),
+    ("iest", "y"),
+    ("est", "e"),
+    ("est", ""),
+    ("ier", "y"),
+    ("er", "e"),
+    ("er", ""),
+    ("men", "man"),
+    ("ness", ""),
+    ("ful", ""),
+];
+
+/**
+ * The dictionary form of an inflected English word, ex: loves -> love, soldiers -> soldier, ran -> run.
+ * Lemmas in the English dictionary come first, then words used in the Latin senses (falling -> fall),
+ * the word itself is never returned.
+ */
+pub fn lemmatize(dictionary: &Dictionary, english_word: &str) -> Option<String> {
+    let candidates = lemma_candidates(english_word);
+
+    candidates
+        .iter()
+        .find(|lemma| !dictionary.get_english_words_by_orth(lemma).is_empty())
+        .or_else(|| {
+            let sense_index = dictionary.get_sense_index();
+            candidates
+                .iter()
+                .find(|lemma| !sense_index.get(lemma).is_empty())
+        })
+        .cloned()
+}
+
+// every form the word could be inflected from, the most likely first
+fn lemma_candidates(english_word: &str) -> Vec<String> {
+    let mut candidates: Vec<String> = Vec::new();
+
+    if let Some((_, lemma)) = EXCEPTIONS.iter().find(|(form, _)| *form == english_word) {
+        candidates.push(lemma.to_string());
+    }
+
+    for (suffix, replacement) in SUFFIX_RULES.iter().copied() {
+        let stem = match english_word.strip_suffix(suffix) {
+            Some(stem) if stem.chars().count() >= 2 => stem,
+            _ => continue,
+        };
+        // "ss" is not a plural, ex: glass
+        if suffix == "s" && stem.ends_with('s') {
+            continue;
+        }
+
+        candidates.push(format!("{}{}", stem, replacement));
+
+        // running -> run, stopped -> stop
+        if replacement.is_empty() && is_doubled_consonant(stem) {
+            let mut lemma = stem.to_string();
+            lemma.pop();
+            candidates.push(lemma);
+        }
+    }
+
+    candidates.retain(|lemma| lemma != english_word);
+    candidates
+}
+
+fn is_doubled_consonant(stem: &str) -> bool {
+    let mut letters = stem.chars().rev();
+    match (letters.next(), letters.next()) {
+        (Some(last), Some(before)) => last == before && !"aeiou".contains(last),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn undoes_doubled_consonants() {
+        assert!(lemma_candidates("running").contains(&"run".to_string()));
+        assert!(lemma_candidates("stopped").contains(&"stop".to_string()));
+        assert!(is_doubled_consonant("runn"));
+        assert!(!is_doubled_consonant("seee"));
+    }
+
+    #[test]
+    fn undoes_non_ascii_doubled_letters() {
+        assert!(is_doubled_consonant("ññ"));
+        assert!(lemma_candidates("ññed").contains(&"ñ".to_string()));
+    }
+
+    #[test]
+    fn replaces_y_endings() {
+        assert_eq!(lemma_candidates("carried")[0], "carry");
+        assert_eq!(lemma_candidates("cities")[0], "city");
+    }
+
+    #[test]
+    fn tries_irregular_forms_first() {
+        assert_eq!(lemma_candidates("fell")[0], "fall");
+        assert_eq!(lemma_candidates("children")[0], "child");
+    }
+
+    #[test]
+    fn keeps_double_s() {
+        assert!(!lemma_candidates("glass").contains(&"glas".to_string()));
+        assert!(lemma_candidates("glass")
+            .iter()
+            .all(|lemma| lemma != "glass"));
+    }
+}
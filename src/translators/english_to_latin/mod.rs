@@ -1,5 +1,9 @@
+mod lemmatizer;
+mod phrase;
 mod utils;
 
+pub(crate) use self::lemmatizer::lemmatize;
+use self::phrase::find_phrase;
 use self::utils::{remove_duplicates, weigh_words};
 use crate::dictionary_structures::dictionary_keys::{
     Age, Area, Frequency, Geography, PartOfSpeech, Source,
@@ -7,28 +11,36 @@ use crate::dictionary_structures::dictionary_keys::{
 use crate::dictionary_structures::dictionary_values::{
     EnglishWordInfo, Form, LatinWordInfo, LongForm,
 };
+use crate::use_data::WordFilter;
+use crate::utils::data::Dictionary;
 use crate::utils::{convert_number_to_roman_numeral, is_all_numbers};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct EnglishTranslationInfo {
     pub word: EnglishWordInfo,
     pub translation: LatinWordInfo,
+    // the dictionary form or the phrase the input was found as, ex: loves -> love
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub lemma: Option<String>,
 }
 
 impl EnglishTranslationInfo {
     pub fn new(word: EnglishWordInfo, translation: LatinWordInfo) -> EnglishTranslationInfo {
-        EnglishTranslationInfo { word, translation }
+        EnglishTranslationInfo {
+            word,
+            translation,
+            lemma: None,
+        }
     }
 }
 
 pub fn translate_english_to_latin(
-    english_dictionary: &Vec<EnglishWordInfo>,
-    latin_dictionary: &Vec<LatinWordInfo>,
+    dictionary: &Dictionary,
     english_word: &str,
     max: usize,
     sort: bool,
+    filter: &WordFilter,
 ) -> Vec<EnglishTranslationInfo> {
     let mut output: Vec<EnglishTranslationInfo> = Vec::new();
 
@@ -63,33 +75,46 @@ pub fn translate_english_to_latin(
 
                 return vec![translation];
             }
-            Err(e) => {
-                println!("Error converting number to roman numeral: {}", e);
+            Err(_) => {
                 return output;
             }
         }
     }
 
-    let latin_words_map: HashMap<i32, &LatinWordInfo> = latin_dictionary
-        .iter()
-        .map(|word| (word.id, word))
-        .collect();
+    if english_word.contains(' ') {
+        output = translate_phrase(dictionary, english_word);
+    } else {
+        // inflected words are looked up by their dictionary form, ex: soldiers -> soldier
+        let mut english_words = dictionary.get_english_words_by_orth(english_word);
+        let mut lemma = None;
+        if english_words.is_empty() {
+            if let Some(found) = lemmatize(dictionary, english_word) {
+                english_words = dictionary.get_english_words_by_orth(&found);
+                lemma = Some(found);
+            }
+        }
 
-    for word in english_dictionary {
-        if word.orth.to_lowercase() == english_word.to_lowercase() {
+        for word in english_words {
             let mut translation =
                 EnglishTranslationInfo::new(EnglishWordInfo::new(), LatinWordInfo::new());
 
-            if let Some(latin_word) = latin_words_map.get(&word.wid) {
+            if let Some(latin_word) = dictionary.get_latin_word_by_id(word.wid) {
                 translation.translation.set_word(latin_word);
             }
 
             translation.word.set_word(word.clone());
+            translation.lemma = lemma.clone();
             output.push(translation);
         }
+
+        // words that are only in the senses, ex: gallop in "run, trot, gallop"
+        if output.is_empty() {
+            output = translate_phrase(dictionary, english_word);
+        }
     }
 
     output = remove_duplicates(output);
+    output.retain(|translation| filter.allows(&translation.translation));
 
     if sort {
         output = weigh_words(output);
@@ -102,3 +127,25 @@ pub fn translate_english_to_latin(
 
     output
 }
+
+// phrases are not in the English index, they are matched against the senses of the Latin words instead
+fn translate_phrase(dictionary: &Dictionary, phrase: &str) -> Vec<EnglishTranslationInfo> {
+    find_phrase(dictionary, phrase)
+        .into_iter()
+        .map(|phrase_match| {
+            let mut translation =
+                EnglishTranslationInfo::new(EnglishWordInfo::new(), LatinWordInfo::new());
+
+            translation.word.set_orth(&phrase_match.matched);
+            translation.word.set_wid(phrase_match.word.id);
+            translation.word.set_pos(phrase_match.word.pos);
+            translation
+                .word
+                .set_frequency_type(phrase_match.word.info.freq);
+
+            translation.translation.set_word(phrase_match.word);
+            translation.lemma = Some(phrase_match.matched);
+            translation
+        })
+        .collect()
+}
@@ -0,0 +1,91 @@
+use crate::dictionary_structures::dictionary_keys::PartOfSpeech;
+use crate::dictionary_structures::dictionary_values::LatinWordInfo;
+use crate::translators::english_to_latin::lemmatizer::lemmatize;
+use crate::utils::data::Dictionary;
+
+// words that only mark what the phrase is, ex: "to go out" is a verb, "the king" a noun
+const LEADING_WORDS: [&str; 4] = ["to", "a", "an", "the"];
+
+/**
+ * A Latin word whose senses contain the phrase, with the words of the phrase as they were matched
+ */
+pub struct PhraseMatch<'a> {
+    pub word: &'a LatinWordInfo,
+    pub matched: String,
+    // the phrase is a whole sense or a whole part of one between commas, ex: "go out" in "go out, leave"
+    pub whole_sense: bool,
+}
+
+/**
+ * Finds the Latin words with a sense containing every word of the phrase in order, ex: "to go out" -> exeo.
+ * The phrase is matched as it is written, then with every word lemmatized ("going out" -> "go out").
+ * A leading "to" only matches verbs, whole senses come first, then the most frequent words.
+ */
+pub fn find_phrase<'a>(dictionary: &'a Dictionary, phrase: &str) -> Vec<PhraseMatch<'a>> {
+    let mut words: Vec<String> = phrase.split_whitespace().map(str::to_lowercase).collect();
+    let is_verb = words.first().is_some_and(|word| word == "to") && words.len() > 1;
+    while words.len() > 1 && LEADING_WORDS.contains(&words[0].as_str()) {
+        words.remove(0);
+    }
+    if words.is_empty() {
+        return Vec::new();
+    }
+
+    let lemmas: Vec<String> = words
+        .iter()
+        .map(|word| lemmatize(dictionary, word).unwrap_or_else(|| word.to_string()))
+        .collect();
+
+    let mut matches = Vec::new();
+    for phrase_words in [&words, &lemmas] {
+        matches = dictionary
+            .latin_words
+            .iter()
+            .filter(|word| !is_verb || word.pos == PartOfSpeech::Verb)
+            .filter_map(|word| {
+                let whole_sense = sense_match(&word.senses, phrase_words)?;
+                Some(PhraseMatch {
+                    word,
+                    matched: phrase_words.join(" "),
+                    whole_sense,
+                })
+            })
+            .collect();
+        if !matches.is_empty() || words == lemmas {
+            break;
+        }
+    }
+
+    matches.sort_by_key(|phrase_match| {
+        (
+            !phrase_match.whole_sense,
+            phrase_match.word.info.freq.as_number(),
+        )
+    });
+    matches
+}
+
+// Some(true) when a part of a sense between commas or semicolons is the phrase, Some(false) when it contains it
+fn sense_match(senses: &[String], phrase_words: &[String]) -> Option<bool> {
+    let mut found = None;
+
+    for part in senses.iter().flat_map(|sense| sense.split([',', ';'])) {
+        let part_words: Vec<String> = part
+            .split(|c: char| !c.is_alphanumeric())
+            .filter(|word| !word.is_empty())
+            .map(str::to_lowercase)
+            .collect();
+
+        if part_words == phrase_words {
+            return Some(true);
+        }
+        if part_words
+            .windows(phrase_words.len())
+            .any(|window| window == phrase_words)
+        {
+            found = Some(false);
+        }
+    }
+
+    found
+}
@@ -2,7 +2,7 @@ use crate::translators::english_to_latin::EnglishTranslationInfo;
 
 pub fn weigh_words(word_list: Vec<EnglishTranslationInfo>) -> Vec<EnglishTranslationInfo> {
     let mut weighted_word_list = word_list;
-    weighted_word_list.sort_by(|a, b| b.word.true_frequency.cmp(&a.word.true_frequency));
+    weighted_word_list.sort_by_key(|word| std::cmp::Reverse(word.word.true_frequency));
     weighted_word_list
 }
 
@@ -1,8 +1,13 @@
+pub mod orthography;
 pub mod parser;
+pub mod sentence;
+pub mod suggestions;
 pub mod translator;
 pub mod tricks;
 pub mod utils;
+pub mod word_formation;
 
+use self::orthography::Orthography;
 use self::tricks::{try_syncopes, try_tricks};
 use crate::dictionary_structures::dictionary_keys::{
     Age, Area, Frequency, Geography, PartOfSpeech, Source,
@@ -10,11 +15,13 @@ use crate::dictionary_structures::dictionary_keys::{
 use crate::dictionary_structures::dictionary_values::{
     Form, Inflection, LatinWordInfo, LongForm, Stem,
 };
+use crate::error::VocabError;
+use crate::utils::data::Dictionary;
 use crate::utils::{evaluate_roman_numeral, is_roman_number};
 use serde::{Deserialize, Serialize};
 
 use self::parser::parse;
-use self::utils::split_enclitic;
+use self::utils::{enclitic_readings, split_tickon};
 
 #[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct LatinTranslationInfo {
@@ -24,6 +31,12 @@ pub struct LatinTranslationInfo {
     pub inflections: Option<Vec<Inflection>>,
 }
 
+impl Default for LatinTranslationInfo {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl LatinTranslationInfo {
     pub fn new() -> LatinTranslationInfo {
         LatinTranslationInfo {
@@ -58,18 +71,23 @@ impl LatinTranslationInfo {
         self.inflections = Some(inflections);
     }
 
-    pub fn set_tricks(&mut self, tricks: &Vec<String>) {
+    pub fn set_tricks(&mut self, tricks: &[String]) {
         self.tricks = Some(tricks.to_vec());
     }
 }
 
-pub fn translate_latin_to_english(latin_word: &str, tricks: bool) -> Vec<LatinTranslationInfo> {
-    if is_roman_number(&latin_word) {
-        match evaluate_roman_numeral(&latin_word) {
+pub fn translate_latin_to_english(
+    dictionary: &Dictionary,
+    latin_word: &str,
+    tricks: bool,
+    orthography: &Orthography,
+) -> Result<Vec<LatinTranslationInfo>, VocabError> {
+    if is_roman_number(latin_word) {
+        match evaluate_roman_numeral(latin_word) {
             Ok(number) => {
                 if number > 0 {
                     let mut translation = LatinTranslationInfo::new();
-                    translation.word.set_orth(&latin_word);
+                    translation.word.set_orth(latin_word);
                     translation
                         .word
                         .set_senses(vec![format!("Number for the Roman Numeral {}", number)]);
@@ -82,20 +100,41 @@ pub fn translate_latin_to_english(latin_word: &str, tricks: bool) -> Vec<LatinTr
                     translation.word.info.set_freq(Frequency::Common);
                     translation.word.info.set_geo(Geography::AllOrNone);
                     translation.word.info.set_source(Source::General);
-                    return vec![translation];
+                    return Ok(vec![translation]);
                 }
             }
-            Err(e) => {
-                println!("Error evaluating roman numeral: {}", e);
-                return Vec::new();
+            Err(_) => {
+                return Ok(Vec::new());
+            }
+        }
+    }
+
+    // the normalizations are reported with the tricks, ex: "'j' is spelled 'i' in the dictionary"
+    for (spelling, normalizations) in orthography.spellings(latin_word) {
+        if let Some(mut output) = translate_spelling(dictionary, &spelling, tricks)? {
+            if !normalizations.is_empty() {
+                for definition in output.iter_mut() {
+                    let mut explanations = normalizations.clone();
+                    explanations.extend(definition.tricks.take().unwrap_or_default());
+                    definition.set_tricks(&explanations);
+                }
             }
+            return Ok(output);
         }
     }
 
-    let mut output = parse(&latin_word, false);
+    Ok(Vec::new())
+}
+
+fn translate_spelling(
+    dictionary: &Dictionary,
+    latin_word: &str,
+    tricks: bool,
+) -> Result<Option<Vec<LatinTranslationInfo>>, VocabError> {
+    let mut output = parse(dictionary, latin_word, false)?;
 
     if tricks {
-        let trick_results = try_tricks(&latin_word);
+        let trick_results = try_tricks(latin_word);
 
         let mut modified_word = if trick_results.is_found() {
             trick_results.get_word()
@@ -112,17 +151,14 @@ pub fn translate_latin_to_english(latin_word: &str, tricks: bool) -> Vec<LatinTr
         }
 
         if modified_word != latin_word && modified_word != String::new() {
-            let mut new_output = parse(&modified_word, false);
-
-            if new_output.is_some() {
-                for word in new_output.as_mut().unwrap() {
+            if let Some(mut new_output) = parse(dictionary, &modified_word, false)? {
+                for word in new_output.iter_mut() {
                     word.set_tricks(&explanations);
                 }
 
-                if output.is_some() {
-                    output.as_mut().unwrap().extend(new_output.unwrap());
-                } else {
-                    output = new_output;
+                match output.as_mut() {
+                    Some(output) => output.extend(new_output),
+                    None => output = Some(new_output),
                 }
             }
         }
@@ -130,19 +166,18 @@ pub fn translate_latin_to_english(latin_word: &str, tricks: bool) -> Vec<LatinTr
 
     // most words should be found by now
 
-    // If nothing is found, try removing enclitics and try again
-    // ex: clamaverunt -> clamare
-    // doing this here instead of earlier should fix words like salve having the "ve" removed and returning wrong def
-    if output.is_none() {
-        let (word_without_ecliptics, modifiers) = split_enclitic(&latin_word);
-        output = parse(&word_without_ecliptics, false);
+    // Enclitics are removed even when the whole word was found, the word could still be a stem with an enclitic
+    // (bene is an adverb, not ben + ne, salve is not sal + ve, but virumque is virum + que).
+    // Both readings are kept, rank_latin_definitions puts the ones with fewer enclitics first.
+    let enclitic_output = enclitic_readings(dictionary, latin_word)?;
+    if !enclitic_output.is_empty() {
+        output.get_or_insert_with(Vec::new).extend(enclitic_output);
+    }
 
-        if output.is_some() {
-            for word in output.as_mut().unwrap() {
-                word.word.set_modifiers(modifiers.clone());
-            }
-        }
+    // tickons are only used with qu pronouns, ex: ecquis -> ec + quis
+    if output.is_none() {
+        output = split_tickon(dictionary, latin_word)?;
     }
 
-    output.unwrap_or(Vec::new())
+    Ok(output)
 }
@@ -0,0 +1,214 @@
+use crate::error::VocabError;
+use crate::utils::is_vowel;
+
+// words with more u's that could be consonants are only tried with all or none of them as v
+const MAX_AMBIGUOUS_U: usize = 4;
+
+/**
+ * The spelling variants that are mapped to the dictionary's spelling before a Latin word is looked up.
+ * The dictionary writes i for j, v for consonant u, ae and oe for the ligatures and c for k.
+ * j and the ligatures are always replaced, u/v and k are only tried when the word is not found as it is written.
+ */
+#[derive(Debug, Clone, Copy)]
+pub struct Orthography {
+    pub j: bool,
+    pub u_v: bool,
+    pub ligatures: bool,
+    pub k: bool,
+}
+
+impl Default for Orthography {
+    fn default() -> Orthography {
+        Orthography::new()
+    }
+}
+
+impl Orthography {
+    /**
+     * Every normalization
+     */
+    pub fn new() -> Orthography {
+        Orthography {
+            j: true,
+            u_v: true,
+            ligatures: true,
+            k: true,
+        }
+    }
+
+    /**
+     * Words are looked up exactly as they are written
+     */
+    pub fn none() -> Orthography {
+        Orthography {
+            j: false,
+            u_v: false,
+            ligatures: false,
+            k: false,
+        }
+    }
+
+    /**
+     * Reads a comma separated list of normalizations: j, uv, ligatures, k, all or none.
+     */
+    pub fn from_rules(rules: &str) -> Result<Orthography, VocabError> {
+        let mut orthography = Orthography::none();
+
+        for rule in rules.split(',').map(|rule| rule.trim().to_lowercase()) {
+            match rule.as_str() {
+                "j" => orthography.j = true,
+                "uv" | "u" | "v" => orthography.u_v = true,
+                "ligatures" | "ae" | "oe" => orthography.ligatures = true,
+                "k" => orthography.k = true,
+                "all" => orthography = Orthography::new(),
+                "none" => orthography = Orthography::none(),
+                _ => {
+                    return Err(VocabError::InvalidArgument {
+                        name: "orthography".to_string(),
+                        value: rule,
+                    })
+                }
+            }
+        }
+
+        Ok(orthography)
+    }
+
+    pub fn with_j(mut self, j: bool) -> Orthography {
+        self.j = j;
+        self
+    }
+
+    pub fn with_u_v(mut self, u_v: bool) -> Orthography {
+        self.u_v = u_v;
+        self
+    }
+
+    pub fn with_ligatures(mut self, ligatures: bool) -> Orthography {
+        self.ligatures = ligatures;
+        self
+    }
+
+    pub fn with_k(mut self, k: bool) -> Orthography {
+        self.k = k;
+        self
+    }
+
+    /**
+     * The spellings to look the word up with, in order, each with an explanation of every normalization applied.
+     * The first one only has the replacements that are always made, ex: jam -> iam, cœlum -> coelum.
+     */
+    pub fn spellings(&self, latin_word: &str) -> Vec<(String, Vec<String>)> {
+        let mut word = latin_word.to_string();
+        let mut explanations: Vec<String> = Vec::new();
+
+        if self.j && word.contains('j') {
+            word = word.replace('j', "i");
+            explanations.push("'j' is spelled 'i' in the dictionary".to_string());
+        }
+        if self.ligatures {
+            for (ligature, letters) in [('æ', "ae"), ('œ', "oe")] {
+                if word.contains(ligature) {
+                    word = word.replace(ligature, letters);
+                    explanations.push(format!(
+                        "'{}' is spelled '{}' in the dictionary",
+                        ligature, letters
+                    ));
+                }
+            }
+        }
+
+        let mut spellings = vec![(word.clone(), explanations.clone())];
+
+        let mut bases = vec![(word.clone(), explanations.clone())];
+        if self.k && word.contains('k') {
+            let mut k_explanations = explanations.clone();
+            k_explanations.push("'k' may have replaced usual 'c'".to_string());
+            bases.push((word.replace('k', "c"), k_explanations));
+        }
+
+        for (base, base_explanations) in bases {
+            let mut variants = vec![(base.clone(), base_explanations.clone())];
+            if self.u_v {
+                variants.extend(
+                    u_v_spellings(&base)
+                        .into_iter()
+                        .map(|(spelling, explanation)| {
+                            let mut explanations = base_explanations.clone();
+                            explanations.extend(explanation);
+                            (spelling, explanations)
+                        }),
+                );
+            }
+
+            for variant in variants {
+                if !spellings.iter().any(|(spelling, _)| *spelling == variant.0) {
+                    spellings.push(variant);
+                }
+            }
+        }
+
+        spellings
+    }
+}
+
+// a v before a consonant is always the vowel u, a u before a vowel can be the consonant v unless it follows q
+fn u_v_spellings(word: &str) -> Vec<(String, Vec<String>)> {
+    let chars: Vec<char> = word.chars().collect();
+    let next_is_vowel = |index: usize| chars.get(index + 1).is_some_and(|c| is_vowel(*c));
+
+    let mut explanations: Vec<String> = Vec::new();
+    let vowel_v: Vec<char> = chars
+        .iter()
+        .enumerate()
+        .map(|(index, c)| match c {
+            'v' if !next_is_vowel(index) => 'u',
+            c => *c,
+        })
+        .collect();
+    if vowel_v != chars {
+        explanations.push("'v' before a consonant is the vowel 'u'".to_string());
+    }
+
+    let ambiguous: Vec<usize> = (0..vowel_v.len())
+        .filter(|index| {
+            vowel_v[*index] == 'u'
+                && vowel_v.get(*index + 1).is_some_and(|c| is_vowel(*c))
+                && (*index == 0 || vowel_v[*index - 1] != 'q')
+        })
+        .collect();
+
+    let mut combinations: Vec<Vec<usize>> = if ambiguous.len() <= MAX_AMBIGUOUS_U {
+        (1..1usize << ambiguous.len())
+            .map(|mask| {
+                ambiguous
+                    .iter()
+                    .enumerate()
+                    .filter(|(bit, _)| mask & (1 << bit) != 0)
+                    .map(|(_, index)| *index)
+                    .collect()
+            })
+            .collect()
+    } else {
+        vec![ambiguous.clone()]
+    };
+
+    // the fewest consonants first
+    combinations.sort_by_key(|consonants| consonants.len());
+
+    let mut spellings = Vec::new();
+    if vowel_v != chars {
+        spellings.push((vowel_v.iter().collect(), explanations.clone()));
+    }
+    for consonants in combinations {
+        let mut spelling = vowel_v.clone();
+        for index in consonants {
+            spelling[index] = 'v';
+        }
+        let mut consonant_explanations = explanations.clone();
+        consonant_explanations.push("'u' before a vowel may be the consonant 'v'".to_string());
+        spellings.push((spelling.iter().collect(), consonant_explanations));
+    }
+
+    spellings
+}
@@ -1,75 +1,81 @@
 use crate::dictionary_structures::dictionary_keys::PartOfSpeech;
 use crate::dictionary_structures::dictionary_values::{Inflection, LatinWordInfo, NValue, Stem};
+use crate::error::VocabError;
 use crate::translators::latin_to_english::translator::lookup_stems;
 use crate::translators::latin_to_english::tricks::{try_medieval_tricks, TrickResult};
 use crate::translators::latin_to_english::utils::reduce;
 use crate::translators::latin_to_english::LatinTranslationInfo;
-use crate::utils::data::{get_latin_inflections, get_latin_stems, get_unique_latin_words};
+use crate::utils::data::Dictionary;
 
-pub fn parse(latin_word: &str, reduced: bool) -> Option<Vec<LatinTranslationInfo>> {
-    match find_form(latin_word, reduced) {
-        Some(form) => return Some(form),
-        None => match parse_unique_latin_words(latin_word) {
+pub fn parse(
+    dictionary: &Dictionary,
+    latin_word: &str,
+    reduced: bool,
+) -> Result<Option<Vec<LatinTranslationInfo>>, VocabError> {
+    match find_form(dictionary, latin_word, reduced)? {
+        Some(form) => Ok(Some(form)),
+        None => match parse_unique_latin_words(dictionary, latin_word) {
             Some(unique_word) => {
                 let mut translation = LatinTranslationInfo::new();
-                translation.word = unique_word;
-                return Some(vec![translation]);
+                translation.word = unique_word.clone();
+                Ok(Some(vec![translation]))
             }
-            None => None,
+            None => Ok(None),
         },
     }
 }
 
-fn parse_unique_latin_words(latin_word: &str) -> Option<LatinWordInfo> {
-    let unique_words = get_unique_latin_words();
-
-    let latin_word_lower = latin_word.to_lowercase();
-    unique_words
-        .into_iter()
-        .find(|unique_word| unique_word.orth.to_lowercase() == latin_word_lower)
+fn parse_unique_latin_words<'a>(
+    dictionary: &'a Dictionary,
+    latin_word: &str,
+) -> Option<&'a LatinWordInfo> {
+    dictionary.get_unique_latin_word(latin_word)
 }
 
-pub fn find_form(latin_word: &str, reduced: bool) -> Option<Vec<LatinTranslationInfo>> {
-    let latin_inflections: Vec<Inflection> = get_latin_inflections();
+pub fn find_form(
+    dictionary: &Dictionary,
+    latin_word: &str,
+    reduced: bool,
+) -> Result<Option<Vec<LatinTranslationInfo>>, VocabError> {
     let mut latin_word_inflections: Vec<Inflection> = Vec::new();
 
-    for inflection in latin_inflections {
-        if latin_word.ends_with(inflection.ending.as_str()) {
-            // if the longest inflection has been found, stop looking
-            if latin_word_inflections.len() > 0
-                && latin_word_inflections[0].ending.len() > inflection.ending.len()
-            {
-                break;
-            }
-            latin_word_inflections.push(inflection);
+    for inflection in dictionary.get_inflections_ending(latin_word) {
+        // if the longest inflection has been found, stop looking
+        if !latin_word_inflections.is_empty()
+            && latin_word_inflections[0].ending.len() > inflection.ending.len()
+        {
+            break;
         }
+        latin_word_inflections.push(inflection.clone());
     }
 
-    let (stems, inflections) = check_stems(latin_word, &latin_word_inflections, false);
-    let mut output = lookup_stems(stems, inflections);
+    let stems = check_stems(dictionary, latin_word, &latin_word_inflections, false)?;
+    let mut output = lookup_stems(dictionary, stems);
 
     if output.is_none() && !reduced {
-        output = reduce(latin_word);
+        output = reduce(dictionary, latin_word)?;
     }
 
     //curebantur -> currebantur (needs to work on stem or word: cureb -> curreb)
     if output.is_none() {
-        let (stems, inflections) = check_stems(latin_word, &latin_word_inflections, true);
-        output = lookup_stems(stems, inflections);
+        let stems = check_stems(dictionary, latin_word, &latin_word_inflections, true)?;
+        output = lookup_stems(dictionary, stems);
     }
 
-    output
+    Ok(output)
 }
 
+/**
+ * Finds the dictionary stems the word can be split into, each with the inflections that fit it.
+ * An inflection is kept once per stem, different entries with the same stem (in + acc, in + abl) each keep their own.
+ */
 fn check_stems(
+    dictionary: &Dictionary,
     latin_word: &str,
     latin_word_inflections: &Vec<Inflection>,
     tricks: bool,
-) -> (Vec<Stem>, Vec<Inflection>) {
-    let latin_stems = get_latin_stems();
-    let mut matched_stems: Vec<Stem> = Vec::new();
-    let mut inflections: Vec<Inflection> = Vec::new();
-    let mut found_inflection_forms: Vec<String> = Vec::new();
+) -> Result<Vec<(Stem, Vec<Inflection>)>, VocabError> {
+    let mut matched_stems: Vec<(Stem, Vec<Inflection>)> = Vec::new();
 
     for inflection in latin_word_inflections {
         //TODO: if the inflection.ending is "" and word cant take inflection endings, continue
@@ -80,80 +86,75 @@ fn check_stems(
             true => {
                 let tricked = try_medieval_tricks(word_stem);
 
-                let word_stem = match tricked {
+                match tricked {
                     TrickResult::Found(word, _) => word,
                     TrickResult::NotFound => word_stem.to_string(),
-                };
-
-                word_stem
+                }
             }
             false => word_stem.to_string(),
         };
 
-        for stem in &latin_stems {
-            if word_stem == stem.orth {
-                if inflection.pos == stem.pos
-                    || (inflection.pos == PartOfSpeech::Participle
-                        && stem.pos == PartOfSpeech::Verb)
-                    || (inflection.pos == PartOfSpeech::Verb
-                        && stem.pos == PartOfSpeech::Participle)
-                {
-                    let n_from_inflection = match &inflection.n {
-                        Some(n) => n,
-                        None => {
-                            println!("Inflection has no n value");
-                            std::process::exit(0);
-                        }
-                    };
-                    let n_from_stem = match &stem.n {
-                        Some(n) => n,
-                        None => {
-                            println!("Stem has no n value");
-                            std::process::exit(0);
-                        }
-                    };
-
-                    //TODO: Weird issue here where some words get inflections but should not (cur)
-                    if n_from_stem.len() == 1 && n_from_stem[0] != n_from_inflection[0] {
-                        continue;
+        for stem in dictionary.get_stems_by_orth(&word_stem) {
+            if inflection.pos == stem.pos
+                || (inflection.pos == PartOfSpeech::Participle && stem.pos == PartOfSpeech::Verb)
+                || (inflection.pos == PartOfSpeech::Verb && stem.pos == PartOfSpeech::Participle)
+            {
+                let n_from_inflection = match &inflection.n {
+                    Some(n) => n,
+                    None => {
+                        return Err(VocabError::MissingNValue {
+                            structure: "Inflection",
+                            orth: inflection.ending.clone(),
+                        })
+                    }
+                };
+                let n_from_stem = match &stem.n {
+                    Some(n) => n,
+                    None => {
+                        return Err(VocabError::MissingNValue {
+                            structure: "Stem",
+                            orth: stem.orth.clone(),
+                        })
                     }
+                };
 
-                    if n_from_stem.len() >= 2 {
-                        if n_from_inflection[0] != n_from_stem[0]
-                            && n_from_inflection[0] != NValue::Integer(0)
-                        {
-                            continue;
-                        }
+                //TODO: Weird issue here where some words get inflections but should not (cur)
+                if n_from_stem.len() == 1 && n_from_stem[0] != n_from_inflection[0] {
+                    continue;
+                }
 
-                        if n_from_inflection[1] != n_from_stem[1]
-                            && n_from_inflection[1] != NValue::Integer(0)
-                        {
-                            continue;
-                        }
+                if n_from_stem.len() >= 2 {
+                    if n_from_inflection[0] != n_from_stem[0]
+                        && n_from_inflection[0] != NValue::Integer(0)
+                    {
+                        continue;
                     }
 
-                    if found_inflection_forms.contains(&inflection.form.as_str()) {
+                    if n_from_inflection[1] != n_from_stem[1]
+                        && n_from_inflection[1] != NValue::Integer(0)
+                    {
                         continue;
                     }
+                }
 
-                    found_inflection_forms.push(inflection.form.as_str());
+                let matched = matched_stems.iter_mut().find(|(matched_stem, _)| {
+                    matched_stem.wid == stem.wid && matched_stem.orth == stem.orth
+                });
 
-                    for stem_inflection in &inflections {
-                        if stem_inflection.pos == inflection.pos
-                            || (stem_inflection.pos == PartOfSpeech::Participle
-                                && inflection.pos == PartOfSpeech::Verb)
-                            || (stem_inflection.pos == PartOfSpeech::Verb
-                                && inflection.pos == PartOfSpeech::Participle)
+                match matched {
+                    Some((_, inflections)) => {
+                        if !inflections
+                            .iter()
+                            .any(|found| found.form.as_str() == inflection.form.as_str())
                         {
-                            break;
+                            inflections.push(inflection.clone());
                         }
                     }
-                    matched_stems.push(stem.clone());
-                    inflections.push(inflection.clone());
+                    None => matched_stems.push((stem.clone(), vec![inflection.clone()])),
                 }
             }
         }
     }
 
-    (matched_stems, inflections)
+    Ok(matched_stems)
 }
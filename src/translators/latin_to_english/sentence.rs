@@ -0,0 +1,568 @@
+use crate::dictionary_structures::dictionary_keys::{Declension, Gender, Number, PartOfSpeech};
+use crate::dictionary_structures::dictionary_values::{Form, Inflection};
+use crate::translators::latin_to_english::LatinTranslationInfo;
+use crate::translators::{DisplayType, Translation, TranslationType};
+use serde::Serialize;
+
+// punctuation that ends a clause, agreement is not checked across it
+const CLAUSE_BREAKS: [char; 9] = ['.', ',', ';', ':', '!', '?', '(', ')', '"'];
+
+// how much each constraint between neighbouring words adds to the score of a reading
+const PREPOSITION_CASE: i32 = 6;
+const AGREEMENT: i32 = 4;
+const SUBJECT_VERB: i32 = 3;
+const SUBJECT_VERB_MISMATCH: i32 = -2;
+// the vocative and locative are rare, they should only win when something points to them
+const RARE_CASE: i32 = -1;
+
+/**
+ * A word of a text, the line and column (both starting at 1, columns in characters)
+ * point to its first letter so results can be mapped back onto the text.
+ */
+#[derive(Debug, Clone)]
+pub struct Token {
+    pub word: String,
+    pub line: usize,
+    pub column: usize,
+    pub ends_clause: bool,
+}
+
+/**
+ * Splits a text into words, anything that is not a letter or digit separates words.
+ * Enclitics stay on their word (virumque), they are split off when the word is translated.
+ * Line breaks are only whitespace, verse often breaks a line in the middle of a clause.
+ */
+pub fn tokenize(text: &str) -> Vec<Token> {
+    let mut tokens: Vec<Token> = Vec::new();
+    let mut word = String::new();
+    let (mut line, mut column) = (1, 0);
+    let mut word_column = 0;
+
+    for character in text.chars() {
+        column += 1;
+
+        if character.is_alphanumeric() {
+            if word.is_empty() {
+                word_column = column;
+            }
+            word.push(character);
+            continue;
+        }
+
+        if !word.is_empty() {
+            tokens.push(Token {
+                word: std::mem::take(&mut word),
+                line,
+                column: word_column,
+                ends_clause: false,
+            });
+        }
+
+        if CLAUSE_BREAKS.contains(&character) {
+            if let Some(token) = tokens.last_mut() {
+                token.ends_clause = true;
+            }
+        }
+
+        if character == '\n' {
+            line += 1;
+            column = 0;
+        }
+    }
+
+    if !word.is_empty() {
+        tokens.push(Token {
+            word,
+            line,
+            column: word_column,
+            ends_clause: false,
+        });
+    }
+
+    tokens
+}
+
+/**
+ * A single reading of a word: one definition with at most one inflection.
+ * The score is the score of the best reading of the whole clause that uses it.
+ */
+#[derive(Debug, Clone, Serialize)]
+pub struct Analysis {
+    pub score: i32,
+    pub definition: LatinTranslationInfo,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct TokenAnalysis {
+    pub word: String,
+    pub best: Option<Analysis>,
+    pub alternatives: Vec<Analysis>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SentenceAnalysis {
+    pub tokens: Vec<TokenAnalysis>,
+}
+
+impl TokenAnalysis {
+    pub fn display(&self, display_type: DisplayType) {
+        match display_type {
+            DisplayType::Pretty(detailed) => {
+                let best = match &self.best {
+                    Some(best) => vec![best.definition.clone()],
+                    None => Vec::new(),
+                };
+                Translation::new(self.word.to_string(), TranslationType::Latin(best))
+                    .display(DisplayType::Pretty(detailed));
+
+                // the inflection the reading was scored with, endingless ones are not in the translation
+                if let Some(best) = &self.best {
+                    println!("Reading: {}", form_line(&best.definition));
+                    println!();
+                }
+
+                if !self.alternatives.is_empty() {
+                    println!("Other readings:");
+                    for alternative in &self.alternatives {
+                        println!("  {}", reading_line(alternative));
+                    }
+                    println!();
+                }
+            }
+            DisplayType::Json => {
+                println!(
+                    "{}",
+                    serde_json::to_string_pretty(&self).unwrap_or_default()
+                );
+            }
+        }
+    }
+}
+
+impl SentenceAnalysis {
+    pub fn display(&self, display_type: DisplayType) {
+        match display_type {
+            DisplayType::Pretty(detailed) => {
+                for token in &self.tokens {
+                    token.display(DisplayType::Pretty(detailed));
+                }
+            }
+            DisplayType::Json => {
+                println!(
+                    "{}",
+                    serde_json::to_string_pretty(&self).unwrap_or_default()
+                );
+            }
+        }
+    }
+}
+
+// ex: puell.am | accusative singular feminine | girl (12)
+fn reading_line(analysis: &Analysis) -> String {
+    let definition = &analysis.definition;
+
+    format!(
+        "{} | {} ({})",
+        form_line(definition),
+        definition
+            .word
+            .senses
+            .first()
+            .map(String::as_str)
+            .unwrap_or(""),
+        analysis.score
+    )
+}
+
+// ex: puell.am | accusative singular feminine
+fn form_line(definition: &LatinTranslationInfo) -> String {
+    let inflection = definition
+        .inflections
+        .as_ref()
+        .and_then(|inflections| inflections.first());
+
+    let (ending, form) = match inflection {
+        Some(inflection) => (
+            inflection.ending.as_str(),
+            match &inflection.form {
+                Form::StrForm(form) => form.to_string(),
+                Form::LongForm(form) => form.as_clean_str(),
+            },
+        ),
+        None => ("", definition.word.pos.as_str().to_string()),
+    };
+
+    format!("{}.{} | {}", definition.stem.orth, ending, form)
+}
+
+/**
+ * What a reading is, as far as the agreement constraints are concerned.
+ * Values that are unknown are None and never conflict.
+ */
+struct Reading {
+    definition: usize,
+    inflection: Option<usize>,
+    pos: PartOfSpeech,
+    case: Option<Declension>,
+    number: Option<Number>,
+    gender: Option<Gender>,
+    person: Option<u8>,
+    governs: Option<Declension>,
+    frequency: i32,
+}
+
+impl Reading {
+    fn new(
+        definition: &LatinTranslationInfo,
+        definition_index: usize,
+        inflection: Option<(usize, &Inflection)>,
+    ) -> Reading {
+        let mut reading = Reading {
+            definition: definition_index,
+            inflection: inflection.map(|(index, _)| index),
+            pos: definition.word.pos,
+            case: None,
+            number: None,
+            gender: None,
+            person: None,
+            governs: None,
+            frequency: definition.word.info.freq.as_number() as i32,
+        };
+
+        // the case a preposition takes is the first value of its form, ex: ACC 1 0
+        // prepositions that take two cases have an inflection for each
+        if definition.word.pos == PartOfSpeech::Preposition {
+            let form = match inflection {
+                Some((_, inflection)) => &inflection.form,
+                None => &definition.word.form,
+            };
+            if let Form::StrForm(form) = form {
+                let case = form.split_whitespace().next().unwrap_or("");
+                reading.governs = Some(Declension::dict_key_to_declension(case))
+                    .filter(|case| *case != Declension::Unknown);
+            }
+        }
+
+        if let Some((_, inflection)) = inflection {
+            reading.pos = inflection.pos;
+            if let Form::LongForm(form) = &inflection.form {
+                reading.case = form.declension.filter(|case| *case != Declension::Unknown);
+                reading.number = form.number.filter(|number| *number != Number::Unknown);
+                reading.gender = form.gender.filter(|gender| *gender != Gender::Unknown);
+                // only finite verbs have a person, ex: 3rd person
+                reading.person = form
+                    .person
+                    .as_ref()
+                    .and_then(|person| person.chars().next())
+                    .and_then(|person| person.to_digit(10))
+                    .map(|person| person as u8);
+            }
+        }
+
+        // noun inflections are mostly for any or common gender, the noun itself has one
+        if reading.pos == PartOfSpeech::Noun {
+            if let Form::LongForm(form) = &definition.word.form {
+                if let Some(gender @ (Gender::Masculine | Gender::Feminine | Gender::Neuter)) =
+                    form.gender
+                {
+                    reading.gender = Some(gender);
+                }
+            }
+        }
+
+        reading
+    }
+
+    fn is_declined(&self) -> bool {
+        self.case.is_some()
+    }
+
+    fn is_modifier(&self) -> bool {
+        matches!(
+            self.pos,
+            PartOfSpeech::Adjective
+                | PartOfSpeech::Participle
+                | PartOfSpeech::Pronoun
+                | PartOfSpeech::Numeral
+        )
+    }
+
+    fn is_finite_verb(&self) -> bool {
+        self.pos == PartOfSpeech::Verb && self.person.is_some_and(|person| person > 0)
+    }
+
+    // common fits masculine and feminine
+    fn gender_agrees(&self, other: &Reading) -> bool {
+        match (self.gender, other.gender) {
+            (Some(Gender::Common), Some(Gender::Neuter))
+            | (Some(Gender::Neuter), Some(Gender::Common)) => false,
+            (Some(Gender::Common), _) | (_, Some(Gender::Common)) => true,
+            (Some(gender), Some(other_gender)) => gender == other_gender,
+            _ => true,
+        }
+    }
+
+    fn number_agrees(&self, other: &Reading) -> bool {
+        match (self.number, other.number) {
+            (Some(number), Some(other_number)) => number == other_number,
+            _ => true,
+        }
+    }
+
+    // common words and cases are slightly preferred when nothing else decides
+    fn score(&self) -> i32 {
+        match self.case {
+            Some(Declension::Vocative | Declension::Locative) => RARE_CASE - self.frequency,
+            _ => -self.frequency,
+        }
+    }
+}
+
+/**
+ * The score of two neighbouring readings, left comes before right in the sentence
+ */
+fn pair_score(left: &Reading, right: &Reading) -> i32 {
+    let mut score = 0;
+
+    // in urbem, not in urbis
+    if let Some(governs) = left.governs {
+        if let Some(case) = right.case {
+            score += if case == governs {
+                PREPOSITION_CASE
+            } else {
+                -PREPOSITION_CASE
+            };
+        }
+    }
+
+    // bonum virum, virum bonum
+    if left.is_declined()
+        && right.is_declined()
+        && (left.is_modifier() || right.is_modifier())
+        && left.case == right.case
+        && left.number_agrees(right)
+        && left.gender_agrees(right)
+    {
+        score += AGREEMENT;
+    }
+
+    // puella amat, amant puellae
+    let subject_and_verb = if left.is_finite_verb() {
+        Some((right, left))
+    } else if right.is_finite_verb() {
+        Some((left, right))
+    } else {
+        None
+    };
+    if let Some((subject, verb)) = subject_and_verb {
+        if subject.case == Some(Declension::Nominative) {
+            score += if subject.number_agrees(verb) && verb.person == Some(3) {
+                SUBJECT_VERB
+            } else {
+                SUBJECT_VERB_MISMATCH
+            };
+        }
+    }
+
+    score
+}
+
+/**
+ * Every definition of a word is split into one reading per inflection,
+ * words that are not inflected (conjunctions, adverbs, ...) have one reading per definition.
+ */
+fn readings_for_word(definitions: &[LatinTranslationInfo]) -> Vec<Reading> {
+    let mut readings = Vec::new();
+
+    for (definition_index, definition) in definitions.iter().enumerate() {
+        let inflections = match &definition.inflections {
+            Some(inflections)
+                if !inflections.is_empty()
+                    && !matches!(
+                        definition.word.pos,
+                        PartOfSpeech::Conjunction
+                            | PartOfSpeech::Adverb
+                            | PartOfSpeech::Interjection
+                    ) =>
+            {
+                inflections
+            }
+            _ => {
+                readings.push(Reading::new(definition, definition_index, None));
+                continue;
+            }
+        };
+
+        for (inflection_index, inflection) in inflections.iter().enumerate() {
+            readings.push(Reading::new(
+                definition,
+                definition_index,
+                Some((inflection_index, inflection)),
+            ));
+        }
+    }
+
+    readings
+}
+
+/**
+ * Picks the most likely reading of every word of a sentence.
+ * `definitions` are the post processed translations of each token, in the same order.
+ *
+ * Only neighbouring words constrain each other, so every clause is scored with the Viterbi algorithm.
+ * The best reading is on the best scoring path through the clause,
+ * the alternatives are ranked by the best path they are a part of.
+ */
+pub fn analyze_sentence(
+    tokens: &[Token],
+    definitions: Vec<Vec<LatinTranslationInfo>>,
+) -> SentenceAnalysis {
+    let readings: Vec<Vec<Reading>> = definitions
+        .iter()
+        .map(|definitions| readings_for_word(definitions))
+        .collect();
+
+    let mut scores: Vec<Vec<i32>> = readings.iter().map(|r| vec![0; r.len()]).collect();
+    let mut best: Vec<Option<usize>> = vec![None; readings.len()];
+
+    let mut start = 0;
+    while start < readings.len() {
+        let mut end = start;
+        while end + 1 < readings.len()
+            && !tokens[end].ends_clause
+            && !readings[end].is_empty()
+            && !readings[end + 1].is_empty()
+        {
+            end += 1;
+        }
+
+        score_clause(
+            &readings[start..=end],
+            &mut scores[start..=end],
+            &mut best[start..=end],
+        );
+        start = end + 1;
+    }
+
+    let tokens = tokens
+        .iter()
+        .zip(definitions)
+        .enumerate()
+        .map(|(index, (token, definitions))| {
+            let mut analyses: Vec<(usize, Analysis)> = readings[index]
+                .iter()
+                .enumerate()
+                .map(|(reading_index, reading)| {
+                    let mut definition = definitions[reading.definition].clone();
+                    if let Some(inflection_index) = reading.inflection {
+                        definition.inflections = definition
+                            .inflections
+                            .map(|inflections| vec![inflections[inflection_index].clone()]);
+                    }
+
+                    (
+                        reading_index,
+                        Analysis {
+                            score: scores[index][reading_index],
+                            definition,
+                        },
+                    )
+                })
+                .collect();
+            analyses.sort_by_key(|(_, analysis)| std::cmp::Reverse(analysis.score));
+
+            let best_analysis = match best[index] {
+                Some(best_index) => analyses
+                    .iter()
+                    .position(|(reading_index, _)| *reading_index == best_index)
+                    .map(|position| analyses.remove(position).1),
+                None => None,
+            };
+
+            TokenAnalysis {
+                word: token.word.to_string(),
+                best: best_analysis,
+                alternatives: analyses.into_iter().map(|(_, analysis)| analysis).collect(),
+            }
+        })
+        .collect();
+
+    SentenceAnalysis { tokens }
+}
+
+fn score_clause(readings: &[Vec<Reading>], scores: &mut [Vec<i32>], best: &mut [Option<usize>]) {
+    if readings.is_empty() || readings[0].is_empty() {
+        return;
+    }
+
+    let length = readings.len();
+    let mut forward: Vec<Vec<i32>> = Vec::with_capacity(length);
+    let mut previous_best: Vec<Vec<usize>> = Vec::with_capacity(length);
+
+    forward.push(readings[0].iter().map(Reading::score).collect());
+    previous_best.push(vec![0; readings[0].len()]);
+
+    for index in 1..length {
+        let mut word_scores = Vec::with_capacity(readings[index].len());
+        let mut word_previous = Vec::with_capacity(readings[index].len());
+
+        for reading in &readings[index] {
+            let (previous, score) = readings[index - 1]
+                .iter()
+                .enumerate()
+                .map(|(previous, previous_reading)| {
+                    (
+                        previous,
+                        forward[index - 1][previous] + pair_score(previous_reading, reading),
+                    )
+                })
+                .max_by_key(|(previous, score)| (*score, std::cmp::Reverse(*previous)))
+                .unwrap_or((0, 0));
+
+            word_scores.push(score + reading.score());
+            word_previous.push(previous);
+        }
+
+        forward.push(word_scores);
+        previous_best.push(word_previous);
+    }
+
+    let mut backward: Vec<Vec<i32>> = vec![Vec::new(); length];
+    backward[length - 1] = vec![0; readings[length - 1].len()];
+    for index in (0..length - 1).rev() {
+        backward[index] = readings[index]
+            .iter()
+            .map(|reading| {
+                readings[index + 1]
+                    .iter()
+                    .enumerate()
+                    .map(|(next, next_reading)| {
+                        pair_score(reading, next_reading)
+                            + next_reading.score()
+                            + backward[index + 1][next]
+                    })
+                    .max()
+                    .unwrap_or(0)
+            })
+            .collect();
+    }
+
+    for index in 0..length {
+        scores[index] = forward[index]
+            .iter()
+            .zip(&backward[index])
+            .map(|(forward, backward)| forward + backward)
+            .collect();
+    }
+
+    let mut current = forward[length - 1]
+        .iter()
+        .enumerate()
+        .max_by_key(|(reading, score)| (**score, std::cmp::Reverse(*reading)))
+        .map(|(reading, _)| reading)
+        .unwrap_or(0);
+    for index in (0..length).rev() {
+        best[index] = Some(current);
+        current = previous_best[index][current];
+    }
+}
@@ -0,0 +1,438 @@
+use crate::error::VocabError;
+use crate::translators::latin_to_english::parser::parse;
+use crate::translators::latin_to_english::LatinTranslationInfo;
+use crate::utils::data::Dictionary;
+use crate::utils::is_vowel;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+// the cost of adding, removing or replacing any letter
+const EDIT_COST: u32 = 10;
+// letters that are written for each other in manuscripts and editions, ex: silva / sylva, caelum / celum
+const SPELLING_COST: u32 = 3;
+// letters OCR often reads for each other, ex: the long s read as f
+const OCR_COST: u32 = 5;
+// the most suggested forms run through the parser, every form is parsed so this keeps unknown words fast
+const MAX_PARSED: usize = 40;
+
+const SPELLING_LETTERS: [(char, char); 4] = [('i', 'y'), ('c', 'k'), ('u', 'v'), ('i', 'j')];
+const OCR_LETTERS: [(char, char); 5] = [('f', 's'), ('c', 'e'), ('l', 'i'), ('n', 'u'), ('t', 'f')];
+const SPELLING_GROUPS: [(&str, &str); 2] = [("ae", "e"), ("oe", "e")];
+const OCR_GROUPS: [(&str, &str); 4] = [("rn", "m"), ("cl", "d"), ("ii", "u"), ("in", "m")];
+// every edit replaces up to 2 letters of the word with up to 2 letters of the target
+const EDIT_SIZES: [(usize, usize); 6] = [(1, 1), (1, 0), (0, 1), (2, 2), (2, 1), (1, 2)];
+
+/**
+ * A dictionary form close to a word that was not found, with the edits from the word to it,
+ * ex: puelam -> puellam, "'l' -> 'll'".
+ */
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Suggestion {
+    pub word: String,
+    pub edits: Vec<String>,
+    pub definitions: Vec<LatinTranslationInfo>,
+}
+
+/**
+ * Finds the dictionary forms closest to a Latin word, closest first.
+ * The stem left after each ending is compared to every dictionary stem, the rest after each dictionary stem
+ * to every ending and the whole word to the unique words,
+ * common spelling variants (e/ae, i/y, c/k, single and double consonants) and OCR misreadings cost less than other edits.
+ * Dictionary stems are only compared when they start like the word, see `starts_alike`.
+ * Only forms the parser finds are suggested.
+ */
+pub fn suggest(
+    dictionary: &Dictionary,
+    latin_word: &str,
+    count: usize,
+) -> Result<Vec<Suggestion>, VocabError> {
+    if count == 0 || latin_word.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    // the form and the cost and edits of its closest spelling
+    let mut candidates: HashMap<String, (u32, Vec<String>)> = HashMap::new();
+    let mut add_candidate = |form: String, cost: u32, edits: Vec<String>| {
+        let closer = candidates
+            .get(&form)
+            .is_none_or(|(found_cost, _)| cost < *found_cost);
+        if closer && form != latin_word {
+            candidates.insert(form, (cost, edits));
+        }
+    };
+
+    let mut word_stems: Vec<(&str, &str)> = dictionary
+        .get_inflections_ending(latin_word)
+        .iter()
+        .map(|inflection| latin_word.split_at(latin_word.len() - inflection.ending.len()))
+        .filter(|(stem, _)| !stem.is_empty())
+        .collect();
+    word_stems.sort_unstable();
+    word_stems.dedup();
+
+    for (word_stem, ending) in word_stems {
+        let max_cost = max_cost(word_stem);
+        for stem in dictionary.get_stem_orths() {
+            if !starts_alike(word_stem, stem) {
+                continue;
+            }
+            if let Some((cost, edits)) = distance(word_stem, stem, max_cost) {
+                add_candidate(format!("{}{}", stem, ending), cost, edits);
+            }
+        }
+    }
+
+    // a misread ending after a stem that is spelled right, ex: puellarurn -> puellarum
+    for (split_at, _) in latin_word.char_indices().skip(1) {
+        let (word_stem, word_ending) = latin_word.split_at(split_at);
+        if dictionary.get_stems_by_orth(word_stem).is_empty() {
+            continue;
+        }
+        for ending in dictionary.get_endings() {
+            if let Some((cost, edits)) = distance(word_ending, ending, EDIT_COST) {
+                add_candidate(format!("{}{}", word_stem, ending), cost, edits);
+            }
+        }
+    }
+
+    let max_cost = max_cost(latin_word);
+    for unique_word in &dictionary.unique_latin_words {
+        let orth = unique_word.orth.to_lowercase();
+        if let Some((cost, edits)) = distance(latin_word, &orth, max_cost) {
+            add_candidate(orth, cost, edits);
+        }
+    }
+
+    let mut candidates: Vec<(String, u32, Vec<String>)> = candidates
+        .into_iter()
+        .map(|(form, (cost, edits))| (form, cost, edits))
+        .collect();
+    candidates.sort_by(|a, b| (a.1, &a.0).cmp(&(b.1, &b.0)));
+
+    // every form as close as the last one kept is parsed, so the most frequent of them can be picked
+    let mut suggestions: Vec<(u32, Suggestion)> = Vec::new();
+    for (form, cost, edits) in candidates.into_iter().take(MAX_PARSED) {
+        if suggestions.len() >= count && suggestions.last().is_some_and(|(last, _)| *last < cost) {
+            break;
+        }
+        if let Some(definitions) = parse(dictionary, &form, true)? {
+            suggestions.push((
+                cost,
+                Suggestion {
+                    word: form,
+                    edits,
+                    definitions,
+                },
+            ));
+        }
+    }
+
+    suggestions.sort_by_key(|(cost, suggestion)| {
+        (
+            *cost,
+            suggestion
+                .definitions
+                .iter()
+                .map(|definition| definition.word.info.freq.as_number())
+                .min(),
+        )
+    });
+    suggestions.truncate(count);
+
+    Ok(suggestions
+        .into_iter()
+        .map(|(_, suggestion)| suggestion)
+        .collect())
+}
+
+/**
+ * If the first letters match, are a spelling or OCR variant, or one was added or removed at the start, ex: puela and puell, uella and puell.
+ * Keeps the stem search fast on the whole dictionary, a misspelled first letter is rare and not suggested.
+ */
+fn starts_alike(word: &str, stem: &str) -> bool {
+    let mut word_letters = word.chars();
+    let mut stem_letters = stem.chars();
+    let (word_first, stem_first) = match (word_letters.next(), stem_letters.next()) {
+        (Some(word_first), Some(stem_first)) => (word_first, stem_first),
+        _ => return true,
+    };
+
+    word_first == stem_first
+        || is_pair(&SPELLING_LETTERS, word_first, stem_first)
+        || is_pair(&OCR_LETTERS, word_first, stem_first)
+        || word_letters.next() == Some(stem_first)
+        || stem_letters.next() == Some(word_first)
+        || SPELLING_GROUPS.iter().chain(&OCR_GROUPS).any(|(a, b)| {
+            (word.starts_with(a) && stem.starts_with(b))
+                || (word.starts_with(b) && stem.starts_with(a))
+        })
+}
+
+// one edit for short words, two for longer ones
+fn max_cost(word: &str) -> u32 {
+    match word.chars().count() {
+        0..=4 => EDIT_COST,
+        _ => 2 * EDIT_COST,
+    }
+}
+
+/**
+ * The weighted edit distance from the word to the dictionary spelling and the edits made, None if it is over max_cost.
+ */
+fn distance(word: &str, target: &str, max_cost: u32) -> Option<(u32, Vec<String>)> {
+    let word: Vec<char> = word.chars().collect();
+    let target: Vec<char> = target.chars().collect();
+    if word.len().abs_diff(target.len()) * SPELLING_COST as usize > max_cost as usize {
+        return None;
+    }
+    // almost every stem is too far from the word, that is found without the whole table the edits are traced in
+    if !within_cost(&word, &target, max_cost) {
+        return None;
+    }
+    let costs = edit_table(&word, &target, max_cost, word.len() + 1)?;
+
+    let width = target.len() + 1;
+    let cost = costs[word.len() * width + target.len()].0;
+    let mut edits = Vec::new();
+    let (mut i, mut j) = (word.len(), target.len());
+    while i > 0 || j > 0 {
+        let (_, from_i, from_j) = costs[i * width + j];
+        if let Some(edit) = describe_edit(&word, from_i, i, &target, from_j, j) {
+            edits.push(edit);
+        }
+        (i, j) = (from_i, from_j);
+    }
+    edits.reverse();
+
+    Some((cost, edits))
+}
+
+/**
+ * If the weighted edit distance from the word to the target is at most max_cost.
+ * Edits reach back two rows, so only the last three rows of costs are kept.
+ */
+fn within_cost(word: &[char], target: &[char], max_cost: u32) -> bool {
+    edit_table(word, target, max_cost, 3).is_some()
+}
+
+/**
+ * The cheapest way to spell target[..j] from word[..i] with the cell it came from, for the last `rows` rows,
+ * None if the whole target costs more than max_cost.
+ * Cell j of row i is at (i % rows) * (target.len() + 1) + j, keeping every row lets the edits be traced back.
+ * It stops once two rows in a row are over max_cost, later rows come from the last two so they cost at least as much.
+ * Every edit that changes the length costs at least SPELLING_COST, so cells too far off the diagonals are skipped.
+ */
+fn edit_table(
+    word: &[char],
+    target: &[char],
+    max_cost: u32,
+    rows: usize,
+) -> Option<Vec<(u32, usize, usize)>> {
+    let width = target.len() + 1;
+    let mut costs = vec![(u32::MAX, 0, 0); rows * width];
+
+    let mut previous_row_min = u32::MAX;
+    for i in 0..=word.len() {
+        let mut row_min = u32::MAX;
+        for j in 0..=target.len() {
+            let length_changes = i.abs_diff(j) + (word.len() - i).abs_diff(target.len() - j);
+            if length_changes * SPELLING_COST as usize > max_cost as usize {
+                costs[(i % rows) * width + j] = (u32::MAX, 0, 0);
+                continue;
+            }
+
+            let mut best = if i == 0 && j == 0 {
+                (0, 0, 0)
+            } else {
+                (u32::MAX, 0, 0)
+            };
+            for (from_i, from_j) in EDIT_SIZES {
+                if from_i > i || from_j > j {
+                    continue;
+                }
+                let (previous, _, _) = costs[((i - from_i) % rows) * width + j - from_j];
+                // a cell over max_cost can't lead to a spelling within it
+                if previous > max_cost {
+                    continue;
+                }
+                if let Some(cost) = edit_cost(word, i - from_i, i, target, j - from_j, j) {
+                    if previous + cost < best.0 {
+                        best = (previous + cost, i - from_i, j - from_j);
+                    }
+                }
+            }
+
+            costs[(i % rows) * width + j] = best;
+            row_min = row_min.min(best.0);
+        }
+
+        if row_min > max_cost && previous_row_min > max_cost {
+            return None;
+        }
+        previous_row_min = row_min;
+    }
+
+    if costs[(word.len() % rows) * width + target.len()].0 > max_cost {
+        return None;
+    }
+    Some(costs)
+}
+
+fn edit_cost(
+    word: &[char],
+    word_start: usize,
+    word_end: usize,
+    target: &[char],
+    target_start: usize,
+    target_end: usize,
+) -> Option<u32> {
+    let from = &word[word_start..word_end];
+    let to = &target[target_start..target_end];
+
+    match (from, to) {
+        ([a], [b]) if a == b => Some(0),
+        ([a], [b]) if is_pair(&SPELLING_LETTERS, *a, *b) => Some(SPELLING_COST),
+        ([a], [b]) if is_pair(&OCR_LETTERS, *a, *b) => Some(OCR_COST),
+        ([_], [_]) => Some(EDIT_COST),
+        ([a], []) if is_doubled(word, word_start, *a) => Some(SPELLING_COST),
+        ([_], []) => Some(EDIT_COST),
+        ([], [b]) if is_doubled(target, target_start, *b) => Some(SPELLING_COST),
+        ([], [_]) => Some(EDIT_COST),
+        ([a, b], [c, d]) if a == d && b == c && a != b => Some(EDIT_COST),
+        // the groups are 2 letters for 1
+        ([_, _], [_, _]) => None,
+        _ => {
+            if is_group(&SPELLING_GROUPS, from, to) {
+                Some(SPELLING_COST)
+            } else if is_group(&OCR_GROUPS, from, to) {
+                Some(OCR_COST)
+            } else {
+                None
+            }
+        }
+    }
+}
+
+// ex: "'y' -> 'i'", "'l' -> 'll'", "'x' removed"
+fn describe_edit(
+    word: &[char],
+    word_start: usize,
+    word_end: usize,
+    target: &[char],
+    target_start: usize,
+    target_end: usize,
+) -> Option<String> {
+    let from: String = word[word_start..word_end].iter().collect();
+    let to: String = target[target_start..target_end].iter().collect();
+
+    if from == to {
+        return None;
+    }
+    Some(match (from.is_empty(), to.is_empty()) {
+        (false, true) if is_doubled(word, word_start, word[word_start]) => {
+            format!("'{}{}' -> '{}'", from, from, from)
+        }
+        (false, true) => format!("'{}' removed", from),
+        (true, false) if is_doubled(target, target_start, target[target_start]) => {
+            format!("'{}' -> '{}{}'", to, to, to)
+        }
+        (true, false) => format!("'{}' added", to),
+        _ => format!("'{}' -> '{}'", from, to),
+    })
+}
+
+// a consonant that repeats the letter before it
+fn is_doubled(letters: &[char], index: usize, letter: char) -> bool {
+    !is_vowel(letter) && index > 0 && letters[index - 1] == letter
+}
+
+fn is_pair(pairs: &[(char, char)], a: char, b: char) -> bool {
+    pairs.contains(&(a, b)) || pairs.contains(&(b, a))
+}
+
+// compared letter by letter, this runs for every cell of every distance
+fn is_group(groups: &[(&str, &str)], a: &[char], b: &[char]) -> bool {
+    let spells = |letters: &[char], group: &str| letters.iter().copied().eq(group.chars());
+    groups.iter().any(|(first, second)| {
+        (spells(a, first) && spells(b, second)) || (spells(a, second) && spells(b, first))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn letters(word: &str) -> Vec<char> {
+        word.chars().collect()
+    }
+
+    #[test]
+    fn weighs_substitutions() {
+        assert_eq!(
+            distance("puelxa", "puella", 2 * EDIT_COST),
+            Some((EDIT_COST, vec!["'x' -> 'l'".to_string()]))
+        );
+        assert_eq!(
+            distance("sylva", "silva", 2 * EDIT_COST),
+            Some((SPELLING_COST, vec!["'y' -> 'i'".to_string()]))
+        );
+        assert_eq!(
+            distance("fol", "sol", EDIT_COST),
+            Some((OCR_COST, vec!["'f' -> 's'".to_string()]))
+        );
+        assert_eq!(
+            distance("celum", "caelum", EDIT_COST),
+            Some((SPELLING_COST, vec!["'e' -> 'ae'".to_string()]))
+        );
+    }
+
+    #[test]
+    fn weighs_transpositions() {
+        assert_eq!(
+            distance("pulela", "puella", 2 * EDIT_COST),
+            Some((EDIT_COST, vec!["'le' -> 'el'".to_string()]))
+        );
+    }
+
+    #[test]
+    fn weighs_insertions() {
+        assert_eq!(
+            distance("puela", "puella", 2 * EDIT_COST),
+            Some((SPELLING_COST, vec!["'l' -> 'll'".to_string()]))
+        );
+        assert_eq!(
+            distance("rosa", "rosas", EDIT_COST),
+            Some((EDIT_COST, vec!["'s' added".to_string()]))
+        );
+        assert_eq!(
+            distance("rosas", "rosa", EDIT_COST),
+            Some((EDIT_COST, vec!["'s' removed".to_string()]))
+        );
+    }
+
+    #[test]
+    fn stops_at_the_cutoff() {
+        assert!(within_cost(
+            &letters("puelxa"),
+            &letters("puella"),
+            EDIT_COST
+        ));
+        assert!(!within_cost(
+            &letters("puelxa"),
+            &letters("puella"),
+            EDIT_COST - 1
+        ));
+        assert!(!within_cost(
+            &letters("puxlxa"),
+            &letters("puella"),
+            EDIT_COST
+        ));
+        assert!(!within_cost(
+            &letters("puella"),
+            &letters("puer"),
+            2 * EDIT_COST
+        ));
+        assert!(within_cost(&letters("puella"), &letters("puella"), 0));
+        assert_eq!(distance("puxlxa", "puella", EDIT_COST), None);
+    }
+}
@@ -1,88 +1,80 @@
 use crate::dictionary_structures::dictionary_keys::PartOfSpeech;
-use crate::dictionary_structures::dictionary_values::{Inflection, LatinWordInfo, Part, Stem};
+use crate::dictionary_structures::dictionary_values::{Inflection, Part, Stem};
 use crate::translators::latin_to_english::utils::add_stem_to_word;
 use crate::translators::latin_to_english::LatinTranslationInfo;
-use crate::utils::data::get_latin_dictionary;
-use std::collections::HashMap;
+use crate::utils::data::Dictionary;
 
+/**
+ * Builds a definition for every dictionary entry of the matched stems, with the inflections that fit its stems.
+ * Stems of the same entry (am, amav, amat) are merged into one definition.
+ */
 pub fn lookup_stems(
-    stems: Vec<Stem>,
-    inflections: Vec<Inflection>,
+    dictionary: &Dictionary,
+    stems: Vec<(Stem, Vec<Inflection>)>,
 ) -> Option<Vec<LatinTranslationInfo>> {
-    let latin_dictionary = get_latin_dictionary();
     let mut output: Vec<LatinTranslationInfo> = Vec::new();
 
-    let latin_words_map: HashMap<i32, &LatinWordInfo> = latin_dictionary
-        .iter()
-        .map(|word| (word.id, word))
-        .collect();
-
-    for stem in stems {
-        let dict_word = latin_words_map.get(&stem.wid);
+    for (stem, inflections) in stems {
+        let dict_word = dictionary.get_latin_word_by_id(stem.wid);
 
         if let Some(latin_word) = dict_word {
-            let word_is_in_output = output
-                .iter()
-                .any(|x| x.word.id == latin_word.id || x.word.orth == latin_word.orth);
-
-            // if the word is already in the output add the stem to it
-            if word_is_in_output {
-                let matching_word = output
-                    .iter_mut()
-                    .find(|x| x.word.id == latin_word.id || x.word.orth == latin_word.orth);
-
-                if let Some(word) = matching_word {
-                    add_stem_to_word(stem, Some(word));
-                }
+            let new_inflections: Vec<Inflection> = if latin_word.pos == PartOfSpeech::Verb
+                || latin_word.pos == PartOfSpeech::Participle
+            {
+                // only the fourth principle part takes participle endings
+                let is_fourth_part = latin_word
+                    .get_part(Part::Fourth)
+                    .is_none_or(|fourth_part| fourth_part == stem.orth);
+                inflections
+                    .into_iter()
+                    .filter(|inflection| match is_fourth_part {
+                        true => inflection.pos != PartOfSpeech::Verb,
+                        false => inflection.pos != PartOfSpeech::Participle,
+                    })
+                    .collect()
             } else {
-                let new_inflections: Vec<Inflection>;
+                inflections
+            };
 
-                if latin_word.pos == PartOfSpeech::Verb
-                    || latin_word.pos == PartOfSpeech::Participle
-                {
-                    let fourth_part = latin_word.get_part(Part::Fourth);
-                    if fourth_part.is_some() && fourth_part.unwrap() != stem.orth {
-                        new_inflections = inflections
-                            .iter()
-                            .filter(|inflection| inflection.pos != PartOfSpeech::Participle)
-                            .cloned()
-                            .collect();
-                    } else {
-                        new_inflections = inflections
-                            .iter()
-                            .filter(|inflection| inflection.pos != PartOfSpeech::Verb)
-                            .cloned()
-                            .collect();
+            // if the word is already in the output add the stem and its inflections to it
+            if let Some(word) = output.iter_mut().find(|x| x.word.id == latin_word.id) {
+                let word_inflections = word.inflections.get_or_insert_with(Vec::new);
+                for inflection in new_inflections {
+                    if !word_inflections
+                        .iter()
+                        .any(|found| found.form.as_str() == inflection.form.as_str())
+                    {
+                        word_inflections.push(inflection);
                     }
-                } else {
-                    new_inflections = inflections.clone();
                 }
+                add_stem_to_word(stem, Some(word));
+                continue;
+            }
 
-                let mut new_word = LatinTranslationInfo::new();
-
-                let next_word = latin_words_map.get(&(latin_word.id + 1));
-
-                if next_word.is_some() {
-                    let next_senses = next_word.unwrap().senses.clone();
+            let mut new_word = LatinTranslationInfo::new();
 
-                    // senses starting with | also apply to word before
-                    if next_senses.len() > 0 && next_senses[0].starts_with('|') {
-                        new_word.word.set_extension_senses(next_senses);
-                    }
+            // senses starting with | also apply to word before
+            if let Some(next_word) = dictionary.get_latin_word_by_id(latin_word.id + 1) {
+                if next_word
+                    .senses
+                    .first()
+                    .is_some_and(|sense| sense.starts_with('|'))
+                {
+                    new_word.word.set_extension_senses(next_word.senses.clone());
                 }
+            }
 
-                new_word.word.set_word(&latin_word);
-                new_word.stem = stem;
-                new_word.inflections = Some(new_inflections);
+            new_word.word.set_word(latin_word);
+            new_word.stem = stem;
+            new_word.inflections = Some(new_inflections);
 
-                output.push(new_word);
-            }
+            output.push(new_word);
         }
     }
 
-    if output.len() > 0 {
-        Some(output)
-    } else {
+    if output.is_empty() {
         None
+    } else {
+        Some(output)
     }
 }
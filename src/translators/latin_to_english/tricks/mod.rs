@@ -48,7 +48,10 @@ pub fn try_tricks(word: &str) -> TrickResult {
         'z',
     ];
     let slur_trick_chars = ['a', 'c', 'i', 'n', 'o', 'q', 's'];
-    let first_char = word.chars().next().unwrap();
+    let first_char = match word.chars().next() {
+        Some(first_char) => first_char,
+        None => return TrickResult::NotFound,
+    };
 
     if trick_chars.contains(&first_char) {
         let trick_list = match_tricks_list(first_char);
@@ -109,16 +112,16 @@ pub fn try_medieval_tricks(word: &str) -> TrickResult {
     TrickResult::NotFound
 }
 
-fn iterate_over_tricks(trick_list: &Vec<Trick>, word: &str) -> (String, Vec<String>) {
+fn iterate_over_tricks(trick_list: &[Trick], word: &str) -> (String, Vec<String>) {
     // word should be modified after each operation is applied.
     let mut explanations = Vec::new();
     let mut modified_word = word.to_string();
 
     for trick in trick_list.iter() {
         let (word, new_explanation) = match trick.operation {
-            Operation::FlipFlop => flip_flop(trick.str_1, trick.str_2, &word),
-            Operation::Flip => flip(trick.str_1, trick.str_2, &word),
-            Operation::Internal => internal(trick.str_1, trick.str_2, &word),
+            Operation::FlipFlop => flip_flop(trick.str_1, trick.str_2, word),
+            Operation::Flip => flip(trick.str_1, trick.str_2, word),
+            Operation::Internal => internal(trick.str_1, trick.str_2, word),
         };
 
         if word != modified_word {
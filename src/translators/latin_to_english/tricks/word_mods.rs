@@ -13,14 +13,10 @@ pub fn flip(str_to_replace: &str, replacement_str: &str, word: &str) -> (String,
         if new_word.len() >= replacement_str.len() + 2 && new_word.starts_with(replacement_str) {
             let canned_explanation = "' may have replaced usual '";
 
-            explanation = if !canned_explanation.is_empty() {
-                format!(
-                    "An initial '{}{}{}'",
-                    str_to_replace, canned_explanation, replacement_str
-                )
-            } else {
-                String::from("")
-            };
+            explanation = format!(
+                "An initial '{}{}{}'",
+                str_to_replace, canned_explanation, replacement_str
+            );
 
             return (new_word, explanation);
         }
@@ -1,10 +1,11 @@
-use crate::dictionary_structures::dictionary_values::{Modifier, ModifierType, Stem};
-use crate::translators::latin_to_english::parser::find_form;
+use crate::dictionary_structures::dictionary_keys::PartOfSpeech;
+use crate::dictionary_structures::dictionary_values::{Attachment, Modifier, ModifierType, Stem};
+use crate::error::VocabError;
+use crate::translators::latin_to_english::parser::parse;
+use crate::translators::latin_to_english::word_formation::find_derivations;
 use crate::translators::latin_to_english::LatinTranslationInfo;
-use crate::utils::data::{
-    get_latin_not_packons, get_latin_packons, get_latin_prefixes, get_latin_suffixes,
-    get_latin_tackons,
-};
+use crate::utils::data::Dictionary;
+use std::cmp::Reverse;
 
 pub fn add_stem_to_word(matched_stem: Stem, matching_word: Option<&mut LatinTranslationInfo>) {
     if let Some(word) = matching_word {
@@ -17,115 +18,192 @@ pub fn add_stem_to_word(matched_stem: Stem, matching_word: Option<&mut LatinTran
     }
 }
 
-pub fn reduce(latin_word: &str) -> Option<Vec<LatinTranslationInfo>> {
-    let mut modifiers: Vec<Modifier> = Vec::new();
-    let latin_prefixes = get_latin_prefixes();
-    let latin_suffixes = get_latin_suffixes();
+/**
+ * Finds words that are not in the dictionary by removing addons, ex: accurro -> ad + curro.
+ * Every derivation found is returned, best first, with its prefixes and suffixes as modifiers.
+ */
+pub fn reduce(
+    dictionary: &Dictionary,
+    latin_word: &str,
+) -> Result<Option<Vec<LatinTranslationInfo>>, VocabError> {
+    let output: Vec<LatinTranslationInfo> = find_derivations(dictionary, latin_word)
+        .into_iter()
+        .map(|derivation| derivation.definition)
+        .collect();
+
+    if output.is_empty() {
+        Ok(None)
+    } else {
+        Ok(Some(output))
+    }
+}
 
-    let mut stripped_latin_word = latin_word.to_string();
-    latin_prefixes.iter().for_each(|prefix| {
-        if stripped_latin_word.starts_with(prefix.orth.as_str()) {
-            stripped_latin_word = stripped_latin_word
-                .trim_start_matches(prefix.orth.as_str())
-                .to_string();
+/**
+ * Finds qu pronouns with a tickon in front of them, ex: ecquis -> ec + quis, nescioquid -> nescio + quid.
+ * Longer tickons are tried first so nescioquis is not read as ne + scioquis.
+ */
+pub fn split_tickon(
+    dictionary: &Dictionary,
+    latin_word: &str,
+) -> Result<Option<Vec<LatinTranslationInfo>>, VocabError> {
+    let mut tickons: Vec<&Attachment> = dictionary.tickons.iter().collect();
+    tickons.sort_by_key(|tickon| Reverse(tickon.orth.len()));
+
+    for tickon in tickons {
+        let pronoun = match latin_word.strip_prefix(tickon.orth.as_str()) {
+            Some(pronoun) if !pronoun.is_empty() => pronoun,
+            _ => continue,
+        };
+
+        if let Some(mut output) = parse(dictionary, pronoun, true)? {
+            output.retain(|word| {
+                word.word.pos == PartOfSpeech::Pronoun || word.word.pos == PartOfSpeech::Packon
+            });
+            if output.is_empty() {
+                continue;
+            }
 
             let mut modifier = Modifier::new();
-            modifier.set_orth(&prefix.orth);
-            modifier.set_pos(prefix.pos);
-            modifier.set_senses(&prefix.senses);
-            modifier.set_modifier(ModifierType::Prefix);
+            modifier.set_orth(&tickon.orth);
+            modifier.set_pos(tickon.pos);
+            modifier.set_senses(&tickon.senses);
+            modifier.set_modifier(ModifierType::Tickon);
 
-            modifiers.push(modifier);
+            for word in output.iter_mut() {
+                word.word.set_modifiers(vec![modifier.clone()]);
+            }
+            return Ok(Some(output));
         }
-    });
-
-    latin_suffixes.iter().for_each(|suffix| {
-        if stripped_latin_word.ends_with(suffix.orth.as_str()) {
-            stripped_latin_word = stripped_latin_word
-                .trim_end_matches(suffix.orth.as_str())
-                .to_string();
+    }
 
-            let mut modifier = Modifier::new();
-            modifier.set_orth(&suffix.orth);
-            modifier.set_pos(suffix.pos);
-            modifier.set_senses(&suffix.senses);
-            modifier.set_modifier(ModifierType::Suffix);
+    Ok(None)
+}
 
-            modifiers.push(modifier);
-        }
-    });
+/**
+ * Every way of removing up to 2 enclitics from the end of the word, ex: virumque -> virum + que, itane -> ita + ne.
+ * Packons are only removed from qu words and are tried before the tackons with the same spelling.
+ * The modifiers are in the order they appear in the word.
+ */
+pub fn split_enclitics(dictionary: &Dictionary, latin_word: &str) -> Vec<(String, Vec<Modifier>)> {
+    let mut splits: Vec<(String, Vec<Modifier>)> = Vec::new();
+    add_enclitic_splits(
+        dictionary,
+        latin_word,
+        Vec::new(),
+        MAX_ENCLITICS,
+        &mut splits,
+    );
+    splits
+}
 
-    if stripped_latin_word == latin_word || stripped_latin_word.len() == 0 || modifiers.len() == 0 {
-        return None;
+const MAX_ENCLITICS: usize = 2;
+
+fn add_enclitic_splits(
+    dictionary: &Dictionary,
+    latin_word: &str,
+    modifiers: Vec<Modifier>,
+    enclitics_left: usize,
+    splits: &mut Vec<(String, Vec<Modifier>)>,
+) {
+    if enclitics_left == 0 {
+        return;
     }
 
-    let mut output = find_form(&stripped_latin_word, true);
-
-    if output.is_some() {
-        for word in output.as_mut().unwrap() {
-            word.word.set_modifiers(modifiers.clone());
-        }
-
-        output
-    } else {
-        None
+    let packons = match latin_word.starts_with("qu") {
+        true => dictionary.packons.iter(),
+        false => [].iter(),
+    };
+    let attachments = packons
+        .map(|packon| (packon, ModifierType::Packon))
+        .chain(
+            dictionary
+                .not_packons
+                .iter()
+                .map(|not_packon| (not_packon, ModifierType::NotPackon)),
+        )
+        .chain(
+            dictionary
+                .tackons
+                .iter()
+                .map(|tackon| (tackon, ModifierType::Tackon)),
+        );
+
+    for (attachment, modifier_type) in attachments {
+        let base = match latin_word.strip_suffix(attachment.orth.as_str()) {
+            Some(base) if !base.is_empty() => base,
+            _ => continue,
+        };
+
+        let mut modifier = Modifier::new();
+        modifier.set_orth(&attachment.orth);
+        modifier.set_pos(attachment.pos);
+        modifier.set_senses(&attachment.senses);
+        modifier.set_modifier(modifier_type);
+
+        let mut base_modifiers = vec![modifier];
+        base_modifiers.extend(modifiers.iter().cloned());
+
+        splits.push((base.to_string(), base_modifiers.clone()));
+        add_enclitic_splits(dictionary, base, base_modifiers, enclitics_left - 1, splits);
     }
 }
 
-pub fn split_enclitic(latin_word: &str) -> (String, Vec<Modifier>) {
-    let mut modifiers: Vec<Modifier> = Vec::new();
-    let latin_not_packons = get_latin_not_packons();
-    let mut split_word = latin_word.to_string();
-    let latin_tackons = get_latin_tackons();
-    let latin_packons = get_latin_packons();
-
-    let tackon = latin_tackons
-        .iter()
-        .find(|tackon| split_word.ends_with(tackon.orth.as_str()));
-
-    if tackon.is_some() {
-        let tackon = tackon.unwrap();
-
-        // Est exception
-        if latin_word != "est" {
-            let mut modifier = Modifier::new();
-            modifier.set_orth(&tackon.orth);
-            modifier.set_pos(tackon.pos);
-            modifier.set_senses(&tackon.senses);
-            modifier.set_modifier(ModifierType::Tackon);
-
-            split_word.truncate(split_word.len() - tackon.orth.len());
-            modifiers.push(modifier);
-        }
-    } else {
-        if latin_word.starts_with("qu") {
-            for packon in latin_packons {
-                if split_word.ends_with(packon.orth.as_str()) {
-                    let mut modifier = Modifier::new();
-                    modifier.set_orth(&packon.orth);
-                    modifier.set_pos(packon.pos);
-                    modifier.set_senses(&packon.senses);
-                    modifier.set_modifier(ModifierType::Packon);
-
-                    split_word.truncate(split_word.len() - packon.orth.len());
-                    modifiers.push(modifier);
-                }
+/**
+ * Translates the word with its enclitics removed, for every split the enclitic next to the word can be attached to.
+ * Tackons (que, ne, ve) go on anything, packons on qu pronouns and the other attachments on their own part of speech.
+ * The same word found with the same enclitics from different lists is only returned once.
+ */
+pub fn enclitic_readings(
+    dictionary: &Dictionary,
+    latin_word: &str,
+) -> Result<Vec<LatinTranslationInfo>, VocabError> {
+    let mut output: Vec<LatinTranslationInfo> = Vec::new();
+
+    for (base, modifiers) in split_enclitics(dictionary, latin_word) {
+        let readings = match parse(dictionary, &base, false)? {
+            Some(readings) => readings,
+            None => continue,
+        };
+
+        for mut reading in readings {
+            if !attaches_to(&modifiers[0], reading.word.pos) {
+                continue;
             }
-        } else {
-            for packon in latin_not_packons {
-                if split_word.ends_with(packon.orth.as_str()) {
-                    let mut modifier = Modifier::new();
-                    modifier.set_orth(&packon.orth);
-                    modifier.set_pos(packon.pos);
-                    modifier.set_senses(&packon.senses);
-                    modifier.set_modifier(ModifierType::Packon);
-
-                    split_word.truncate(split_word.len() - packon.orth.len());
-                    modifiers.push(modifier);
-                }
+
+            let mut word_modifiers = reading.word.modifiers.take().unwrap_or_default();
+            word_modifiers.extend(modifiers.iter().cloned());
+            reading.word.set_modifiers(word_modifiers);
+
+            let found = output.iter().any(|other| {
+                other.word.id == reading.word.id
+                    && other.word.orth == reading.word.orth
+                    && other.stem.orth == reading.stem.orth
+                    && same_orths(&other.word.modifiers, &reading.word.modifiers)
+            });
+            if !found {
+                output.push(reading);
             }
         }
     }
 
-    (split_word, modifiers)
+    Ok(output)
+}
+
+fn attaches_to(enclitic: &Modifier, pos: PartOfSpeech) -> bool {
+    match enclitic.modifier {
+        ModifierType::Packon => pos == PartOfSpeech::Packon || pos == PartOfSpeech::Pronoun,
+        ModifierType::NotPackon => enclitic.pos == pos,
+        _ => true,
+    }
+}
+
+fn same_orths(modifiers: &Option<Vec<Modifier>>, other_modifiers: &Option<Vec<Modifier>>) -> bool {
+    let orths = |modifiers: &Option<Vec<Modifier>>| -> Vec<String> {
+        modifiers
+            .iter()
+            .flatten()
+            .map(|modifier| modifier.orth.clone())
+            .collect()
+    };
+    orths(modifiers) == orths(other_modifiers)
 }
@@ -0,0 +1,353 @@
+use crate::dictionary_structures::dictionary_keys::PartOfSpeech;
+use crate::dictionary_structures::dictionary_values::{
+    Inflection, Modifier, ModifierType, NValue, Stem,
+};
+use crate::translators::latin_to_english::translator::lookup_stems;
+use crate::translators::latin_to_english::LatinTranslationInfo;
+use crate::utils::data::Dictionary;
+use serde::Serialize;
+use std::cmp::Reverse;
+
+// the most prefixes and suffixes a word is split into
+const MAX_STEPS: usize = 3;
+
+/**
+ * A reading of a word that is not in the dictionary as a dictionary word with prefixes and suffixes added,
+ * ex: accurro -> ad + curro, amatrix -> amo + ric.
+ * The definition has the root word, the stem as it appears in the word and the modifiers,
+ * the steps are the same modifiers in the order they are added to the root.
+ */
+#[derive(Debug, Clone, Serialize)]
+pub struct Derivation {
+    pub definition: LatinTranslationInfo,
+    pub root: String,
+    pub steps: Vec<Modifier>,
+}
+
+impl Derivation {
+    /**
+     * One line per step, ex: "suffix -ric: amat + ric = amatric, verb -> noun, -or; -er; indicates the doer;"
+     */
+    pub fn explanation(&self) -> Vec<String> {
+        let mut stem = self.root.clone();
+
+        self.steps
+            .iter()
+            .map(|step| match step.modifier {
+                ModifierType::Prefix => {
+                    let derived = format!("{}{}", step.orth, stem);
+                    let line = format!(
+                        "prefix {}-: {} + {} = {}, {}",
+                        step.orth,
+                        step.orth,
+                        stem,
+                        derived,
+                        step.senses.join(" ")
+                    );
+                    stem = derived;
+                    line
+                }
+                _ => {
+                    let derived = format!("{}{}", stem, step.orth);
+                    let change = match step.suffix_form() {
+                        Some(form) => format!(
+                            "{} -> {}, ",
+                            form.root_pos.as_str(),
+                            form.target_pos.as_str()
+                        ),
+                        None => String::new(),
+                    };
+                    let line = format!(
+                        "suffix -{}: {} + {} = {}, {}{}",
+                        step.orth,
+                        stem,
+                        step.orth,
+                        derived,
+                        change,
+                        step.senses.join(" ")
+                    );
+                    stem = derived;
+                    line
+                }
+            })
+            .collect()
+    }
+}
+
+// what the stem left after removing an ending or an addon has to be
+struct Target<'a> {
+    pos: PartOfSpeech,
+    // which stem of the word, 0 is any
+    key: i8,
+    // only known for the stem the inflection is added to
+    n: Option<&'a [NValue]>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Affix {
+    Prefix,
+    Suffix,
+}
+
+// a prefix or suffix added to the stem, the index is into the dictionary's prefixes or suffixes
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Step {
+    affix: Affix,
+    index: usize,
+}
+
+impl Step {
+    fn to_modifier(self, dictionary: &Dictionary) -> Modifier {
+        let (mut modifier, modifier_type) = match self.affix {
+            Affix::Prefix => (
+                dictionary.prefixes[self.index].clone(),
+                ModifierType::Prefix,
+            ),
+            Affix::Suffix => (
+                dictionary.suffixes[self.index].clone(),
+                ModifierType::Suffix,
+            ),
+        };
+        modifier.set_modifier(modifier_type);
+        modifier
+    }
+}
+
+struct Root<'a> {
+    stem: &'a Stem,
+    // from the root outwards
+    steps: Vec<Step>,
+}
+
+struct Candidate<'a> {
+    stem: &'a Stem,
+    derived_stem: String,
+    steps: Vec<Step>,
+    inflections: Vec<Inflection>,
+}
+
+/**
+ * Finds every way the word can be built from a dictionary word with up to 3 prefixes and suffixes.
+ * Each inflection ending is removed and the rest is split into a prefix or a suffix and a smaller stem until
+ * a dictionary stem is found. Suffixes change the part of speech and stem of the word they are added to
+ * as listed in ADDONS.LAT, ex: ric is added to the 4th stem of a verb and makes a 3rd declension noun.
+ * Prefixes are only added after suffixes, which avoids finding ad + (curs + ric) and (ad + curs) + ric twice.
+ * The derivations with the fewest steps come first, then the ones with the most common inflections and longest roots.
+ */
+pub fn find_derivations(dictionary: &Dictionary, latin_word: &str) -> Vec<Derivation> {
+    let mut candidates: Vec<Candidate> = Vec::new();
+
+    for inflection in dictionary.get_inflections_ending(latin_word) {
+        let stem = &latin_word[..latin_word.len() - inflection.ending.len()];
+        if stem.is_empty() {
+            continue;
+        }
+
+        let target = Target {
+            pos: inflection.pos,
+            key: inflection.stem_key,
+            n: inflection.n.as_deref(),
+        };
+
+        for root in stem_roots(dictionary, stem, &target, MAX_STEPS, true) {
+            if root.steps.is_empty() {
+                continue;
+            }
+
+            let existing = candidates.iter_mut().find(|candidate| {
+                candidate.stem.wid == root.stem.wid
+                    && candidate.derived_stem == stem
+                    && candidate.steps == root.steps
+            });
+
+            match existing {
+                Some(candidate) => {
+                    if !candidate
+                        .inflections
+                        .iter()
+                        .any(|found| found.form.as_str() == inflection.form.as_str())
+                    {
+                        candidate.inflections.push(inflection.clone());
+                    }
+                }
+                None => candidates.push(Candidate {
+                    stem: root.stem,
+                    derived_stem: stem.to_string(),
+                    steps: root.steps,
+                    inflections: vec![inflection.clone()],
+                }),
+            }
+        }
+    }
+
+    candidates.sort_by_key(|candidate| {
+        (
+            candidate.steps.len(),
+            candidate
+                .inflections
+                .iter()
+                .map(|inflection| inflection.rank())
+                .min(),
+            Reverse(candidate.stem.orth.len()),
+        )
+    });
+
+    candidates
+        .into_iter()
+        .filter_map(|candidate| {
+            let mut definition = lookup_stems(
+                dictionary,
+                vec![(candidate.stem.clone(), candidate.inflections)],
+            )?
+            .remove(0);
+            let steps: Vec<Modifier> = candidate
+                .steps
+                .iter()
+                .map(|step| step.to_modifier(dictionary))
+                .collect();
+
+            definition.stem.orth = candidate.derived_stem;
+            definition.word.set_modifiers(steps.clone());
+
+            Some(Derivation {
+                definition,
+                root: candidate.stem.orth.clone(),
+                steps,
+            })
+        })
+        .collect()
+}
+
+fn stem_roots<'a>(
+    dictionary: &'a Dictionary,
+    stem: &str,
+    target: &Target,
+    steps_left: usize,
+    prefixes_allowed: bool,
+) -> Vec<Root<'a>> {
+    let mut roots: Vec<Root> = dictionary
+        .get_stems_by_orth(stem)
+        .into_iter()
+        .filter(|found| stem_matches(dictionary, found, target))
+        .map(|found| Root {
+            stem: found,
+            steps: Vec::new(),
+        })
+        .collect();
+
+    if steps_left == 0 {
+        return roots;
+    }
+
+    for (index, suffix) in dictionary.suffixes.iter().enumerate() {
+        let form = match suffix.suffix_form() {
+            Some(form) => form,
+            None => continue,
+        };
+        if !pos_matches(form.target_pos, target.pos)
+            || (form.target_key > 0 && target.key > 0 && form.target_key != target.key)
+            || target
+                .n
+                .is_some_and(|n| !suffix_n_matches(&form.target_n, n))
+        {
+            continue;
+        }
+        let base = match suffix.strip_suffix(stem) {
+            Some(base) => base,
+            None => continue,
+        };
+
+        let root_target = Target {
+            pos: form.root_pos,
+            key: form.root_key,
+            n: None,
+        };
+        for mut root in stem_roots(dictionary, base, &root_target, steps_left - 1, false) {
+            root.steps.push(Step {
+                affix: Affix::Suffix,
+                index,
+            });
+            roots.push(root);
+        }
+    }
+
+    if prefixes_allowed {
+        for (index, prefix) in dictionary.prefixes.iter().enumerate() {
+            if !prefix.applies_to(target.pos) {
+                continue;
+            }
+            let base = match prefix.strip_prefix(stem) {
+                Some(base) => base,
+                None => continue,
+            };
+
+            for mut root in stem_roots(dictionary, base, target, steps_left - 1, true) {
+                root.steps.push(Step {
+                    affix: Affix::Prefix,
+                    index,
+                });
+                roots.push(root);
+            }
+        }
+    }
+
+    roots
+}
+
+fn stem_matches(dictionary: &Dictionary, stem: &Stem, target: &Target) -> bool {
+    if !pos_matches(stem.pos, target.pos) {
+        return false;
+    }
+
+    if target.key > 0 {
+        let is_key_stem = dictionary
+            .get_latin_word_by_id(stem.wid)
+            .and_then(|word| word.parts.get(target.key as usize - 1))
+            .is_some_and(|part| *part == stem.orth);
+        if !is_key_stem {
+            return false;
+        }
+    }
+
+    match (target.n, &stem.n) {
+        (Some(inflection_n), Some(stem_n)) => stem_n_matches(stem_n, inflection_n),
+        _ => true,
+    }
+}
+
+// the same rules as finding a stem for an inflection without addons
+fn stem_n_matches(stem_n: &[NValue], inflection_n: &[NValue]) -> bool {
+    if inflection_n.is_empty() {
+        return true;
+    }
+    if stem_n.len() == 1 {
+        return stem_n[0] == inflection_n[0];
+    }
+
+    stem_n.len() < 2
+        || inflection_n.len() < 2
+        || ((inflection_n[0] == stem_n[0] || inflection_n[0] == NValue::Integer(0))
+            && (inflection_n[1] == stem_n[1] || inflection_n[1] == NValue::Integer(0)))
+}
+
+// the declension or conjugation a suffix makes, 0 on either side matches anything
+fn suffix_n_matches(suffix_n: &[i8], inflection_n: &[NValue]) -> bool {
+    suffix_n
+        .iter()
+        .zip(inflection_n)
+        .all(|(suffix_n, inflection_n)| {
+            let inflection_n = inflection_n.get_n_value_1();
+            *suffix_n == 0 || inflection_n == 0 || *suffix_n == inflection_n
+        })
+}
+
+// unknown (X) is any part of speech, participles are forms of verbs
+fn pos_matches(pos: PartOfSpeech, target: PartOfSpeech) -> bool {
+    pos == target
+        || target == PartOfSpeech::Unknown
+        || matches!(
+            (pos, target),
+            (PartOfSpeech::Verb, PartOfSpeech::Participle)
+                | (PartOfSpeech::Participle, PartOfSpeech::Verb)
+        )
+}
@@ -1,9 +1,13 @@
 pub mod english_to_latin;
 pub mod latin_to_english;
 
-use crate::dictionary_structures::dictionary_values::Form;
+use crate::dictionary_structures::dictionary_keys::Frequency;
+use crate::dictionary_structures::dictionary_values::{Form, Modifier, ModifierType};
+use crate::error::VocabError;
 use crate::translators::english_to_latin::EnglishTranslationInfo;
+use crate::translators::latin_to_english::suggestions::Suggestion;
 use crate::translators::latin_to_english::LatinTranslationInfo;
+use crate::utils::vowel_quantity::{mark_inflection, mark_principle_parts};
 use serde::{Deserialize, Serialize, Serializer};
 use std::mem::take;
 
@@ -12,6 +16,7 @@ pub enum DisplayType {
     Json,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub enum Language {
     Latin,
     English,
@@ -38,6 +43,8 @@ pub struct Translation {
     pub word: String,
     #[serde(serialize_with = "serialize_translation")]
     pub definitions: TranslationType,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub suggestions: Option<Vec<Suggestion>>,
 }
 
 fn serialize_translation<S>(def: &TranslationType, serializer: S) -> Result<S::Ok, S::Error>
@@ -52,7 +59,19 @@ where
 
 impl Translation {
     pub fn new(word: String, definitions: TranslationType) -> Translation {
-        Translation { word, definitions }
+        Translation {
+            word,
+            definitions,
+            suggestions: None,
+        }
+    }
+
+    /**
+     * Spellings close to the word, shown when it has no definitions
+     */
+    pub fn with_suggestions(mut self, suggestions: Vec<Suggestion>) -> Translation {
+        self.suggestions = Some(suggestions);
+        self
     }
 
     pub fn as_json(&self) -> String {
@@ -63,96 +82,64 @@ impl Translation {
      * Makes output more readable.
      * Should only be called after all parsing is done.
      */
-    pub fn post_process(&mut self, language: Language, sort: bool) {
-        let processed_translation = self;
-
+    pub fn post_process(&mut self, language: Language, sort: bool) -> Result<(), VocabError> {
         match language {
             Language::Latin => {
                 if sort {
-                    processed_translation.sort();
+                    self.sort();
                 }
 
-                processed_translation.definitions = match &mut processed_translation.definitions {
+                match &mut self.definitions {
                     TranslationType::Latin(definitions) => {
-                        let new_definitions: Vec<_> = definitions
-                            .drain(..)
-                            .map(|mut definition| {
-                                definition.word.form.str_form_to_long_form(
-                                    definition.word.pos,
-                                    Structure::LatinWordInfo,
-                                );
-
-                                definition.word.generate_principle_parts();
-
-                                definition
-                                    .stem
-                                    .form
-                                    .str_form_to_long_form(definition.word.pos, Structure::Stem);
-
-                                if let Some(mut inflections) = take(&mut definition.inflections) {
-                                    definition.remove_inflections_with_wrong_pos();
-                                    let part_of_speech = definition.word.pos;
-
-                                    inflections.iter_mut().for_each(|inflection| {
-                                        inflection.form.str_form_to_long_form(
-                                            part_of_speech,
-                                            Structure::Inflection,
-                                        );
-                                    });
-
-                                    definition.inflections = Some(inflections);
-                                }
-                                definition
-                            })
-                            .collect();
-                        TranslationType::Latin(new_definitions)
+                        post_process_latin_definitions(definitions)
                     }
-                    _ => {
-                        println!("Expected Latin translation type");
-                        std::process::exit(0);
-                    }
-                };
+                    TranslationType::English(_) => Err(VocabError::LanguageMismatch {
+                        expected: "Latin",
+                        found: "English",
+                    }),
+                }
             }
-            Language::English => {
-                processed_translation.definitions = match &mut processed_translation.definitions {
-                    TranslationType::English(definitions) => {
-                        let new_definitions: Vec<_> = definitions
-                            .drain(..)
-                            .map(|mut definition| {
-                                definition.translation.form.str_form_to_long_form(
-                                    definition.word.pos,
-                                    Structure::LatinWordInfo,
-                                );
+            Language::English => match &mut self.definitions {
+                TranslationType::English(definitions) => {
+                    post_process_english_definitions(definitions)
+                }
+                TranslationType::Latin(_) => Err(VocabError::LanguageMismatch {
+                    expected: "English",
+                    found: "Latin",
+                }),
+            },
+        }
+    }
 
-                                definition.translation.generate_principle_parts();
-                                definition
-                            })
-                            .collect();
-                        TranslationType::English(new_definitions)
-                    }
-                    _ => {
-                        println!("Expected English translation type");
-                        std::process::exit(0);
-                    }
+    // one line per suggestion with the edits, then the dictionary form and senses of its first definition
+    fn display_suggestions(&self) {
+        let suggestions = match &self.suggestions {
+            Some(suggestions) if !suggestions.is_empty() => suggestions,
+            _ => return,
+        };
+
+        println!("Did you mean:");
+        for suggestion in suggestions {
+            println!("  {} ({})", suggestion.word, suggestion.edits.join(", "));
+            if let Some(definition) = suggestion.definitions.first() {
+                let parts = match definition.word.parts.is_empty() {
+                    true => definition.word.orth.clone(),
+                    false => definition.word.parts.join(" "),
                 };
+                println!(
+                    "    {} | {} | {}",
+                    parts,
+                    definition.word.pos.as_str(),
+                    definition.word.senses.join(" ")
+                );
             }
         }
+        println!();
     }
 
     fn sort(&mut self) {
-        let sorted_translation = self;
-
-        match &mut sorted_translation.definitions {
-            &mut TranslationType::Latin(ref mut info) => {
-                info.sort_by(|a, b| {
-                    a.word
-                        .info
-                        .freq
-                        .as_number()
-                        .cmp(&b.word.info.freq.as_number())
-                });
-            }
-            _ => {}
+        if let TranslationType::Latin(definitions) = &mut self.definitions {
+            sort_latin_definitions(definitions);
         }
     }
 
@@ -163,15 +150,16 @@ impl Translation {
                 TranslationType::Latin(definitions) => {
                     if definitions.is_empty() {
                         println!("No definitions found");
+                        self.display_suggestions();
                         return;
                     }
                     for definition in definitions {
-                        if definition.tricks.is_some() {
-                            for trick in definition.tricks.as_ref().unwrap() {
+                        if let Some(tricks) = &definition.tricks {
+                            for trick in tricks {
                                 println!("{}", trick);
                             }
                         }
-                        if &definition.word.parts.len() > &0 {
+                        if !definition.word.parts.is_empty() {
                             println!();
                             for part in &definition.word.parts {
                                 print!("{} ", part);
@@ -187,9 +175,9 @@ impl Translation {
                                 println!("{}", form.as_clean_str());
                             }
                         }
-                        if definition.inflections.is_some() {
+                        if let Some(inflections) = &definition.inflections {
                             let stem_orth = &definition.stem.orth;
-                            for inflection in definition.inflections.as_ref().unwrap() {
+                            for inflection in inflections {
                                 if inflection.ending.is_empty() {
                                     continue;
                                 }
@@ -209,10 +197,21 @@ impl Translation {
                                         form_string
                                     }
                                 };
-                                let inflection_line = format!(
+                                let mut inflection_line = format!(
                                     "{}.{} | {}",
                                     stem_orth, inflection.ending, form_string
                                 );
+                                // only uncommon endings are marked, most are classical and very frequent
+                                let mut rarity = Vec::new();
+                                if !inflection.is_classical() {
+                                    rarity.push(inflection.age.as_str());
+                                }
+                                if inflection.freq.as_number() > 1 {
+                                    rarity.push(inflection.freq.as_str());
+                                }
+                                if !rarity.is_empty() {
+                                    inflection_line += &format!(" ({})", rarity.join(", "));
+                                }
                                 println!("{}", inflection_line);
                             }
                         }
@@ -224,9 +223,9 @@ impl Translation {
                             print!("{} ", sense);
                         }
 
-                        if definition.word.modifiers.is_some() {
+                        if let Some(modifiers) = &definition.word.modifiers {
                             println!();
-                            for modifier in definition.word.modifiers.as_ref().unwrap() {
+                            for modifier in modifiers {
                                 println!("\n{}: {}", modifier.modifier.as_str(), modifier.orth);
                                 println!("{} ", modifier.pos.as_str());
                                 for sense in &modifier.senses {
@@ -245,6 +244,9 @@ impl Translation {
                     }
                     for definition in definitions {
                         println!();
+                        if let Some(lemma) = &definition.lemma {
+                            println!("found as: {}", lemma);
+                        }
                         for part in &definition.translation.parts {
                             print!("{} ", part);
                         }
@@ -276,6 +278,153 @@ impl Translation {
     }
 }
 
+/**
+ * Converts forms to long forms and generates principle parts for Latin definitions.
+ */
+pub fn post_process_latin_definitions(
+    definitions: &mut [LatinTranslationInfo],
+) -> Result<(), VocabError> {
+    for definition in definitions.iter_mut() {
+        definition
+            .word
+            .form
+            .str_form_to_long_form(definition.word.pos, Structure::LatinWordInfo);
+
+        definition.word.generate_principle_parts()?;
+
+        definition
+            .stem
+            .form
+            .str_form_to_long_form(definition.word.pos, Structure::Stem);
+
+        if let Some(mut inflections) = take(&mut definition.inflections) {
+            definition.remove_inflections_with_wrong_pos();
+
+            // words built with suffixes are inflected as another part of speech than their root, ex: amatrix
+            inflections.iter_mut().for_each(|inflection| {
+                inflection
+                    .form
+                    .str_form_to_long_form(inflection.pos, Structure::Inflection);
+            });
+
+            definition.inflections = Some(inflections);
+        }
+    }
+
+    Ok(())
+}
+
+/**
+ * Marks the long vowels of the principle parts and inflection endings that follow from the declension or conjugation.
+ * Should only be called after post_process_latin_definitions.
+ */
+pub fn mark_vowel_quantity(definitions: &mut [LatinTranslationInfo]) {
+    for definition in definitions.iter_mut() {
+        mark_principle_parts(&mut definition.word);
+
+        for inflection in definition.inflections.iter_mut().flatten() {
+            mark_inflection(inflection, &definition.word, &definition.stem.orth);
+        }
+    }
+}
+
+/**
+ * Converts forms to long forms and generates principle parts for the Latin side of English definitions.
+ */
+pub fn post_process_english_definitions(
+    definitions: &mut [EnglishTranslationInfo],
+) -> Result<(), VocabError> {
+    for definition in definitions.iter_mut() {
+        definition
+            .translation
+            .form
+            .str_form_to_long_form(definition.word.pos, Structure::LatinWordInfo);
+
+        definition.translation.generate_principle_parts()?;
+    }
+
+    Ok(())
+}
+
+/**
+ * Sorts Latin definitions by how frequently the word is used, then by how common its most common inflection is.
+ */
+pub fn sort_latin_definitions(definitions: &mut [LatinTranslationInfo]) {
+    definitions.sort_by_key(|definition| {
+        (
+            definition.word.info.freq.as_number(),
+            best_inflection_rank(definition),
+        )
+    });
+}
+
+/**
+ * Orders the inflections of every definition from most to least common, then orders the definitions:
+ * words found as they are come first, then the ones split into enclitics, then the ones built with prefixes and suffixes.
+ * Within each, definitions that only match rare, archaic or late inflections are moved after the others.
+ * With `classical_only` the non classical inflections are removed, along with definitions that have none left.
+ */
+pub fn rank_latin_definitions(definitions: &mut Vec<LatinTranslationInfo>, classical_only: bool) {
+    definitions.retain_mut(|definition| match definition.inflections.as_mut() {
+        Some(inflections) => {
+            let had_inflections = !inflections.is_empty();
+            if classical_only {
+                inflections.retain(|inflection| inflection.is_classical());
+            }
+            inflections.sort_by_key(|inflection| inflection.rank());
+            !had_inflections || !inflections.is_empty()
+        }
+        None => true,
+    });
+
+    definitions.sort_by_key(|definition| {
+        (
+            addon_count(definition),
+            enclitic_count(definition),
+            best_inflection_rank(definition),
+        )
+    });
+}
+
+fn addon_count(definition: &LatinTranslationInfo) -> usize {
+    modifier_count(definition, |modifier| {
+        matches!(
+            modifier.modifier,
+            ModifierType::Prefix | ModifierType::Suffix
+        )
+    })
+}
+
+fn enclitic_count(definition: &LatinTranslationInfo) -> usize {
+    modifier_count(definition, |modifier| {
+        matches!(
+            modifier.modifier,
+            ModifierType::Tackon | ModifierType::Packon | ModifierType::NotPackon
+        )
+    })
+}
+
+fn modifier_count(definition: &LatinTranslationInfo, counted: fn(&Modifier) -> bool) -> usize {
+    definition
+        .word
+        .modifiers
+        .iter()
+        .flatten()
+        .filter(|modifier| counted(modifier))
+        .count()
+}
+
+// definitions without inflections (unique words, roman numerals) are never demoted
+fn best_inflection_rank(definition: &LatinTranslationInfo) -> (u8, bool) {
+    definition
+        .inflections
+        .iter()
+        .flatten()
+        .map(|inflection| inflection.rank())
+        .min()
+        .unwrap_or((Frequency::VeryFrequent.as_number(), false))
+}
+
 #[derive(Debug, Deserialize, Serialize)]
 #[serde(tag = "type")]
 pub enum TranslationType {
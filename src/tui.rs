@@ -0,0 +1,454 @@
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use rustyline::completion::Completer;
+use rustyline::error::ReadlineError;
+use rustyline::highlight::Highlighter;
+use rustyline::hint::Hinter;
+use rustyline::history::DefaultHistory;
+use rustyline::validate::Validator;
+use rustyline::{CompletionType, Config, Context, Editor, Helper};
+use vocab_vault::translators::Language;
+use vocab_vault::{Translator, VocabError, WordType};
+
+use crate::{
+    derive, english_to_latin, form, inflect, latin_to_english, lookup, parse_form,
+    parse_list_filter, parse_number, search,
+};
+
+const COMMANDS: [&str; 19] = [
+    ".help",
+    ".exit",
+    ".quit",
+    ".switch",
+    ".clear",
+    ".settings",
+    ".set",
+    ".max",
+    ".sort",
+    ".detailed",
+    ".tricks",
+    ".json",
+    ".inflect",
+    ".form",
+    ".lookup",
+    ".list",
+    ".search",
+    ".derive",
+    ".history",
+];
+const SETTINGS: [&str; 5] = ["max", "sort", "detailed", "tricks", "json"];
+// commands followed by a latin word, completed with latin stems in either language
+const LATIN_COMMANDS: [&str; 3] = [".inflect", ".form", ".derive"];
+const HISTORY_FILE: &str = ".vocab_vault_history";
+const HISTORY_SIZE: usize = 1000;
+
+/**
+ * The translation options that can be changed while the tui runs, ex: `.set max 10` or `.detailed on`
+ */
+struct Settings {
+    language: Language,
+    max: usize,
+    sort: bool,
+    detailed: bool,
+    tricks: bool,
+    json: bool,
+}
+
+impl Settings {
+    fn new() -> Settings {
+        Settings {
+            language: Language::Latin,
+            max: 6,
+            sort: true,
+            detailed: false,
+            tricks: true,
+            json: false,
+        }
+    }
+
+    /**
+     * Sets max to a number or sort, detailed, tricks or json to on or off, a toggle without a value is flipped
+     */
+    fn set(&mut self, name: &str, value: &str) -> Result<(), VocabError> {
+        let toggle = |current: bool| match value {
+            "" => Ok(!current),
+            "on" | "true" | "yes" => Ok(true),
+            "off" | "false" | "no" => Ok(false),
+            _ => Err(VocabError::InvalidArgument {
+                name: name.to_string(),
+                value: value.to_string(),
+            }),
+        };
+
+        match name {
+            "max" => self.max = parse_number("max", value)?,
+            "sort" => self.sort = toggle(self.sort)?,
+            "detailed" => self.detailed = toggle(self.detailed)?,
+            "tricks" => self.tricks = toggle(self.tricks)?,
+            "json" => self.json = toggle(self.json)?,
+            _ => {
+                return Err(VocabError::InvalidArgument {
+                    name: "setting".to_string(),
+                    value: name.to_string(),
+                })
+            }
+        }
+
+        Ok(())
+    }
+
+    fn apply(&self, translator: Translator) -> Translator {
+        translator
+            .with_max(self.max)
+            .with_sort(self.sort)
+            .with_tricks(self.tricks)
+    }
+
+    fn values(&self) -> Vec<(&'static str, String)> {
+        let on_off = |value: bool| if value { "on" } else { "off" }.to_string();
+        vec![
+            ("language", self.language.as_str().to_string()),
+            ("max", self.max.to_string()),
+            ("sort", on_off(self.sort)),
+            ("detailed", on_off(self.detailed)),
+            ("tricks", on_off(self.tricks)),
+            ("json", on_off(self.json)),
+        ]
+    }
+
+    // every setting, or only the one with the name
+    fn display(&self, only: Option<&str>) {
+        for (name, value) in self.values() {
+            if only.is_none_or(|only| only == name) {
+                println!("{}: {}", name, value);
+            }
+        }
+    }
+}
+
+/**
+ * Completes dot commands, setting names, latin stems and english words from the dictionary
+ */
+struct TuiHelper {
+    language: Language,
+    // sorted and without duplicates, so a prefix is a range
+    latin_words: Vec<String>,
+    english_words: Vec<String>,
+}
+
+impl TuiHelper {
+    fn new(translator: &Translator) -> TuiHelper {
+        let dictionary = translator.dictionary();
+
+        let mut latin_words: Vec<String> = dictionary
+            .stems
+            .iter()
+            .map(|stem| stem.orth.to_lowercase())
+            .chain(
+                dictionary
+                    .unique_latin_words
+                    .iter()
+                    .map(|word| word.orth.to_lowercase()),
+            )
+            .collect();
+        latin_words.sort_unstable();
+        latin_words.dedup();
+
+        let mut english_words: Vec<String> = dictionary
+            .english_words
+            .iter()
+            .map(|word| word.orth.to_lowercase())
+            .collect();
+        english_words.sort_unstable();
+        english_words.dedup();
+
+        TuiHelper {
+            language: Language::Latin,
+            latin_words,
+            english_words,
+        }
+    }
+}
+
+impl Completer for TuiHelper {
+    type Candidate = String;
+
+    fn complete(
+        &self,
+        line: &str,
+        pos: usize,
+        _: &Context<'_>,
+    ) -> rustyline::Result<(usize, Vec<String>)> {
+        let before = &line[..pos];
+        let start = before
+            .char_indices()
+            .rev()
+            // the dot of a command is part of the word
+            .find(|(index, c)| !(c.is_alphabetic() || *index == 0 && *c == '.'))
+            .map(|(index, c)| index + c.len_utf8())
+            .unwrap_or(0);
+        let prefix = &before[start..];
+        let command = line.split_whitespace().next().unwrap_or_default();
+
+        let words: &[String] = if start == 0 && prefix.starts_with('.') {
+            return Ok((start, complete_from(&COMMANDS, prefix)));
+        } else if command == ".set" && before.split_whitespace().count() <= 2 {
+            return Ok((start, complete_from(&SETTINGS, prefix)));
+        } else if LATIN_COMMANDS.contains(&command) {
+            &self.latin_words
+        } else if command == ".search" {
+            &self.english_words
+        } else if command.starts_with('.') {
+            return Ok((start, Vec::new()));
+        } else {
+            match self.language {
+                Language::Latin => &self.latin_words,
+                Language::English => &self.english_words,
+            }
+        };
+
+        if prefix.is_empty() {
+            return Ok((start, Vec::new()));
+        }
+        let prefix = prefix.to_lowercase();
+        let first = words.partition_point(|word| word.as_str() < prefix.as_str());
+        let candidates = words[first..]
+            .iter()
+            .take_while(|word| word.starts_with(&prefix))
+            .cloned()
+            .collect();
+
+        Ok((start, candidates))
+    }
+}
+
+fn complete_from(names: &[&str], prefix: &str) -> Vec<String> {
+    names
+        .iter()
+        .filter(|name| name.starts_with(prefix))
+        .map(|name| name.to_string())
+        .collect()
+}
+
+impl Hinter for TuiHelper {
+    type Hint = String;
+}
+
+impl Highlighter for TuiHelper {}
+
+impl Validator for TuiHelper {}
+
+impl Helper for TuiHelper {}
+
+/**
+ * Translates every line entered, with history kept between sessions and tab completion.
+ * Lines starting with a dot are commands, see `.help`.
+ */
+pub fn run(translator: Translator) -> Result<(), VocabError> {
+    let mut settings = Settings::new();
+    let mut translator = settings.apply(translator);
+
+    let config = Config::builder()
+        .completion_type(CompletionType::List)
+        .max_history_size(HISTORY_SIZE)
+        .and_then(|config| config.history_ignore_dups(true))
+        .map_err(readline_error)?
+        .build();
+    let mut editor: Editor<TuiHelper, DefaultHistory> =
+        Editor::with_config(config).map_err(readline_error)?;
+    editor.set_helper(Some(TuiHelper::new(&translator)));
+
+    let history = history_path();
+    if let Some(history) = &history {
+        // there is no history before the first session
+        let _ = editor.load_history(history);
+    }
+
+    loop {
+        let line = match editor.readline("> ") {
+            Ok(line) => line,
+            // ctrl-c clears the line, ctrl-d exits
+            Err(ReadlineError::Interrupted) => continue,
+            Err(ReadlineError::Eof) => break,
+            Err(error) => return Err(readline_error(error)),
+        };
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        editor.add_history_entry(line).map_err(readline_error)?;
+
+        let (command, argument) = split_first(line);
+        let result = match command {
+            ".exit" | ".quit" | "q" => break,
+            ".help" => {
+                display_help();
+                Ok(())
+            }
+            ".switch" => {
+                settings.language = match settings.language {
+                    Language::Latin => Language::English,
+                    Language::English => Language::Latin,
+                };
+                if let Some(helper) = editor.helper_mut() {
+                    helper.language = settings.language;
+                }
+                println!("Switched to {:?}", settings.language.as_str());
+                Ok(())
+            }
+            ".clear" => {
+                print!("\x1B[2J\x1B[1;1H");
+                Ok(())
+            }
+            ".history" => {
+                for (index, entry) in editor.history().iter().enumerate() {
+                    println!("{:>4}  {}", index + 1, entry);
+                }
+                Ok(())
+            }
+            ".settings" => {
+                settings.display(None);
+                Ok(())
+            }
+            ".set" | ".max" | ".sort" | ".detailed" | ".tricks" | ".json" => {
+                let (name, value) = match command {
+                    ".set" => split_first(argument),
+                    _ => (&command[1..], argument),
+                };
+                match settings.set(name, value) {
+                    Ok(()) => {
+                        translator = settings.apply(translator);
+                        settings.display(Some(name));
+                        Ok(())
+                    }
+                    Err(error) => Err(error),
+                }
+            }
+            _ => run_command(&translator, &settings, command, argument, line),
+        };
+
+        // a bad word or command should not end the session
+        if let Err(error) = result {
+            eprintln!("{}", error);
+        }
+    }
+
+    if let Some(history) = &history {
+        editor.save_history(history).map_err(readline_error)?;
+    }
+
+    Ok(())
+}
+
+// the commands that print something, anything that is not a command is translated
+fn run_command(
+    translator: &Translator,
+    settings: &Settings,
+    command: &str,
+    argument: &str,
+    line: &str,
+) -> Result<(), VocabError> {
+    let pretty = !settings.json;
+    let detailed = settings.detailed;
+    let required = |name: &str| match argument {
+        "" => Err(VocabError::MissingArgument(name.to_string())),
+        _ => Ok(argument),
+    };
+
+    match command {
+        ".inflect" => inflect(translator, required("word")?, pretty, detailed),
+        ".form" => {
+            let (word, description) = parse_form(required("form")?)?;
+            form(translator, word, description, pretty)
+        }
+        ".derive" => derive(translator, required("word")?, pretty),
+        ".search" => search(
+            translator,
+            required("query")?,
+            settings.max,
+            pretty,
+            detailed,
+        ),
+        ".lookup" => {
+            let (id, english) = split_first(required("id")?);
+            let id = parse_number("id", id)? as i32;
+            lookup(translator, id, english == "english", pretty, detailed)
+        }
+        ".list" => list(translator, required("type")?),
+        _ if command.starts_with('.') => Err(VocabError::CommandNotFound(command.to_string())),
+        _ => match settings.language {
+            Language::Latin => latin_to_english(translator, line, pretty, detailed),
+            Language::English => english_to_latin(translator, line, false, pretty, detailed),
+        },
+    }
+}
+
+/**
+ * `.list <type> [random] [option=value]...` takes the getList options as key=value, ex: `.list latin pos=noun amount=5 random`
+ */
+fn list(translator: &Translator, argument: &str) -> Result<(), VocabError> {
+    let (type_of_words, options) = split_first(argument);
+    let word_type = type_of_words
+        .parse::<WordType>()
+        .map_err(|_| VocabError::InvalidWordType(type_of_words.to_string()))?;
+
+    let options: HashMap<&str, &str> = options
+        .split_whitespace()
+        .map(|option| option.split_once('=').unwrap_or((option, "")))
+        .collect();
+    let filter = parse_list_filter(&|name| options.get(name).map(|value| value.to_string()))?;
+    let amount = match options.get("amount") {
+        Some(amount) => Some(parse_number("amount", amount)? as i32),
+        None => None,
+    };
+    let random = options.contains_key("random");
+
+    let list = translator.get_list(word_type, &filter, amount, random)?;
+    println!("{}", serde_json::to_string_pretty(&list)?);
+    Ok(())
+}
+
+fn display_help() {
+    println!("Commands:");
+    println!(".help - Displays this message");
+    println!(".exit - Exits the program");
+    println!(".switch - Switches between latin and english");
+    println!(".clear - Clears the screen");
+    println!(".history - Shows the lines entered, kept between sessions");
+    println!(".settings - Shows the current settings");
+    println!(".set <setting> <value> - Changes a setting, ex: .set max 10");
+    println!(".max <number> - The maximum number of translations per definition");
+    println!(".sort, .detailed, .tricks, .json [on|off] - Turns an option on or off, toggles without a value");
+    println!(".inflect <word or id> - Generates every inflected form of a latin word");
+    println!(".form <word> <form> - Generates one form, ex: .form rex ablative plural");
+    println!(
+        ".lookup <id> [english] - Gets a dictionary entry, or the english entries with the wid"
+    );
+    println!(".list <type> [random] [option=value] - Gets a list of words, ex: .list latin pos=noun amount=5");
+    println!(".search <query> - Searches the senses of latin entries");
+    println!(".derive <word> - Shows how a latin word is built from a dictionary word");
+    println!("enter a word to translate it, tab completes words from the dictionary");
+}
+
+// the first word and the rest of the text, ex: ".set max 10" -> (".set", "max 10")
+fn split_first(text: &str) -> (&str, &str) {
+    match text.trim().split_once(char::is_whitespace) {
+        Some((first, rest)) => (first, rest.trim()),
+        None => (text.trim(), ""),
+    }
+}
+
+// the history is kept in the home folder, without one it only lasts the session
+fn history_path() -> Option<PathBuf> {
+    std::env::var_os("HOME")
+        .or_else(|| std::env::var_os("USERPROFILE"))
+        .map(|home| PathBuf::from(home).join(HISTORY_FILE))
+}
+
+fn readline_error(error: ReadlineError) -> VocabError {
+    match error {
+        ReadlineError::Io(error) => VocabError::Io(error),
+        error => VocabError::Io(std::io::Error::other(error)),
+    }
+}
@@ -0,0 +1,212 @@
+use crate::dictionary_structures::dictionary_keys::{
+    Age, Area, Frequency, Geography, PartOfSpeech, Source,
+};
+use crate::dictionary_structures::dictionary_values::{Inflection, LatinWordInfo, WordInfo};
+use crate::error::VocabError;
+use std::fmt::Debug;
+
+/**
+ * Which dictionary entries to keep, by part of speech, length and word info.
+ * An empty list or None allows every value, ex: `WordFilter::new().with_age(vec![Age::Classical])`.
+ * The length filters are only used for lists, translations and searches ignore them.
+ * Entries tagged X for age, area, geography or source fit any requested value of that key.
+ */
+#[derive(Debug, Clone, Default)]
+pub struct WordFilter {
+    pub pos: Vec<PartOfSpeech>,
+    pub max_length: Option<usize>,
+    pub min_length: Option<usize>,
+    pub exact_length: Option<usize>,
+    pub age: Vec<Age>,
+    pub area: Vec<Area>,
+    pub geo: Vec<Geography>,
+    pub freq: Vec<Frequency>,
+    // the rarest frequency kept, ex: common keeps very frequent, frequent and common entries
+    pub min_freq: Option<Frequency>,
+    pub source: Vec<Source>,
+}
+
+impl WordFilter {
+    pub fn new() -> WordFilter {
+        WordFilter::default()
+    }
+
+    pub fn with_pos(mut self, pos: Vec<PartOfSpeech>) -> WordFilter {
+        self.pos = pos;
+        self
+    }
+
+    pub fn with_max_length(mut self, max_length: usize) -> WordFilter {
+        self.max_length = Some(max_length);
+        self
+    }
+
+    pub fn with_min_length(mut self, min_length: usize) -> WordFilter {
+        self.min_length = Some(min_length);
+        self
+    }
+
+    pub fn with_exact_length(mut self, exact_length: usize) -> WordFilter {
+        self.exact_length = Some(exact_length);
+        self
+    }
+
+    pub fn with_age(mut self, age: Vec<Age>) -> WordFilter {
+        self.age = age;
+        self
+    }
+
+    pub fn with_area(mut self, area: Vec<Area>) -> WordFilter {
+        self.area = area;
+        self
+    }
+
+    pub fn with_geo(mut self, geo: Vec<Geography>) -> WordFilter {
+        self.geo = geo;
+        self
+    }
+
+    pub fn with_freq(mut self, freq: Vec<Frequency>) -> WordFilter {
+        self.freq = freq;
+        self
+    }
+
+    pub fn with_min_freq(mut self, min_freq: Frequency) -> WordFilter {
+        self.min_freq = Some(min_freq);
+        self
+    }
+
+    pub fn with_source(mut self, source: Vec<Source>) -> WordFilter {
+        self.source = source;
+        self
+    }
+
+    /**
+     * If any of the age, area, geography, frequency or source filters is set
+     */
+    pub fn filters_info(&self) -> bool {
+        !self.age.is_empty()
+            || !self.area.is_empty()
+            || !self.geo.is_empty()
+            || !self.freq.is_empty()
+            || self.min_freq.is_some()
+            || !self.source.is_empty()
+    }
+
+    pub fn allows_pos(&self, pos: &PartOfSpeech) -> bool {
+        self.pos.is_empty() || self.pos.contains(pos)
+    }
+
+    pub fn allows_length(&self, orth: &str) -> bool {
+        let length = orth.len();
+        self.max_length.is_none_or(|max| length <= max)
+            && self.min_length.is_none_or(|min| length >= min)
+            && self.exact_length.is_none_or(|exact| length == exact)
+    }
+
+    pub fn allows_info(&self, info: &WordInfo) -> bool {
+        self.allows_age_and_freq(&info.age, &info.freq)
+            && allowed(&self.area, &info.area, Some(&Area::AllOrNone), Area::as_str)
+            && allowed(
+                &self.geo,
+                &info.geo,
+                Some(&Geography::AllOrNone),
+                Geography::as_str,
+            )
+            && allowed(
+                &self.source,
+                &info.source,
+                Some(&Source::General),
+                Source::as_str,
+            )
+    }
+
+    /**
+     * Inflections only have an age and a frequency, the other word info filters are ignored for them
+     */
+    pub fn allows_inflection(&self, inflection: &Inflection) -> bool {
+        self.allows_age_and_freq(&inflection.age, &inflection.freq)
+    }
+
+    fn allows_age_and_freq(&self, age: &Age, freq: &Frequency) -> bool {
+        allowed(&self.age, age, Some(&Age::UsedThroughoutAges), Age::as_str)
+            && allowed(&self.freq, freq, None, Frequency::as_str)
+            && self
+                .min_freq
+                .is_none_or(|min_freq| freq.as_number() <= min_freq.as_number())
+    }
+
+    /**
+     * The part of speech and length filters, used for every list
+     */
+    pub fn fits(&self, orth: &str, pos: &PartOfSpeech) -> bool {
+        self.allows_pos(pos) && self.allows_length(orth)
+    }
+
+    /**
+     * The part of speech and word info filters, used for translations and searches
+     */
+    pub fn allows(&self, word: &LatinWordInfo) -> bool {
+        self.allows_pos(&word.pos) && self.allows_info(&word.info)
+    }
+}
+
+// an empty list allows every value, and an entry tagged with the wildcard (X) fits any requested value
+fn allowed<T>(
+    values: &[T],
+    value: &T,
+    wildcard: Option<&T>,
+    as_str: fn(&T) -> &'static str,
+) -> bool {
+    values.is_empty()
+        || wildcard.is_some_and(|wildcard| as_str(wildcard) == as_str(value))
+        || values
+            .iter()
+            .any(|allowed| as_str(allowed) == as_str(value))
+}
+
+/**
+ * Reads comma separated values of a word info key as dictionary keys or names, ex: "C,D" or "classical, late" for ages.
+ * Names are case insensitive, words can be joined with - or _ and a name can be one side of a slash,
+ * ex: "very-frequent", "lewis-short", "rome" for Italy / Rome
+ */
+pub fn parse_keys<T: Debug>(
+    name: &str,
+    values: &str,
+    dict_key_to: fn(&str) -> T,
+    as_str: fn(&T) -> &'static str,
+) -> Result<Vec<T>, VocabError> {
+    let mut keys = Vec::new();
+
+    for value in values
+        .split(',')
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+    {
+        let name_value = value.to_lowercase().replace(['-', '_'], " ");
+        let compact_value = name_value.replace(' ', "");
+        let key = ('A'..='Z')
+            .map(|letter| (letter, dict_key_to(&letter.to_string())))
+            .filter(|(_, key)| as_str(key) != "unknown")
+            .find(|(letter, key)| {
+                let key_name = as_str(key).to_lowercase();
+                let key_name = key_name.split(" (").next().unwrap_or_default();
+                name_value == letter.to_lowercase().to_string()
+                    || compact_value == format!("{:?}", key).to_lowercase()
+                    || name_value == key_name
+                    || key_name.split(" / ").any(|part| name_value == part)
+            });
+
+        match key {
+            Some((_, key)) => keys.push(key),
+            None => {
+                return Err(VocabError::InvalidArgument {
+                    name: name.to_string(),
+                    value: value.to_string(),
+                })
+            }
+        }
+    }
+
+    Ok(keys)
+}
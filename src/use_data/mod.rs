@@ -4,16 +4,14 @@ use self::parsers::latin_dictionary_parser::parse_latin_dictionary;
 use self::parsers::latin_inflection_parser::parse_latin_inflections;
 use self::parsers::modifiers_parser::parse_modifiers;
 use self::parsers::stem_parser::parse_latin_stems;
-use crate::dictionary_structures::dictionary_keys::PartOfSpeech;
 use crate::dictionary_structures::dictionary_values::{
     Attachment, EnglishWordInfo, Inflection, LatinWordInfo, Modifier, Stem,
 };
-use crate::utils::data::{
-    get_latin_dictionary, get_latin_not_packons, get_latin_packons, get_latin_prefixes,
-    get_latin_suffixes, get_latin_tackons, get_latin_tickons, get_unique_latin_words,
-};
+use crate::error::VocabError;
+use crate::utils::data::Dictionary;
+use rand::seq::SliceRandom;
 use serde::Serialize;
-use serde_json;
+use std::str::FromStr;
 
 mod parsers {
     pub mod attachment_parser;
@@ -24,7 +22,9 @@ mod parsers {
     pub mod stem_parser;
 }
 
-mod utils;
+mod filter;
+
+pub use self::filter::{parse_keys, WordFilter};
 
 #[derive(Debug)]
 pub enum WordType {
@@ -41,8 +41,10 @@ pub enum WordType {
     UniqueLatin,
 }
 
-impl WordType {
-    pub fn from_str(s: &str) -> Result<WordType, String> {
+impl FromStr for WordType {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<WordType, String> {
         match s {
             "english" => Ok(WordType::English), // done
             "latin" => Ok(WordType::Latin),     // done
@@ -58,14 +60,32 @@ impl WordType {
             _ => Err(format!("Invalid word type: {}", s)),
         }
     }
+}
 
+impl WordType {
     pub fn is_valid_word_type(s: &str) -> bool {
-        match s {
-            "english" | "latin" | "inflections" | "inflection" | "not_packons" | "not_packon"
-            | "packon" | "packons" | "prefixes" | "prefix" | "stems" | "stem" | "suffixes"
-            | "suffix" | "tackons" | "tackon" | "tickons" | "tickon" | "unique_latin" => true,
-            _ => false,
-        }
+        matches!(
+            s,
+            "english"
+                | "latin"
+                | "inflections"
+                | "inflection"
+                | "not_packons"
+                | "not_packon"
+                | "packon"
+                | "packons"
+                | "prefixes"
+                | "prefix"
+                | "stems"
+                | "stem"
+                | "suffixes"
+                | "suffix"
+                | "tackons"
+                | "tackon"
+                | "tickons"
+                | "tickon"
+                | "unique_latin"
+        )
     }
 }
 
@@ -80,101 +100,161 @@ pub enum OutputList {
     Stems(Vec<Stem>),
 }
 
+/**
+ * The entries of a list that fit the filter. English words and stems are filtered by the word info
+ * of the Latin entry they belong to, inflections by their age and frequency and attachments ignore the part of speech.
+ */
 pub fn get_list(
+    dictionary: &Dictionary,
     word_type: WordType,
-    pos_list: Option<Vec<PartOfSpeech>>,
-    max: Option<i32>,
-    min: Option<i32>,
-    exact: Option<i32>,
+    filter: &WordFilter,
     amount: Option<i32>,
     random: bool,
-    display: bool,
-    to: Option<String>,
-) {
-    let list: OutputList = match word_type {
+) -> Result<OutputList, VocabError> {
+    let latin_entry_allowed = |wid: i32| match dictionary.get_latin_word_by_id(wid) {
+        Some(word) => filter.allows_info(&word.info),
+        None => !filter.filters_info(),
+    };
+    let attachment_filter = WordFilter {
+        pos: Vec::new(),
+        ..filter.clone()
+    };
+
+    let output_list = match word_type {
         WordType::English => {
-            let list = parse_english_dictionary(pos_list, max, min, exact, amount, random);
+            let english_words = dictionary
+                .english_words
+                .iter()
+                .filter(|word| latin_entry_allowed(word.wid))
+                .cloned()
+                .collect();
+            let list = parse_english_dictionary(english_words, filter, amount, random);
             OutputList::English(list)
         }
         WordType::Latin => {
-            let dictionary = get_latin_dictionary();
-            let list =
-                parse_latin_dictionary(dictionary, pos_list, max, min, exact, amount, random);
+            let latin_words = dictionary
+                .latin_words
+                .iter()
+                .filter(|word| filter.allows_info(&word.info))
+                .cloned()
+                .collect();
+            let list = parse_latin_dictionary(latin_words, filter, amount, random)?;
             OutputList::Latin(list)
         }
         WordType::Inflections => {
-            let list = parse_latin_inflections(pos_list, max, min, exact, amount, random);
+            let inflections = dictionary
+                .inflections
+                .iter()
+                .filter(|inflection| filter.allows_inflection(inflection))
+                .cloned()
+                .collect();
+            let list = parse_latin_inflections(inflections, filter, amount, random);
             OutputList::Inflections(list)
         }
         WordType::NotPackons => {
-            let attachments = get_latin_not_packons();
-            let list = parse_attachments(attachments, None, max, min, exact, amount, random);
+            let attachments = dictionary.not_packons.clone();
+            let list = parse_attachments(attachments, &attachment_filter, amount, random);
             OutputList::Attachment(list)
         }
         WordType::Packons => {
-            let attachments = get_latin_packons();
-            let list = parse_attachments(attachments, None, max, min, exact, amount, random);
+            let attachments = dictionary.packons.clone();
+            let list = parse_attachments(attachments, &attachment_filter, amount, random);
             OutputList::Attachment(list)
         }
         WordType::Prefixes => {
-            let modifiers = get_latin_prefixes();
-            let list = parse_modifiers(modifiers, pos_list, max, min, exact, amount, random);
+            let modifiers = dictionary.prefixes.clone();
+            let list = parse_modifiers(modifiers, filter, amount, random);
             OutputList::Modifiers(list)
         }
         WordType::Stems => {
-            let list = parse_latin_stems(pos_list, max, min, exact, amount, random);
+            let stems = dictionary
+                .stems
+                .iter()
+                .filter(|stem| latin_entry_allowed(stem.wid))
+                .cloned()
+                .collect();
+            let list = parse_latin_stems(stems, filter, amount, random);
             OutputList::Stems(list)
         }
         WordType::Suffixes => {
-            let modifiers = get_latin_suffixes();
-            let list = parse_modifiers(modifiers, pos_list, max, min, exact, amount, random);
+            let modifiers = dictionary.suffixes.clone();
+            let list = parse_modifiers(modifiers, filter, amount, random);
             OutputList::Modifiers(list)
         }
         WordType::Tackons => {
-            let attachments = get_latin_tackons();
-            let list = parse_attachments(attachments, None, max, min, exact, amount, random);
+            let attachments = dictionary.tackons.clone();
+            let list = parse_attachments(attachments, &attachment_filter, amount, random);
             OutputList::Attachment(list)
         }
         WordType::Tickons => {
-            let attachments = get_latin_tickons();
-            let list = parse_attachments(attachments, None, max, min, exact, amount, random);
+            let attachments = dictionary.tickons.clone();
+            let list = parse_attachments(attachments, &attachment_filter, amount, random);
             OutputList::Attachment(list)
         }
         WordType::UniqueLatin => {
-            let dictionary = get_unique_latin_words();
-            let list =
-                parse_latin_dictionary(dictionary, pos_list, max, min, exact, amount, random);
+            let unique_latin_words = dictionary
+                .unique_latin_words
+                .iter()
+                .filter(|word| filter.allows_info(&word.info))
+                .cloned()
+                .collect();
+            let list = parse_latin_dictionary(unique_latin_words, filter, amount, random)?;
             OutputList::Latin(list)
         }
     };
-    if display {
-        println!("{}", serde_json::to_string_pretty(&list).unwrap());
-    }
-
-    if to.is_some() {
-        let mut file_path = to.unwrap();
 
-        if !file_path.ends_with(".json") {
-            file_path.push_str(".json");
-        }
+    Ok(output_list)
+}
 
-        if std::path::Path::new(&file_path).exists() {
-            println!("File already exists, do you want to overwrite it? (y/n)");
-            let mut input = String::new();
-            std::io::stdin().read_line(&mut input).unwrap();
-            if input.trim() != "y" {
-                return;
-            }
-        }
+// up to amount of the entries in a random order, without repeats
+fn choose_random<T: Clone>(entries: &[T], amount: i32) -> Vec<T> {
+    entries
+        .choose_multiple(&mut rand::thread_rng(), amount.max(0) as usize)
+        .cloned()
+        .collect()
+}
 
-        let path = std::path::Path::new(&file_path);
+/**
+ * Random Latin entries with senses that fit the filter, without repeats or the excluded ids.
+ * Fewer are returned when there are not enough entries.
+ * The entries are shuffled before the principle parts are generated, so only the picked ones are cloned and generated
+ */
+pub fn random_latin_entries(
+    dictionary: &Dictionary,
+    filter: &WordFilter,
+    count: usize,
+    exclude: &[i32],
+) -> Result<Vec<LatinWordInfo>, VocabError> {
+    let mut eligible: Vec<&LatinWordInfo> = dictionary
+        .latin_words
+        .iter()
+        .filter(|word| {
+            // extension senses belong to the entry before
+            let has_senses = word
+                .senses
+                .first()
+                .is_some_and(|sense| !sense.starts_with('|'));
+            has_senses
+                && !exclude.contains(&word.id)
+                && filter.allows_info(&word.info)
+                && filter.allows_pos(&word.pos)
+        })
+        .collect();
+    eligible.shuffle(&mut rand::thread_rng());
 
-        if !path.exists() {
-            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+    let mut picked: Vec<LatinWordInfo> = Vec::new();
+    for word in eligible {
+        if picked.len() >= count {
+            break;
         }
 
-        let file = std::fs::File::create(&file_path).unwrap();
-        serde_json::to_writer_pretty(file, &list).unwrap();
-        println!("File created successfully at {}", file_path);
+        // the length filters apply to the principle parts
+        let mut word = word.clone();
+        word.generate_principle_parts()?;
+        if filter.allows_length(&word.orth) {
+            picked.push(word);
+        }
     }
+
+    Ok(picked)
 }
@@ -1,14 +1,9 @@
-use crate::dictionary_structures::dictionary_keys::PartOfSpeech;
 use crate::dictionary_structures::dictionary_values::Attachment;
-use crate::use_data::utils::word_fits_filters;
-use rand::Rng;
+use crate::use_data::{choose_random, WordFilter};
 
 pub fn parse_attachments(
     attachments: Vec<Attachment>,
-    pos_list: Option<Vec<PartOfSpeech>>,
-    max: Option<i32>,
-    min: Option<i32>,
-    exact: Option<i32>,
+    filter: &WordFilter,
     amount: Option<i32>,
     random: bool,
 ) -> Vec<Attachment> {
@@ -16,51 +11,27 @@ pub fn parse_attachments(
 
     if let Some(amount) = amount {
         if random {
-            let mut rng = rand::thread_rng();
-            while attachment_list.len() as i32 != amount {
-                let random_index = rng.gen_range(0..attachments.len());
-                let attachment_at_index = attachments[random_index].clone();
-                if !word_fits_filters(
-                    &attachment_at_index.orth,
-                    &attachment_at_index.pos,
-                    &pos_list,
-                    &max,
-                    &min,
-                    &exact,
-                ) {
-                    continue;
-                }
-                attachment_list.push(attachment_at_index);
-            }
+            // picked from every entry that fits, picking until enough fit would never end when too few do
+            let fitting: Vec<Attachment> = attachments
+                .into_iter()
+                .filter(|entry| filter.fits(&entry.orth, &entry.pos))
+                .collect();
+            attachment_list = choose_random(&fitting, amount);
         } else {
             for attachment in attachments {
-                if !word_fits_filters(
-                    &attachment.orth,
-                    &attachment.pos,
-                    &pos_list,
-                    &max,
-                    &min,
-                    &exact,
-                ) {
+                if !filter.fits(&attachment.orth, &attachment.pos) {
                     continue;
                 }
 
-                attachment_list.push(attachment);
-                if attachment_list.len() as i32 == amount {
+                if attachment_list.len() >= amount.max(0) as usize {
                     break;
                 }
+                attachment_list.push(attachment);
             }
         }
     } else {
         for attachment in attachments {
-            if !word_fits_filters(
-                &attachment.orth,
-                &attachment.pos,
-                &pos_list,
-                &max,
-                &min,
-                &exact,
-            ) {
+            if !filter.fits(&attachment.orth, &attachment.pos) {
                 continue;
             }
 
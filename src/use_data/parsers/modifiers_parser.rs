@@ -1,14 +1,9 @@
-use crate::dictionary_structures::dictionary_keys::PartOfSpeech;
 use crate::dictionary_structures::dictionary_values::Modifier;
-use crate::use_data::utils::word_fits_filters;
-use rand::Rng;
+use crate::use_data::{choose_random, WordFilter};
 
 pub fn parse_modifiers(
     modifiers: Vec<Modifier>,
-    pos_list: Option<Vec<PartOfSpeech>>,
-    max: Option<i32>,
-    min: Option<i32>,
-    exact: Option<i32>,
+    filter: &WordFilter,
     amount: Option<i32>,
     random: bool,
 ) -> Vec<Modifier> {
@@ -16,51 +11,27 @@ pub fn parse_modifiers(
 
     if let Some(amount) = amount {
         if random {
-            let mut rng = rand::thread_rng();
-            while modifier_list.len() as i32 != amount {
-                let random_index = rng.gen_range(0..modifiers.len());
-                let modifier_at_index = modifiers[random_index].clone();
-                if !word_fits_filters(
-                    &modifier_at_index.orth,
-                    &modifier_at_index.pos,
-                    &pos_list,
-                    &max,
-                    &min,
-                    &exact,
-                ) {
-                    continue;
-                }
-                modifier_list.push(modifier_at_index);
-            }
+            // picked from every entry that fits, picking until enough fit would never end when too few do
+            let fitting: Vec<Modifier> = modifiers
+                .into_iter()
+                .filter(|entry| filter.fits(&entry.orth, &entry.pos))
+                .collect();
+            modifier_list = choose_random(&fitting, amount);
         } else {
             for modifier in modifiers {
-                if !word_fits_filters(
-                    &modifier.orth,
-                    &modifier.pos,
-                    &pos_list,
-                    &max,
-                    &min,
-                    &exact,
-                ) {
+                if !filter.fits(&modifier.orth, &modifier.pos) {
                     continue;
                 }
 
-                modifier_list.push(modifier);
-                if modifier_list.len() as i32 == amount {
+                if modifier_list.len() >= amount.max(0) as usize {
                     break;
                 }
+                modifier_list.push(modifier);
             }
         }
     } else {
         for modifier in modifiers {
-            if !word_fits_filters(
-                &modifier.orth,
-                &modifier.pos,
-                &pos_list,
-                &max,
-                &min,
-                &exact,
-            ) {
+            if !filter.fits(&modifier.orth, &modifier.pos) {
                 continue;
             }
 
@@ -1,53 +1,37 @@
-use crate::dictionary_structures::dictionary_keys::PartOfSpeech;
 use crate::dictionary_structures::dictionary_values::Stem;
-use crate::use_data::utils::word_fits_filters;
-use crate::utils::data::get_latin_stems;
-use rand::Rng;
+use crate::use_data::{choose_random, WordFilter};
 
 pub fn parse_latin_stems(
-    pos_list: Option<Vec<PartOfSpeech>>,
-    max: Option<i32>,
-    min: Option<i32>,
-    exact: Option<i32>,
+    latin_stems: Vec<Stem>,
+    filter: &WordFilter,
     amount: Option<i32>,
     random: bool,
 ) -> Vec<Stem> {
-    let latin_stems = get_latin_stems();
     let mut stem_list: Vec<Stem> = Vec::new();
 
     if let Some(amount) = amount {
         if random {
-            let mut rng = rand::thread_rng();
-            while stem_list.len() as i32 != amount {
-                let random_index = rng.gen_range(0..latin_stems.len());
-                let stem_at_index = latin_stems[random_index].clone();
-                if !word_fits_filters(
-                    &stem_at_index.orth,
-                    &stem_at_index.pos,
-                    &pos_list,
-                    &max,
-                    &min,
-                    &exact,
-                ) {
-                    continue;
-                }
-                stem_list.push(stem_at_index);
-            }
+            // picked from every entry that fits, picking until enough fit would never end when too few do
+            let fitting: Vec<Stem> = latin_stems
+                .into_iter()
+                .filter(|entry| filter.fits(&entry.orth, &entry.pos))
+                .collect();
+            stem_list = choose_random(&fitting, amount);
         } else {
             for stem in latin_stems {
-                if !word_fits_filters(&stem.orth, &stem.pos, &pos_list, &max, &min, &exact) {
+                if !filter.fits(&stem.orth, &stem.pos) {
                     continue;
                 }
 
-                stem_list.push(stem);
-                if stem_list.len() as i32 == amount {
+                if stem_list.len() >= amount.max(0) as usize {
                     break;
                 }
+                stem_list.push(stem);
             }
         }
     } else {
         for stem in latin_stems {
-            if !word_fits_filters(&stem.orth, &stem.pos, &pos_list, &max, &min, &exact) {
+            if !filter.fits(&stem.orth, &stem.pos) {
                 continue;
             }
 
@@ -1,24 +1,34 @@
 use crate::dictionary_structures::dictionary_values::{
     Attachment, EnglishWordInfo, Inflection, LatinWordInfo, Modifier, Stem, UniqueLatinWordInfo,
 };
+use crate::error::VocabError;
+use crate::search::index::SenseIndex;
+use serde::de::DeserializeOwned;
+use std::collections::HashMap;
 use std::include_bytes;
+use std::sync::OnceLock;
 
-pub fn get_english_dictionary() -> Vec<EnglishWordInfo> {
-    let english_words_json = include_bytes!("../dictionary/english_words.json");
-    serde_json::from_slice(english_words_json).unwrap()
+fn load_json<T: DeserializeOwned>(file: &'static str, json: &[u8]) -> Result<Vec<T>, VocabError> {
+    serde_json::from_slice(json).map_err(|source| VocabError::DataLoad { file, source })
 }
 
-pub fn get_latin_dictionary() -> Vec<LatinWordInfo> {
-    let latin_words_json = include_bytes!("../dictionary/latin_dictionary.json");
-    serde_json::from_slice(latin_words_json).unwrap()
+pub fn get_english_dictionary() -> Result<Vec<EnglishWordInfo>, VocabError> {
+    let english_words_json = include_bytes!(concat!(env!("OUT_DIR"), "/english_words.json"));
+    load_json("english_words.json", english_words_json)
 }
 
-pub fn get_unique_latin_words() -> Vec<LatinWordInfo> {
-    let unique_latin_words_json = include_bytes!("../dictionary/unique_latin_words.json");
+pub fn get_latin_dictionary() -> Result<Vec<LatinWordInfo>, VocabError> {
+    let latin_words_json = include_bytes!(concat!(env!("OUT_DIR"), "/latin_dictionary.json"));
+    load_json("latin_dictionary.json", latin_words_json)
+}
+
+pub fn get_unique_latin_words() -> Result<Vec<LatinWordInfo>, VocabError> {
+    let unique_latin_words_json =
+        include_bytes!(concat!(env!("OUT_DIR"), "/unique_latin_words.json"));
     let unique_latin_words: Vec<UniqueLatinWordInfo> =
-        serde_json::from_slice(unique_latin_words_json).unwrap();
+        load_json("unique_latin_words.json", unique_latin_words_json)?;
 
-    unique_latin_words
+    Ok(unique_latin_words
         .iter()
         .map(|word| {
             let mut latin_word_info = LatinWordInfo::new();
@@ -30,45 +40,252 @@ pub fn get_unique_latin_words() -> Vec<LatinWordInfo> {
             latin_word_info.n = word.n.clone();
             latin_word_info
         })
-        .collect()
+        .collect())
+}
+
+pub fn get_latin_inflections() -> Result<Vec<Inflection>, VocabError> {
+    let latin_inflections_json =
+        include_bytes!(concat!(env!("OUT_DIR"), "/latin_inflections.json"));
+    load_json("latin_inflections.json", latin_inflections_json)
 }
 
-pub fn get_latin_inflections() -> Vec<Inflection> {
-    let latin_inflections_json = include_bytes!("../dictionary/latin_inflections.json");
-    serde_json::from_slice(latin_inflections_json).unwrap()
+pub fn get_latin_stems() -> Result<Vec<Stem>, VocabError> {
+    let latin_stems_json = include_bytes!(concat!(env!("OUT_DIR"), "/latin_stems.json"));
+    load_json("latin_stems.json", latin_stems_json)
 }
 
-pub fn get_latin_stems() -> Vec<Stem> {
-    let latin_stems_json = include_bytes!("../dictionary/latin_stems.json");
-    serde_json::from_slice(latin_stems_json).unwrap()
+pub fn get_latin_prefixes() -> Result<Vec<Modifier>, VocabError> {
+    let latin_prefixes_json = include_bytes!(concat!(env!("OUT_DIR"), "/latin_prefixes.json"));
+    load_json("latin_prefixes.json", latin_prefixes_json)
 }
 
-pub fn get_latin_prefixes() -> Vec<Modifier> {
-    let latin_prefixes_json = include_bytes!("../dictionary/latin_prefixes.json");
-    serde_json::from_slice(latin_prefixes_json).unwrap()
+pub fn get_latin_suffixes() -> Result<Vec<Modifier>, VocabError> {
+    let latin_suffixes_json = include_bytes!(concat!(env!("OUT_DIR"), "/latin_suffixes.json"));
+    load_json("latin_suffixes.json", latin_suffixes_json)
 }
 
-pub fn get_latin_suffixes() -> Vec<Modifier> {
-    let latin_suffixes_json = include_bytes!("../dictionary/latin_suffixes.json");
-    serde_json::from_slice(latin_suffixes_json).unwrap()
+pub fn get_latin_packons() -> Result<Vec<Attachment>, VocabError> {
+    let latin_packons_json = include_bytes!(concat!(env!("OUT_DIR"), "/latin_packons.json"));
+    load_json("latin_packons.json", latin_packons_json)
 }
 
-pub fn get_latin_packons() -> Vec<Attachment> {
-    let latin_packons_json = include_bytes!("../dictionary/latin_packons.json");
-    serde_json::from_slice(latin_packons_json).unwrap()
+pub fn get_latin_not_packons() -> Result<Vec<Attachment>, VocabError> {
+    let latin_not_packons_json =
+        include_bytes!(concat!(env!("OUT_DIR"), "/latin_not_packons.json"));
+    load_json("latin_not_packons.json", latin_not_packons_json)
 }
 
-pub fn get_latin_not_packons() -> Vec<Attachment> {
-    let latin_not_packons_json = include_bytes!("../dictionary/latin_not_packons.json");
-    serde_json::from_slice(latin_not_packons_json).unwrap()
+pub fn get_latin_tackons() -> Result<Vec<Attachment>, VocabError> {
+    let latin_tackons_json = include_bytes!(concat!(env!("OUT_DIR"), "/latin_tackons.json"));
+    load_json("latin_tackons.json", latin_tackons_json)
 }
 
-pub fn get_latin_tackons() -> Vec<Attachment> {
-    let latin_tackons_json = include_bytes!("../dictionary/latin_tackons.json");
-    serde_json::from_slice(latin_tackons_json).unwrap()
+pub fn get_latin_tickons() -> Result<Vec<Attachment>, VocabError> {
+    let latin_tickons_json = include_bytes!(concat!(env!("OUT_DIR"), "/latin_tickons.json"));
+    load_json("latin_tickons.json", latin_tickons_json)
 }
 
-pub fn get_latin_tickons() -> Vec<Attachment> {
-    let latin_tickons_json = include_bytes!("../dictionary/latin_tickons.json");
-    serde_json::from_slice(latin_tickons_json).unwrap()
+/**
+ * Every dictionary loaded once, with hash indexes for the lookups done while translating.
+ * Build it once and pass it by reference instead of calling the get_* functions per word.
+ */
+pub struct Dictionary {
+    pub latin_words: Vec<LatinWordInfo>,
+    pub english_words: Vec<EnglishWordInfo>,
+    pub unique_latin_words: Vec<LatinWordInfo>,
+    pub inflections: Vec<Inflection>,
+    pub stems: Vec<Stem>,
+    pub prefixes: Vec<Modifier>,
+    pub suffixes: Vec<Modifier>,
+    pub packons: Vec<Attachment>,
+    pub not_packons: Vec<Attachment>,
+    pub tackons: Vec<Attachment>,
+    pub tickons: Vec<Attachment>,
+    latin_words_by_id: HashMap<i32, usize>,
+    english_words_by_orth: HashMap<String, Vec<usize>>,
+    english_words_by_wid: HashMap<i32, Vec<usize>>,
+    unique_latin_words_by_orth: HashMap<String, usize>,
+    inflections_by_ending: HashMap<String, Vec<usize>>,
+    stems_by_orth: HashMap<String, Vec<usize>>,
+    stems_by_wid: HashMap<i32, Vec<usize>>,
+    longest_ending: usize,
+    // only built for sense searches
+    sense_index: OnceLock<SenseIndex>,
+}
+
+impl Dictionary {
+    pub fn load() -> Result<Dictionary, VocabError> {
+        let latin_words = get_latin_dictionary()?;
+        let english_words = get_english_dictionary()?;
+        let unique_latin_words = get_unique_latin_words()?;
+        let inflections = get_latin_inflections()?;
+        let stems = get_latin_stems()?;
+
+        let mut latin_words_by_id: HashMap<i32, usize> = HashMap::new();
+        for (index, word) in latin_words.iter().enumerate() {
+            latin_words_by_id.entry(word.id).or_insert(index);
+        }
+
+        let mut english_words_by_orth: HashMap<String, Vec<usize>> = HashMap::new();
+        let mut english_words_by_wid: HashMap<i32, Vec<usize>> = HashMap::new();
+        for (index, word) in english_words.iter().enumerate() {
+            english_words_by_orth
+                .entry(word.orth.to_lowercase())
+                .or_default()
+                .push(index);
+            english_words_by_wid
+                .entry(word.wid)
+                .or_default()
+                .push(index);
+        }
+
+        let mut unique_latin_words_by_orth: HashMap<String, usize> = HashMap::new();
+        for (index, word) in unique_latin_words.iter().enumerate() {
+            unique_latin_words_by_orth
+                .entry(word.orth.to_lowercase())
+                .or_insert(index);
+        }
+
+        let mut inflections_by_ending: HashMap<String, Vec<usize>> = HashMap::new();
+        let mut longest_ending = 0;
+        for (index, inflection) in inflections.iter().enumerate() {
+            longest_ending = longest_ending.max(inflection.ending.len());
+            inflections_by_ending
+                .entry(inflection.ending.to_string())
+                .or_default()
+                .push(index);
+        }
+
+        let mut stems_by_orth: HashMap<String, Vec<usize>> = HashMap::new();
+        let mut stems_by_wid: HashMap<i32, Vec<usize>> = HashMap::new();
+        for (index, stem) in stems.iter().enumerate() {
+            stems_by_orth
+                .entry(stem.orth.to_string())
+                .or_default()
+                .push(index);
+            stems_by_wid.entry(stem.wid).or_default().push(index);
+        }
+
+        Ok(Dictionary {
+            latin_words,
+            english_words,
+            unique_latin_words,
+            inflections,
+            stems,
+            prefixes: get_latin_prefixes()?,
+            suffixes: get_latin_suffixes()?,
+            packons: get_latin_packons()?,
+            not_packons: get_latin_not_packons()?,
+            tackons: get_latin_tackons()?,
+            tickons: get_latin_tickons()?,
+            latin_words_by_id,
+            english_words_by_orth,
+            english_words_by_wid,
+            unique_latin_words_by_orth,
+            inflections_by_ending,
+            stems_by_orth,
+            stems_by_wid,
+            longest_ending,
+            sense_index: OnceLock::new(),
+        })
+    }
+
+    pub fn get_latin_word_by_id(&self, id: i32) -> Option<&LatinWordInfo> {
+        self.latin_words_by_id
+            .get(&id)
+            .map(|index| &self.latin_words[*index])
+    }
+
+    /**
+     * Case insensitive, returns the entries in dictionary order
+     */
+    pub fn get_english_words_by_orth(&self, orth: &str) -> Vec<&EnglishWordInfo> {
+        match self.english_words_by_orth.get(&orth.to_lowercase()) {
+            Some(indexes) => indexes
+                .iter()
+                .map(|index| &self.english_words[*index])
+                .collect(),
+            None => Vec::new(),
+        }
+    }
+
+    /**
+     * The English entries that translate the Latin entry with the id, in dictionary order
+     */
+    pub fn get_english_words_by_wid(&self, wid: i32) -> Vec<&EnglishWordInfo> {
+        match self.english_words_by_wid.get(&wid) {
+            Some(indexes) => indexes
+                .iter()
+                .map(|index| &self.english_words[*index])
+                .collect(),
+            None => Vec::new(),
+        }
+    }
+
+    pub fn get_unique_latin_word(&self, orth: &str) -> Option<&LatinWordInfo> {
+        self.unique_latin_words_by_orth
+            .get(&orth.to_lowercase())
+            .map(|index| &self.unique_latin_words[*index])
+    }
+
+    pub fn get_stems_by_orth(&self, orth: &str) -> Vec<&Stem> {
+        match self.stems_by_orth.get(orth) {
+            Some(indexes) => indexes.iter().map(|index| &self.stems[*index]).collect(),
+            None => Vec::new(),
+        }
+    }
+
+    pub fn get_stems_by_wid(&self, wid: i32) -> Vec<&Stem> {
+        match self.stems_by_wid.get(&wid) {
+            Some(indexes) => indexes.iter().map(|index| &self.stems[*index]).collect(),
+            None => Vec::new(),
+        }
+    }
+
+    /**
+     * The inverted index over the senses of the Latin entries, built the first time it is needed
+     */
+    pub fn get_sense_index(&self) -> &SenseIndex {
+        self.sense_index.get_or_init(|| SenseIndex::build(self))
+    }
+
+    /**
+     * Every distinct stem spelling, in no particular order
+     */
+    pub fn get_stem_orths(&self) -> impl Iterator<Item = &str> {
+        self.stems_by_orth.keys().map(|orth| orth.as_str())
+    }
+
+    /**
+     * Every distinct inflection ending, in no particular order
+     */
+    pub fn get_endings(&self) -> impl Iterator<Item = &str> {
+        self.inflections_by_ending
+            .keys()
+            .map(|ending| ending.as_str())
+    }
+
+    /**
+     * Returns every inflection whose ending the word ends with, in the order of the inflection list
+     */
+    pub fn get_inflections_ending(&self, latin_word: &str) -> Vec<&Inflection> {
+        let mut indexes: Vec<usize> = Vec::new();
+
+        for split_at in (0..=latin_word.len()).filter(|i| latin_word.is_char_boundary(*i)) {
+            let ending = &latin_word[split_at..];
+            if ending.len() > self.longest_ending {
+                continue;
+            }
+
+            if let Some(matching) = self.inflections_by_ending.get(ending) {
+                indexes.extend(matching);
+            }
+        }
+
+        indexes.sort_unstable();
+        indexes
+            .iter()
+            .map(|index| &self.inflections[*index])
+            .collect()
+    }
 }
@@ -1,6 +1,9 @@
 pub mod data;
 pub mod principle_part_generator;
 pub mod type_translator;
+pub mod vowel_quantity;
+
+use self::vowel_quantity::fold_vowel_marks;
 
 /**
  * Returns the number with the appropriate ending
@@ -9,7 +12,7 @@ pub mod type_translator;
 pub fn number_with_ending(number: i8) -> String {
     let last_digit = number % 10;
     let last_two_digits = number % 100;
-    if last_two_digits >= 11 && last_two_digits <= 13 {
+    if (11..=13).contains(&last_two_digits) {
         return format!("{}th", number);
     }
     match last_digit {
@@ -21,11 +24,11 @@ pub fn number_with_ending(number: i8) -> String {
 }
 
 /**
- * Removes all non-alphanumeric characters from a string
+ * Removes all non-alphanumeric characters from a string and replaces macrons, breves and diaeresis with the plain vowel
  */
 pub fn sanitize_word(word: &str) -> String {
     let mut word = word.to_owned();
-    word = word.trim().to_lowercase();
+    word = fold_vowel_marks(word.trim()).to_lowercase();
 
     // allows for translation of numbers to roman numerals
     if contains_number(word.clone()) && !is_all_numbers(&word) {
@@ -60,17 +63,14 @@ pub fn remove_non_alphanumeric(word: String) -> String {
 }
 
 pub fn is_vowel(c: char) -> bool {
-    match c {
-        'a' | 'e' | 'i' | 'o' | 'u' => true,
-        _ => false,
-    }
+    matches!(c, 'a' | 'e' | 'i' | 'o' | 'u')
 }
 
 pub fn is_roman_digit(c: char) -> bool {
-    match c.to_ascii_uppercase() {
-        'I' | 'V' | 'X' | 'L' | 'C' | 'D' | 'M' => true,
-        _ => false,
-    }
+    matches!(
+        c.to_ascii_uppercase(),
+        'I' | 'V' | 'X' | 'L' | 'C' | 'D' | 'M'
+    )
 }
 
 pub fn is_roman_number(possible_roman_number: &str) -> bool {
@@ -94,7 +94,7 @@ pub fn translate_roman_digit_to_number(c: char) -> Result<i32, String> {
         'C' => Ok(100),
         'D' => Ok(500),
         'M' => Ok(1000),
-        _ => return Err(format!("{} is an invalid roman numeral digit", c)),
+        _ => Err(format!("{} is an invalid roman numeral digit", c)),
     }
 }
 
@@ -1,4 +1,5 @@
 use crate::dictionary_structures::dictionary_keys::Comparison;
+use crate::error::VocabError;
 use crate::utils::principle_part_generator::set_principle_parts;
 
 pub fn generate_for_adjectives(
@@ -6,7 +7,7 @@ pub fn generate_for_adjectives(
     num_type_2: i8,
     parts: Vec<String>,
     comparison: Comparison,
-) -> Vec<String> {
+) -> Result<Vec<String>, VocabError> {
     match comparison {
         Comparison::Comparative => {
             set_principle_parts(parts, vec![("or", 1), ("or", 1), ("us", 1)], None)
@@ -48,7 +49,7 @@ pub fn generate_for_adjectives(
                 (9, 9) => {
                     set_principle_parts(parts, vec![("", 0), ("", 0), ("", 0)], Some("undeclined"))
                 }
-                _ => parts,
+                _ => Ok(parts),
             }
         }
         Comparison::Unknown => {
@@ -99,7 +100,7 @@ pub fn generate_for_adjectives(
                 (9, 9) => {
                     set_principle_parts(parts, vec![("", 0), ("", 0), ("", 0)], Some("undeclined"))
                 }
-                _ => parts,
+                _ => Ok(parts),
             }
         }
     }
@@ -1,4 +1,5 @@
 use crate::dictionary_structures::dictionary_keys::Gender;
+use crate::error::VocabError;
 use crate::utils::principle_part_generator::set_principle_parts;
 
 pub fn generate_for_nouns(
@@ -6,7 +7,7 @@ pub fn generate_for_nouns(
     num_type_2: i8,
     gender: Gender,
     parts: Vec<String>,
-) -> Vec<String> {
+) -> Result<Vec<String>, VocabError> {
     match (num_type_1, num_type_2) {
         // first declension
         (1, 1) => set_principle_parts(parts, vec![("a", 1), ("ae", 2)], None),
@@ -24,7 +25,7 @@ pub fn generate_for_nouns(
             } else if gender == Gender::Neuter {
                 set_principle_parts(parts, vec![("um", 1), ("(i)", 2)], None)
             } else {
-                parts
+                Ok(parts)
             }
         }
         (2, 5) => set_principle_parts(parts, vec![("us", 1), ("", 2)], None),
@@ -45,6 +46,6 @@ pub fn generate_for_nouns(
         // special
         (9, 8) => set_principle_parts(parts, vec![("", 0), ("", 0)], Some("abbreviation")),
         (9, 9) => set_principle_parts(parts, vec![("", 0), ("", 0)], Some("undeclined")),
-        _ => parts,
+        _ => Ok(parts),
     }
 }
@@ -1,4 +1,5 @@
 use crate::dictionary_structures::dictionary_keys::Numeral;
+use crate::error::VocabError;
 use crate::utils::principle_part_generator::set_principle_parts;
 
 pub fn generate_for_numerals(
@@ -6,7 +7,7 @@ pub fn generate_for_numerals(
     num_type_2: i8,
     parts: Vec<String>,
     numeral_type: Numeral,
-) -> Vec<String> {
+) -> Result<Vec<String>, VocabError> {
     match numeral_type {
         Numeral::Unknown | Numeral::Adverbial => match (num_type_1, num_type_2) {
             (1, 1) => set_principle_parts(
@@ -47,7 +48,7 @@ pub fn generate_for_numerals(
                         None,
                     )
                 } else {
-                    parts
+                    Ok(parts)
                 }
             }
         },
@@ -56,7 +57,7 @@ pub fn generate_for_numerals(
             (1, 2) => set_principle_parts(parts, vec![("o", 1), ("ae", 1), ("o", 1)], None),
             (1, 3) => set_principle_parts(parts, vec![("es", 1), ("es", 1), ("ia", 1)], None),
             (1, 4) => set_principle_parts(parts, vec![("i", 1), ("ae", 1), ("a", 1)], None),
-            _ => parts,
+            _ => Ok(parts),
         },
         Numeral::Ordinal => set_principle_parts(parts, vec![("us", 1), ("a", 1), ("um", 1)], None),
         Numeral::Distributive => {
@@ -1,6 +1,11 @@
+use crate::error::VocabError;
 use crate::utils::principle_part_generator::set_principle_parts;
 
-pub fn generate_for_pronouns(num_type_1: i8, num_type_2: i8, parts: Vec<String>) -> Vec<String> {
+pub fn generate_for_pronouns(
+    num_type_1: i8,
+    num_type_2: i8,
+    parts: Vec<String>,
+) -> Result<Vec<String>, VocabError> {
     match (num_type_1, num_type_2) {
         // proximal demonstrative pronouns (hic, haec hoc)
         (3, 1) => set_principle_parts(parts, vec![("ic", 1), ("aec", 1), ("oc", 1)], None),
@@ -15,6 +20,6 @@ pub fn generate_for_pronouns(num_type_1: i8, num_type_2: i8, parts: Vec<String>)
         // special
         (9, 8) => set_principle_parts(parts, vec![("", 0), ("", 0), ("", 0)], Some("abbreviation")),
         (9, 9) => set_principle_parts(parts, vec![("", 0), ("", 0), ("", 0)], Some("undeclined")),
-        _ => parts,
+        _ => Ok(parts),
     }
 }
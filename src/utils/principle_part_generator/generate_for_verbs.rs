@@ -1,4 +1,5 @@
 use crate::dictionary_structures::dictionary_keys::Verb;
+use crate::error::VocabError;
 use crate::utils::principle_part_generator::set_principle_parts;
 
 pub fn generate_for_verbs(
@@ -6,7 +7,7 @@ pub fn generate_for_verbs(
     num_type_2: i8,
     parts: Vec<String>,
     verb_type: Verb,
-) -> Vec<String> {
+) -> Result<Vec<String>, VocabError> {
     if num_type_1 == 9 && num_type_2 == 8 {
         return set_principle_parts(
             parts,
@@ -50,7 +51,7 @@ pub fn generate_for_verbs(
                     )
                 }
             }
-            _ => parts,
+            _ => Ok(parts),
         },
         Verb::PerfectDefinite => {
             set_principle_parts(parts, vec![("i", 3), ("isse", 3), ("us", 4), ("", 0)], None)
@@ -102,7 +103,7 @@ pub fn generate_for_verbs(
                                 None,
                             )
                         } else {
-                            parts
+                            Ok(parts)
                         }
                     }
                     7 => {
@@ -113,10 +114,10 @@ pub fn generate_for_verbs(
                                 None,
                             )
                         } else {
-                            parts
+                            Ok(parts)
                         }
                     }
-                    _ => parts,
+                    _ => Ok(parts),
                 }
             } else {
                 // building array instead of each case, because lots of options / overlap
@@ -10,7 +10,10 @@ use self::generate_for_nouns::generate_for_nouns;
 use self::generate_for_numerals::generate_for_numerals;
 use self::generate_for_pronouns::generate_for_pronouns;
 use self::generate_for_verbs::generate_for_verbs;
-use crate::dictionary_structures::dictionary_keys::{Comparison, Gender, Numeral, Verb};
+use crate::dictionary_structures::dictionary_keys::{
+    Comparison, Gender, Numeral, PartOfSpeech, Verb,
+};
+use crate::error::VocabError;
 
 pub enum Generator {
     Noun,
@@ -21,6 +24,7 @@ pub enum Generator {
 }
 
 // fn takes in Generator, and required args for all generators, then Options for values specific to each generator
+#[allow(clippy::too_many_arguments)]
 pub fn generate_principle_parts(
     generator: Generator,
     num_type_1: i8,
@@ -30,36 +34,35 @@ pub fn generate_principle_parts(
     comparison: Option<Comparison>,
     verb_type: Option<Verb>,
     numeral_type: Option<Numeral>,
-) -> Vec<String> {
+) -> Result<Vec<String>, VocabError> {
     match generator {
         Generator::Noun => {
-            if gender.is_none() {
-                println!("A gender is required for generating principle parts for a noun, but none was provided");
-                std::process::exit(0);
-            }
-
-            generate_for_nouns(num_type_1, num_type_2, gender.unwrap(), parts)
+            let gender = gender.ok_or(VocabError::MissingPrinciplePartValue {
+                part_of_speech: PartOfSpeech::Noun,
+                value: "gender",
+            })?;
+            generate_for_nouns(num_type_1, num_type_2, gender, parts)
         }
         Generator::Adjective => {
-            if comparison.is_none() {
-                println!("A comparison is required for generating principle parts for an adjective, but none was provided");
-                std::process::exit(0);
-            }
-            generate_for_adjectives(num_type_1, num_type_2, parts, comparison.unwrap())
+            let comparison = comparison.ok_or(VocabError::MissingPrinciplePartValue {
+                part_of_speech: PartOfSpeech::Adjective,
+                value: "comparison",
+            })?;
+            generate_for_adjectives(num_type_1, num_type_2, parts, comparison)
         }
         Generator::Verb => {
-            if verb_type.is_none() {
-                println!("A verb type is required for generating principle parts for a verb, but none was provided");
-                std::process::exit(0);
-            }
-            generate_for_verbs(num_type_1, num_type_2, parts, verb_type.unwrap())
+            let verb_type = verb_type.ok_or(VocabError::MissingPrinciplePartValue {
+                part_of_speech: PartOfSpeech::Verb,
+                value: "verb type",
+            })?;
+            generate_for_verbs(num_type_1, num_type_2, parts, verb_type)
         }
         Generator::Numeral => {
-            if numeral_type.is_none() {
-                println!("A numeral type is required for generating principle parts for a numeral, but none was provided");
-                std::process::exit(0);
-            }
-            generate_for_numerals(num_type_1, num_type_2, parts, numeral_type.unwrap())
+            let numeral_type = numeral_type.ok_or(VocabError::MissingPrinciplePartValue {
+                part_of_speech: PartOfSpeech::Numeral,
+                value: "numeral type",
+            })?;
+            generate_for_numerals(num_type_1, num_type_2, parts, numeral_type)
         }
         Generator::Pronoun => generate_for_pronouns(num_type_1, num_type_2, parts),
     }
@@ -69,15 +72,14 @@ pub fn set_principle_parts(
     parts: Vec<String>,
     endings: Vec<(&str, i8)>,
     special_case: Option<&str>,
-) -> Vec<String> {
+) -> Result<Vec<String>, VocabError> {
     let mut principle_parts = Vec::new();
 
-    if endings.iter().all(|x| x.0 == "" && x.1 == 0) {
-        if special_case.is_none() {
-            println!("No Endings or Special Case provided");
-            std::process::exit(0);
-        }
-        return vec![parts[0].clone() + " | " + special_case.unwrap_or("")];
+    if endings.iter().all(|x| x.0.is_empty() && x.1 == 0) {
+        return match special_case {
+            Some(special_case) => Ok(vec![parts[0].clone() + " | " + special_case]),
+            None => Err(VocabError::NoPrinciplePartEndings(parts[0].clone())),
+        };
     }
 
     // number in ending is referring to principle part number to add ending to
@@ -85,17 +87,17 @@ pub fn set_principle_parts(
         let ending_to_add_to_part = ending.0;
         let part_to_add_ending_to = ending.1;
 
-        if ending_to_add_to_part == "" && part_to_add_ending_to == 0 {
+        if ending_to_add_to_part.is_empty() && part_to_add_ending_to == 0 {
             principle_parts.push("---".to_string());
             continue;
         }
 
-        if ending_to_add_to_part != "" && part_to_add_ending_to == 0 {
+        if !ending_to_add_to_part.is_empty() && part_to_add_ending_to == 0 {
             principle_parts.push(ending_to_add_to_part.to_string());
             continue;
         }
 
-        let mut part = if part_to_add_ending_to as usize - 1 >= parts.len() {
+        let mut part = if part_to_add_ending_to as usize > parts.len() {
             "---".to_string()
         } else {
             parts[part_to_add_ending_to as usize - 1].to_string()
@@ -109,5 +111,5 @@ pub fn set_principle_parts(
         part.push_str(ending_to_add_to_part);
         principle_parts.push(part);
     }
-    principle_parts
+    Ok(principle_parts)
 }
@@ -4,14 +4,12 @@ use crate::dictionary_structures::dictionary_values::NValue;
 pub fn translate_type(number_types: NValue, pos: PartOfSpeech) -> String {
     let num_type_1 = number_types.get_n_value_1();
 
-    let type_name = match pos {
+    match pos {
         PartOfSpeech::Noun => translate_noun_type(num_type_1),
         PartOfSpeech::Verb => translate_verb_type(num_type_1),
         PartOfSpeech::Adjective => translate_adjective_type(num_type_1),
         _ => "unknown".to_string(),
-    };
-
-    type_name
+    }
 }
 
 fn translate_noun_type(num_type_1: i8) -> String {
@@ -0,0 +1,259 @@
+// Whitaker's data has no vowel lengths, so only the long vowels that follow from the declension
+// or conjugation of a word are marked, the stems are left as they are.
+use crate::dictionary_structures::dictionary_keys::{
+    Declension, Gender, Mood, Number, PartOfSpeech, Tense, Verb, Voice,
+};
+use crate::dictionary_structures::dictionary_values::{Form, Inflection, LatinWordInfo, LongForm};
+
+/**
+ * Replaces vowels with macrons, breves or a diaeresis with the plain vowel, ex: puellārum -> puellarum, poëta -> poeta.
+ * Combining marks (a + U+0304) are removed as well.
+ */
+pub fn fold_vowel_marks(word: &str) -> String {
+    word.chars()
+        .filter(|c| !matches!(c, '\u{0304}' | '\u{0306}' | '\u{0308}'))
+        .map(|c| match c {
+            'ā' | 'ă' | 'ä' => 'a',
+            'ē' | 'ĕ' | 'ë' => 'e',
+            'ī' | 'ĭ' | 'ï' => 'i',
+            'ō' | 'ŏ' | 'ö' => 'o',
+            'ū' | 'ŭ' | 'ü' => 'u',
+            'ȳ' | 'ÿ' => 'y',
+            'Ā' | 'Ă' | 'Ä' => 'A',
+            'Ē' | 'Ĕ' | 'Ë' => 'E',
+            'Ī' | 'Ĭ' | 'Ï' => 'I',
+            'Ō' | 'Ŏ' | 'Ö' => 'O',
+            'Ū' | 'Ŭ' | 'Ü' => 'U',
+            'Ȳ' | 'Ÿ' => 'Y',
+            c => c,
+        })
+        .collect()
+}
+
+/**
+ * Marks the long vowels of the endings of the principle parts, ex: amo, amāre, amāvī, amātus.
+ * Should be called after the principle parts are generated.
+ */
+pub fn mark_principle_parts(word: &mut LatinWordInfo) {
+    let (n_value_1, n_value_2) = match word.n.as_deref() {
+        Some([n_1, n_2, ..]) => (n_1.get_n_value_1(), n_2.get_n_value_2()),
+        _ => return,
+    };
+    let parts = &mut word.parts;
+    if parts.len() < 2 {
+        return;
+    }
+
+    match word.pos {
+        PartOfSpeech::Noun => {
+            let genitive = match n_value_1 {
+                2 => Some(("i", "ī")),
+                4 => Some(("us", "ūs")),
+                5 => Some(fifth_declension_ei(&parts[1])),
+                _ => None,
+            };
+            if let Some((ending, marked)) = genitive {
+                mark_end(&mut parts[1], ending, marked);
+            }
+        }
+        PartOfSpeech::Verb => {
+            let deponent = match &word.form {
+                Form::LongForm(form) => matches!(form.verb, Some(Verb::Deponent)),
+                Form::StrForm(form) => form.contains("DEP"),
+            };
+            let conjugation_vowel = match (n_value_1, n_value_2) {
+                (1, _) => Some(("a", "ā")),
+                (2, _) => Some(("e", "ē")),
+                (3, 4) => Some(("i", "ī")),
+                _ => None,
+            };
+
+            if deponent {
+                match conjugation_vowel {
+                    Some((vowel, marked)) => mark_end(
+                        &mut parts[1],
+                        &format!("{}ri", vowel),
+                        &format!("{}rī", marked),
+                    ),
+                    None if n_value_1 == 3 => mark_end(&mut parts[1], "i", "ī"),
+                    None => {}
+                }
+                if parts.len() >= 4 {
+                    if let Some((vowel, marked)) = conjugation_vowel.filter(|_| n_value_1 != 2) {
+                        mark_end(
+                            &mut parts[3],
+                            &format!("{}tus sum", vowel),
+                            &format!("{}tus sum", marked),
+                        );
+                    }
+                }
+                return;
+            }
+
+            if let Some((vowel, marked)) = conjugation_vowel {
+                mark_end(
+                    &mut parts[1],
+                    &format!("{}re", vowel),
+                    &format!("{}re", marked),
+                );
+            }
+            if parts.len() >= 3 {
+                if let Some((vowel, marked)) = conjugation_vowel.filter(|_| n_value_1 != 2) {
+                    mark_end(
+                        &mut parts[2],
+                        &format!("{}vi", vowel),
+                        &format!("{}vī", marked),
+                    );
+                }
+                mark_end(&mut parts[2], "i", "ī");
+            }
+            if parts.len() >= 4 {
+                if let Some((vowel, marked)) = conjugation_vowel.filter(|_| n_value_1 != 2) {
+                    mark_end(
+                        &mut parts[3],
+                        &format!("{}tus", vowel),
+                        &format!("{}tus", marked),
+                    );
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+/**
+ * Marks the long vowels of an ending from its declension or conjugation, ex: puell.ārum, vir.ōs, am.āre.
+ * Should be called after the form is converted to a long form, the word is used when the inflection
+ * is shared by every declension or conjugation.
+ */
+pub fn mark_inflection(inflection: &mut Inflection, word: &LatinWordInfo, stem: &str) {
+    let form = match &inflection.form {
+        Form::LongForm(form) => form,
+        Form::StrForm(_) => return,
+    };
+    let (n_value_1, n_value_2) = match (inflection.n.as_deref(), word.n.as_deref()) {
+        (Some([n_1, n_2, ..]), _) if n_1.get_n_value_1() != 0 => {
+            (n_1.get_n_value_1(), n_2.get_n_value_2())
+        }
+        (_, Some([n_1, n_2, ..])) => (n_1.get_n_value_1(), n_2.get_n_value_2()),
+        _ => return,
+    };
+
+    let marked = match inflection.pos {
+        PartOfSpeech::Noun => long_noun_ending(n_value_1, form, &inflection.ending, stem),
+        PartOfSpeech::Adjective => {
+            // 1st and 2nd declension adjectives are 1st declension when feminine
+            let declension = match (n_value_1, form.gender) {
+                (1, Some(Gender::Feminine)) => 1,
+                (1, _) => 2,
+                (3, _) => 3,
+                _ => 0,
+            };
+            long_noun_ending(declension, form, &inflection.ending, stem)
+        }
+        PartOfSpeech::Verb => long_verb_ending(n_value_1, n_value_2, form, &inflection.ending),
+        _ => None,
+    };
+
+    if let Some(marked) = marked {
+        inflection.ending = marked.to_string();
+    }
+}
+
+fn long_noun_ending(
+    declension: i8,
+    form: &LongForm,
+    ending: &str,
+    stem: &str,
+) -> Option<&'static str> {
+    use Declension::{Ablative, Accusative, Dative, Genitive, Nominative, Vocative};
+    use Number::{Plural, Singular};
+
+    let (case, number) = (form.declension?, form.number?);
+    let marked = match (declension, case, number, ending) {
+        (1, Ablative, Singular, "a") => "ā",
+        (1, Genitive, Plural, "arum") => "ārum",
+        (1, Dative | Ablative, Plural, "is") => "īs",
+        (1, Accusative, Plural, "as") => "ās",
+        (2, Genitive, Singular, "i") => "ī",
+        (2, Dative | Ablative, Singular, "o") => "ō",
+        (2, Nominative | Vocative, Plural, "i") => "ī",
+        (2, Genitive, Plural, "orum") => "ōrum",
+        (2, Dative | Ablative, Plural, "is") => "īs",
+        (2, Accusative, Plural, "os") => "ōs",
+        (3, Dative | Ablative, Singular, "i") => "ī",
+        (3, Nominative | Accusative | Vocative, Plural, "es") => "ēs",
+        (3, Accusative, Plural, "is") => "īs",
+        (4, Genitive, Singular, "us") => "ūs",
+        (4, Ablative, Singular, "u") => "ū",
+        (4, Nominative | Accusative | Vocative, Plural, "us") => "ūs",
+        (5, Genitive | Dative, Singular, "ei") => fifth_declension_ei(stem).1,
+        (5, Ablative, Singular, "e") => "ē",
+        (5, Nominative | Accusative | Vocative, Plural, "es") => "ēs",
+        (5, Genitive, Plural, "erum") => "ērum",
+        (5, Dative | Ablative, Plural, "ebus") => "ēbus",
+        _ => return None,
+    };
+
+    Some(marked)
+}
+
+fn long_verb_ending(
+    conjugation: i8,
+    variant: i8,
+    form: &LongForm,
+    ending: &str,
+) -> Option<&'static str> {
+    use Mood::{Imperative, Indicative, Infinitive};
+    use Tense::{Perfect, Present};
+    use Voice::{Active, Passive};
+
+    let first_person_singular = form.person.as_deref() == Some("1st person")
+        && matches!(form.number, Some(Number::Singular));
+    let marked = match (form.tense?, form.voice?, form.mood?, ending) {
+        // the infinitive, the passive imperative and the passive 2nd person singular
+        (Present, _, Infinitive | Imperative | Indicative, "are") if conjugation == 1 => "āre",
+        (Present, _, Infinitive | Imperative | Indicative, "ere") if conjugation == 2 => "ēre",
+        (Present, _, Infinitive | Imperative | Indicative, "ire")
+            if conjugation == 3 && variant == 4 =>
+        {
+            "īre"
+        }
+        (Present, Passive, Infinitive, "ari") if conjugation == 1 => "ārī",
+        (Present, Passive, Infinitive, "eri") if conjugation == 2 => "ērī",
+        (Present, Passive, Infinitive, "iri") if conjugation == 3 && variant == 4 => "īrī",
+        (Present, Passive, Infinitive, "i") if conjugation == 3 => "ī",
+        (Present, Active, Indicative, "as") if conjugation == 1 => "ās",
+        (Present, Active, Indicative, "amus") if conjugation == 1 => "āmus",
+        (Present, Active, Indicative, "atis") if conjugation == 1 => "ātis",
+        (Present, Active, Indicative, "es") if conjugation == 2 => "ēs",
+        (Present, Active, Indicative, "emus") if conjugation == 2 => "ēmus",
+        (Present, Active, Indicative, "etis") if conjugation == 2 => "ētis",
+        (Present, Active, Indicative, "is") if conjugation == 3 && variant == 4 => "īs",
+        (Present, Active, Indicative, "imus") if conjugation == 3 && variant == 4 => "īmus",
+        (Present, Active, Indicative, "itis") if conjugation == 3 && variant == 4 => "ītis",
+        (Perfect, Active, Indicative, "i") if first_person_singular => "ī",
+        (Perfect, Active, Indicative, "erunt") => "ērunt",
+        (Perfect, Active, Indicative, "ere") => "ēre",
+        _ => return None,
+    };
+
+    Some(marked)
+}
+
+// the e of -ei is long after a vowel, ex: diēī, but reī
+fn fifth_declension_ei(stem: &str) -> (&'static str, &'static str) {
+    match stem
+        .trim_end_matches("ei")
+        .ends_with(['a', 'e', 'i', 'o', 'u'])
+    {
+        true => ("ei", "ēī"),
+        false => ("ei", "eī"),
+    }
+}
+
+fn mark_end(word: &mut String, ending: &str, marked: &str) {
+    if let Some(stem) = word.strip_suffix(ending) {
+        *word = format!("{}{}", stem, marked);
+    }
+}